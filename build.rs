@@ -1,9 +1,9 @@
-use std::path::Path;
-
 fn main() {
     // 仅在 Windows 平台上配置图标
     #[cfg(target_os = "windows")]
     {
+        use std::path::Path;
+
         let mut res = winres::WindowsResource::new();
 
         // 设置应用程序图标
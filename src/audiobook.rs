@@ -0,0 +1,91 @@
+use crate::cue;
+use crate::i18n::{Msg, tr};
+use crate::playlist::canonical_key;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const STATE_DIR: &str = ".beatcli";
+const STATE_FILE: &str = "audiobook_state.json";
+
+/// 有声书模式允许的播放速度范围
+pub const MIN_SPEED: f32 = 0.5;
+pub const MAX_SPEED: f32 = 3.0;
+
+/// cue 文件中的一个章节
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub start: Duration,
+}
+
+/// 读取与歌曲同名的 .cue 文件中的章节，按开始时间排序。
+/// 没有 cue 文件或章节少于两个时返回空列表
+pub fn load_chapters(path: &Path) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = cue::load(&path.with_extension("cue"))
+        .into_iter()
+        .enumerate()
+        .map(|(i, track)| Chapter {
+            title: track
+                .title
+                .unwrap_or_else(|| tr!(Msg::ChapterDefaultTitle, i + 1)),
+            start: track.start,
+        })
+        .collect();
+    chapters.sort_by_key(|c| c.start);
+    if chapters.len() < 2 {
+        chapters.clear();
+    }
+    chapters
+}
+
+/// 位置所在章节的下标
+pub fn chapter_index(chapters: &[Chapter], position: Duration) -> Option<usize> {
+    chapters.iter().rposition(|c| c.start <= position)
+}
+
+/// 有声书模式下每个文件的播放速度：规范路径 -> 速度
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AudiobookState {
+    pub speeds: HashMap<PathBuf, f32>,
+}
+
+impl AudiobookState {
+    /// 状态文件路径：~/.beatcli/audiobook_state.json
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|dir| dir.join(STATE_DIR).join(STATE_FILE))
+    }
+
+    /// 读取状态文件，文件不存在或无法解析时返回空状态
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!(tr!(Msg::NoHomeDir)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 文件上次使用的速度
+    pub fn speed(&self, path: &Path) -> Option<f32> {
+        self.speeds.get(&canonical_key(path)).copied()
+    }
+
+    pub fn set_speed(&mut self, path: &Path, speed: f32) {
+        self.speeds.insert(canonical_key(path), speed);
+    }
+}
+
+/// 速度的显示文本，例如 1.25x、1x
+pub fn format_speed(speed: f32) -> String {
+    format!("{}x", (speed * 100.0).round() / 100.0)
+}
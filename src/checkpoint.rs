@@ -0,0 +1,126 @@
+use crate::i18n::{Msg, tr};
+use crate::playlist::PlaybackMode;
+use crate::ui::UiState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+const CHECKPOINT_DIR: &str = ".beatcli";
+const CHECKPOINT_FILE: &str = ".checkpoint.json";
+
+/// 写入检查点的间隔
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(60);
+// 检查点超过这个时间就不再提示恢复
+const MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// 播放中定期保存的会话状态，程序异常退出后下次启动时用来恢复；正常退出时删除
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub now_index: Option<usize>, // 正在播放的歌曲在列表中的下标
+    pub current_ms: u64,          // 播放位置（毫秒）
+    pub volume: Option<u8>,
+    pub mode: PlaybackMode,
+    pub show_lyrics: bool,
+    pub lyrics_stream_mode: bool,
+    pub items: Vec<PathBuf>, // 播放列表
+    pub current: Option<usize>,
+}
+
+impl Checkpoint {
+    /// 检查点文件路径：~/.beatcli/.checkpoint.json
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|dir| dir.join(CHECKPOINT_DIR).join(CHECKPOINT_FILE))
+    }
+
+    /// 由界面状态和播放列表（items、current）生成检查点
+    pub fn capture(ui: &UiState, items: Vec<PathBuf>, current: Option<usize>) -> Self {
+        Self {
+            now_index: ui.now_index,
+            current_ms: ui.current_ms as u64,
+            volume: ui.volume,
+            mode: ui.mode,
+            show_lyrics: ui.show_lyrics,
+            lyrics_stream_mode: ui.lyrics_stream_mode,
+            items,
+            current,
+        }
+    }
+
+    /// 先写入临时文件再改名，写到一半时退出也不会留下损坏的检查点
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!(tr!(Msg::NoHomeDir)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// 读取 5 分钟内写入的检查点，没有、过旧或无法解析时返回 None
+    pub fn load_recent() -> Option<Self> {
+        let path = Self::path()?;
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        if !is_recent(modified, SystemTime::now()) {
+            return None;
+        }
+        let content = fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn remove() {
+        if let Some(path) = Self::path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+// 检查点是否在 MAX_AGE 之内写入；修改时间晚于当前时间（时钟回拨）时视为过期
+fn is_recent(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified).is_ok_and(|age| age <= MAX_AGE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::Playlist;
+
+    #[test]
+    fn round_trip_restores_the_session() {
+        let ui = UiState {
+            now_index: Some(1),
+            current_ms: 42_500,
+            volume: Some(70),
+            mode: PlaybackMode::Shuffle,
+            show_lyrics: false,
+            lyrics_stream_mode: true,
+            ..UiState::default()
+        };
+        let items = vec![PathBuf::from("a.mp3"), PathBuf::from("b 文件.flac")];
+        let checkpoint = Checkpoint::capture(&ui, items.clone(), Some(1));
+
+        let json = serde_json::to_string_pretty(&checkpoint).unwrap();
+        let loaded: Checkpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, checkpoint);
+
+        let mut pl = Playlist::default();
+        pl.restore(loaded.items, loaded.current, loaded.mode);
+        assert_eq!(pl.items, items);
+        assert_eq!(pl.current, Some(1));
+        assert_eq!(pl.mode, PlaybackMode::Shuffle);
+    }
+
+    #[test]
+    fn only_checkpoints_from_the_last_five_minutes_are_recent() {
+        let now = SystemTime::now();
+        let ago = |secs| now - Duration::from_secs(secs);
+        assert!(is_recent(ago(0), now));
+        assert!(is_recent(ago(4 * 60), now));
+        assert!(is_recent(ago(5 * 60), now));
+        assert!(!is_recent(ago(5 * 60 + 1), now));
+        // 修改时间在未来（时钟回拨）
+        assert!(!is_recent(now + Duration::from_secs(60), now));
+    }
+}
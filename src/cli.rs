@@ -0,0 +1,225 @@
+use crate::command::parse_mode;
+use crate::i18n::{Lang, Msg, tr};
+use crate::player::Player;
+use crate::playlist::{PlaybackMode, resolve_path};
+use crate::ui::format_time;
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+// 进程退出码
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILURE: i32 = 1; // 播放器初始化失败、脚本执行出错等
+pub const EXIT_USAGE: i32 = 2; // 命令行参数错误
+pub const EXIT_NOT_FOUND: i32 = 3; // 文件不存在
+pub const EXIT_DECODE: i32 = 4; // 文件无法打开或解码
+pub const EXIT_INTERRUPTED: i32 = 130; // 被 Ctrl+C 中断
+
+/// 命令行参数
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    pub once: Option<String>,
+    pub volume: Option<u8>,
+    pub duration: Option<Duration>,
+    pub script: Option<String>,
+    pub keep_going: bool,
+    pub quit_after: bool,
+    pub lang: Option<Lang>,
+    pub folder: Option<String>, // --folder 或位置参数
+    pub play: Option<usize>,    // 启动后播放的歌曲序号（从 1 开始）
+    pub mode: Option<PlaybackMode>,
+    pub vis: bool,      // 在播放界面显示频谱面板
+    pub tui: bool,      // 使用全屏界面
+    pub quiet: bool,    // 播放时只显示一行状态
+    pub no_color: bool, // 不输出颜色
+    pub verbose: bool,  // 把调试日志写入 ~/.beatcli/beatcli.log
+    pub yes: bool,      // 脚本和管道输入中自动确认 /clear 等需要确认的命令
+    pub help: bool,
+}
+
+/// 解析命令行参数（不含程序名），支持 `--x v` 和 `--x=v` 两种写法。
+/// 不以 - 开头的参数视为要加载的文件夹
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliArgs, String> {
+    let mut cli = CliArgs::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg, None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| tr!(Msg::CliMissingValue, name))
+        };
+        match flag.as_str() {
+            "-h" | "--help" => cli.help = true,
+            "--once" => cli.once = Some(value("--once")?),
+            "--volume" => {
+                let v = value("--volume")?;
+                match v.parse::<u8>() {
+                    Ok(n) if n <= 100 => cli.volume = Some(n),
+                    _ => return Err(tr!(Msg::CliInvalidVolume, v)),
+                }
+            }
+            "--duration" => {
+                let v = value("--duration")?;
+                match v.parse::<u64>() {
+                    Ok(n) if n > 0 => cli.duration = Some(Duration::from_secs(n)),
+                    _ => return Err(tr!(Msg::CliInvalidDuration, v)),
+                }
+            }
+            "--script" => cli.script = Some(value("--script")?),
+            "--keep-going" => cli.keep_going = true,
+            "--vis" => cli.vis = true,
+            "--tui" => cli.tui = true,
+            "--quiet" => cli.quiet = true,
+            "--no-color" => cli.no_color = true,
+            "--verbose" => cli.verbose = true,
+            "-y" | "--yes" => cli.yes = true,
+            "--quit-after" => cli.quit_after = true,
+            "--lang" => {
+                let v = value("--lang")?;
+                match Lang::parse(&v) {
+                    Some(lang) => cli.lang = Some(lang),
+                    None => return Err(tr!(Msg::CliInvalidLang, v)),
+                }
+            }
+            "--folder" => set_folder(&mut cli, value("--folder")?)?,
+            "--play" => {
+                let v = value("--play")?;
+                match v.parse::<usize>() {
+                    Ok(n) if n > 0 => cli.play = Some(n),
+                    _ => return Err(tr!(Msg::CliInvalidPlay, v)),
+                }
+            }
+            "--mode" => {
+                let v = value("--mode")?;
+                match parse_mode(&v) {
+                    Some(mode) => cli.mode = Some(mode),
+                    None => return Err(tr!(Msg::CliInvalidMode, v)),
+                }
+            }
+            _ if !flag.starts_with('-') => set_folder(&mut cli, flag)?,
+            _ => return Err(tr!(Msg::CliUnknownOption, flag)),
+        }
+    }
+    if cli.duration.is_some() && cli.once.is_none() {
+        return Err(tr!(Msg::CliDurationNeedsOnce));
+    }
+    if (cli.keep_going || cli.quit_after) && cli.script.is_none() {
+        return Err(tr!(Msg::CliScriptOnly));
+    }
+    if cli.once.is_some() && cli.script.is_some() {
+        return Err(tr!(Msg::CliOnceAndScript));
+    }
+    if cli.once.is_some() && (cli.folder.is_some() || cli.play.is_some() || cli.mode.is_some()) {
+        return Err(tr!(Msg::CliOnceConflict));
+    }
+    Ok(cli)
+}
+
+// 文件夹只能指定一次（--folder 和位置参数二选一）
+fn set_folder(cli: &mut CliArgs, folder: String) -> Result<(), String> {
+    if cli.folder.is_some() {
+        return Err(tr!(Msg::CliExtraArgument, folder));
+    }
+    cli.folder = Some(folder);
+    Ok(())
+}
+
+/// 在完整解析之前找出 --lang，使参数错误也能用所选语言显示
+pub fn lang_arg(args: &[String]) -> Option<Lang> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(v) = arg.strip_prefix("--lang=") {
+            return Lang::parse(v);
+        }
+        if arg == "--lang" {
+            return iter.next().and_then(|v| Lang::parse(v));
+        }
+    }
+    None
+}
+
+/// 命令行用法说明
+pub fn usage() -> String {
+    tr!(Msg::CliUsage)
+}
+
+/// 非交互模式：播放一个文件直到结束、达到 --duration 或按下 Ctrl+C，返回退出码
+pub fn run_once(raw_path: &str, cli: &CliArgs) -> i32 {
+    let path = resolve_path(raw_path);
+    if !path.is_file() {
+        eprintln!("{}", tr!(Msg::CliFileNotFound, path.display()));
+        return EXIT_NOT_FOUND;
+    }
+
+    let mut player = match Player::new() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{}", tr!(Msg::PlayerInitFailed, e));
+            return EXIT_FAILURE;
+        }
+    };
+    let total = match player.try_play_file(&path) {
+        Ok(total) => total,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                tr!(Msg::ErrorLine, format!("{}: {}", path.display(), e))
+            );
+            return EXIT_DECODE;
+        }
+    };
+    player.set_volume(cli.volume.unwrap_or(50) as f32 / 100.0);
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    let total_text = total.map(format_time).unwrap_or_else(|| tr!(Msg::Unknown));
+    println!("{}", tr!(Msg::CliPlaying, name));
+    println!("{}", tr!(Msg::CliDuration, total_text));
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    {
+        let interrupted = interrupted.clone();
+        // 设置失败（例如已有处理器）时仍可播放，只是 Ctrl+C 会直接结束进程
+        let _ = ctrlc::set_handler(move || interrupted.store(true, Ordering::SeqCst));
+    }
+
+    let show_progress = io::stdout().is_terminal();
+    let code = loop {
+        if interrupted.load(Ordering::SeqCst) {
+            break EXIT_INTERRUPTED;
+        }
+        if player.finished() {
+            break EXIT_OK;
+        }
+        let elapsed = Duration::from_millis(player.get_current_ms() as u64);
+        if cli.duration.is_some_and(|limit| elapsed >= limit) {
+            break EXIT_OK;
+        }
+        if show_progress {
+            print!("\r{} / {}", format_time(elapsed), total_text);
+            let _ = io::stdout().flush();
+        }
+        thread::sleep(Duration::from_millis(200));
+    };
+    player.stop();
+    if show_progress {
+        println!();
+    }
+    if code == EXIT_INTERRUPTED {
+        println!("{}", tr!(Msg::CliInterrupted));
+    }
+    code
+}
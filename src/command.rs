@@ -1,109 +1,1934 @@
-use crate::playlist::PlaybackMode;
+use crate::audiobook::{MAX_SPEED, MIN_SPEED};
+use crate::i18n::{Lang, Msg, command_doc_en, lang, tr};
+use crate::playlist::{ListFormat, PlaybackMode, SortKey};
+use crate::replaygain::RgMode;
+use crate::ui::{BorderStyle, FlashLevel};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
 
 #[derive(Debug, Clone)]
 pub enum Command {
-    Help,
+    Help(Option<String>), // 要查看详细说明的命令
     Quit,
-    Folder(String),
-    List,
+    Folder(String, FolderFlags), // 路径, 扫描选项
+    Reload,                      // 重新扫描当前文件夹，保留播放状态
+    List(ListFormat),            // 列出当前歌曲所在的一页
+    ListPage(usize, ListFormat), // 列出第 N 页（从 1 开始）
+    ListPageSize(usize),         // /list 每页的歌曲数
     Search(String),
     PlayIndex(usize),
+    PlayName(String),        // 按名称播放最匹配的一首
+    PlayRange(usize, usize), // 播放第 from 首，其后到第 to 首依次加入队列（从 1 开始，含两端）
+    Goto(usize),             // 定位列表位置但不播放
+    PlayRandom,              // 立即随机播放一首
+    Sample(usize, bool),     // 随机抽取 N 首加入队列（0 表示清空队列）, 是否循环抽取
     Pause,
     Resume,
+    Continue,          // 从上次保存的位置继续播放当前歌曲
+    Seek(Duration),    // 跳转到当前歌曲的指定位置
+    SeekRelative(i64), // 相对当前位置前后跳转的秒数
     Next,
     Prev,
     Mode(PlaybackMode),
+    ShowMode,      // 显示当前播放模式和可选的模式
+    AudiobookMode, // 切换到有声书模式，使用当前文件上次的速度
+    Speed(f32),    // 有声书模式下的播放速度
+    Shuffle,       // 一次性打乱列表顺序
+    Sort(SortKey),
+    Dedup(bool),                     // 是否只删除路径完全相同的重复项
+    Clear,                           // 清空播放列表并停止播放
+    Undo,                            // 撤销最近一次打乱、排序、去重、删除或清空
+    RemoveRange(usize, usize),       // 删除第 from 到第 to 首（从 1 开始，含两端）
+    Add(String),                     // 追加文件或文件夹到播放列表
+    Merge(String),                   // 合并播放列表文件
+    CopyPlaylist(String, CopyFlags), // 把播放列表中的歌曲复制到另一个文件夹
+    Snapshot,                        // 保存播放列表快照
+    Diff,                            // 与快照比较文件夹变化
     Volume(u8),
-    Lyrics,     // 切换歌词显示
-    LyricsMode, // 切换歌词显示模式（流式 vs 清屏）
-    Now,        // 显示当前播放信息
-    Unknown(String),
+    VolumeUp(u8),          // 按步长增大音量
+    VolumeDown(u8),        // 按步长减小音量
+    Lyrics,                // 切换歌词显示
+    LyricsMode,            // 切换歌词显示模式（流式 vs 清屏）
+    LyricsExport(String),  // 把当前歌曲的歌词导出为 LRC 文件
+    Confirm(bool),         // 对等待确认的命令回答 y 或 n
+    Vu(Option<bool>),      // 显示或隐藏 VU 表，None 表示切换
+    Viz(Option<bool>),     // 显示或隐藏频谱面板，None 表示切换
+    Compact(Option<bool>), // 开启或关闭紧凑显示，None 表示切换
+    Quiet(Option<bool>),   // 开启或关闭安静模式，None 表示切换
+    Debug(Option<bool>),   // 显示或隐藏调试信息，None 表示切换
+    Pane(Option<bool>),    // 显示或隐藏播放界面中的列表面板，None 表示切换
+    Watch(Option<bool>),   // 开启或关闭文件夹监视，None 表示切换
+    Lang(Lang),            // 切换界面语言
+    Theme(String),         // 切换配色方案（内置方案或配置中定义的方案名）
+    Border(BorderStyle),   // 切换标题方框和分隔线的样式
+    ReplayGain(RgMode),    // 切换 ReplayGain 音量归一化模式
+    FadeIn(Duration),      // 新歌曲开始时的淡入时长，0 为关闭
+    FadeOut(Duration),     // 歌曲结尾的淡出时长，0 为关闭
+    DeviceList,
+    DeviceSelect(String), // 切换到指定名称的输出设备
+    Now,                  // 显示当前播放信息
+    Info,                 // 显示当前歌曲的格式、采样率等技术信息和全部标签
+    AliasList,
+    AliasSet(String, String), // 别名, 展开后的命令
+    AliasRemove(String),      // 删除别名
+    Source(String),           // 逐行执行脚本文件中的命令
+    Sleep(Duration),          // 暂停读取后续命令，用于脚本和管道输入
+    ConfigShow,               // 显示当前生效的配置
+    Noop,                     // 空命令（如单独的 /），静默忽略
 }
 
-pub fn parse_command(line: &str) -> Command {
+/// 命令解析失败的原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// 不是已知的命令，suggestion 为相近命令的提示
+    UnknownCommand {
+        input: String,
+        suggestion: Option<String>,
+    },
+    /// 命令正确但缺少必需的参数
+    MissingArgument {
+        cmd: &'static str,
+        usage: &'static str,
+    },
+    /// 命令正确但参数无法识别
+    InvalidArgument {
+        cmd: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+    /// 命令名的前缀同时匹配多个命令
+    AmbiguousCommand {
+        input: String,
+        candidates: Vec<String>,
+    },
+    /// 别名展开失败（如循环引用）
+    InvalidAlias(String),
+}
+
+impl ParseError {
+    fn missing(cmd: &'static str) -> Self {
+        ParseError::MissingArgument {
+            cmd,
+            usage: usage_of(cmd),
+        }
+    }
+
+    fn invalid(cmd: &'static str, value: &str, expected: &'static str) -> Self {
+        ParseError::InvalidArgument {
+            cmd,
+            value: value.to_string(),
+            expected,
+        }
+    }
+
+    /// 提示信息的级别：缺少参数只是提示用法，其余按错误显示
+    pub fn level(&self) -> FlashLevel {
+        match self {
+            ParseError::MissingArgument { .. } => FlashLevel::Info,
+            _ => FlashLevel::Error,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnknownCommand { input, suggestion } => {
+                f.write_str(&tr!(Msg::UnknownCommand, input))?;
+                if let Some(s) = suggestion {
+                    f.write_str(&tr!(Msg::DidYouMean, s))?;
+                }
+                f.write_str(Msg::HelpHint.text())
+            }
+            ParseError::MissingArgument { cmd, usage } => {
+                f.write_str(&tr!(Msg::MissingArgument, cmd, usage))
+            }
+            ParseError::InvalidArgument {
+                cmd,
+                value,
+                expected,
+            } => f.write_str(&tr!(
+                Msg::InvalidArgument,
+                cmd,
+                value,
+                expected,
+                usage_of(cmd)
+            )),
+            ParseError::AmbiguousCommand { input, candidates } => {
+                let candidates: Vec<String> =
+                    candidates.iter().map(|c| format!("/{}", c)).collect();
+                f.write_str(&tr!(Msg::AmbiguousCommand, input, candidates.join(" ")))
+            }
+            ParseError::InvalidAlias(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+/// /folder 路径之后的可选参数，未指定的项使用配置中的值
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FolderFlags {
+    pub depth: Option<usize>, // depth=N 或 norecurse
+    pub symlinks: bool,       // symlinks：跟随符号链接
+    pub hidden: bool,         // hidden：包含隐藏文件
+    pub play: Option<bool>,   // play：扫描到歌曲后立即开始播放
+}
+
+/// /copyto 路径之后的可选参数
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyFlags {
+    pub symlink: bool, // symlink：创建符号链接而不是复制文件
+    pub lrc: bool,     // lrc：同时复制同名的 LRC 歌词文件
+}
+
+// 热键模式下单个按键对应的动作
+#[derive(Debug, Clone)]
+pub enum HotkeyAction {
+    Command(Command),
+    Prompt, // 切回行输入模式执行一条命令
+}
+
+/// /volup、/voldown 与音量热键默认的调节步长
+pub const VOLUME_STEP: u8 = 5;
+/// 热键 ←/→ 每次跳转的秒数
+pub const SEEK_STEP: i64 = 10;
+
+/// 将播放界面中的按键翻译为命令
+pub fn parse_hotkey(key: &KeyEvent, paused: bool) -> Option<HotkeyAction> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        // raw 模式下 Ctrl+C 不再产生信号，按退出处理
+        return match key.code {
+            KeyCode::Char('c') => Some(HotkeyAction::Command(Command::Quit)),
+            _ => None,
+        };
+    }
+    let cmd = match key.code {
+        KeyCode::Char(' ') if paused => Command::Resume,
+        KeyCode::Char(' ') => Command::Pause,
+        KeyCode::Char('n') => Command::Next,
+        KeyCode::Char('p') => Command::Prev,
+        KeyCode::Char('+') | KeyCode::Char('=') => Command::VolumeUp(VOLUME_STEP),
+        KeyCode::Char('-') => Command::VolumeDown(VOLUME_STEP),
+        KeyCode::Left => Command::SeekRelative(-SEEK_STEP),
+        KeyCode::Right => Command::SeekRelative(SEEK_STEP),
+        KeyCode::Char('l') => Command::Lyrics,
+        KeyCode::Char('q') => Command::Quit,
+        KeyCode::Char(':') => return Some(HotkeyAction::Prompt),
+        _ => return None,
+    };
+    Some(HotkeyAction::Command(cmd))
+}
+
+/// 命令说明：/help 的内容和参数错误时的用法提示都来自这里
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub short: &'static [&'static str], // 简写
+    pub usage: &'static str,
+    pub summary: &'static str,
+    pub details: &'static str, // /help <命令> 显示的详细说明
+    pub examples: &'static [&'static str],
+}
+
+impl CommandSpec {
+    /// 当前语言的简介
+    pub fn summary(&self) -> &'static str {
+        match lang() {
+            Lang::Zh => self.summary,
+            Lang::En => command_doc_en(self.name).map_or(self.summary, |(summary, _)| summary),
+        }
+    }
+
+    /// 当前语言的详细说明
+    pub fn details(&self) -> &'static str {
+        match lang() {
+            Lang::Zh => self.details,
+            Lang::En => command_doc_en(self.name).map_or(self.details, |(_, details)| details),
+        }
+    }
+}
+
+/// 内置命令表（按 /help 中的显示顺序），用户别名不能与其中的命令名或简写重名
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "help",
+        short: &[],
+        usage: "/help [command]",
+        summary: "显示帮助",
+        details: "不带参数时列出所有命令；/help <命令> 显示该命令的详细说明和示例，命令名可以带或不带 /，也可以使用简写。",
+        examples: &["/help", "/help mode"],
+    },
+    CommandSpec {
+        name: "folder",
+        short: &["f"],
+        usage: "/folder <path> [depth=N|norecurse] [symlinks] [hidden] [play]",
+        summary: "选择音乐文件夹",
+        details: "扫描文件夹（默认递归所有子文件夹），用找到的歌曲替换播放列表，播放模式恢复为配置中的默认值。\n路径中有空格时用双引号或单引号包围，或用反斜杠转义空格；支持 ~、$VAR 和 %VAR% 展开。\n可选参数: depth=N 限制递归层数，norecurse 只扫描当前目录，symlinks 跟随符号链接，hidden 包含隐藏文件，play（或 --play）扫描到歌曲后立即开始播放（随机模式下随机选一首），默认值见配置项 playback.play_after_scan。",
+        examples: &[
+            "/folder D:\\Music",
+            "/folder \"C:\\My Music\\新专辑\" depth=2",
+            "/folder ~/Music norecurse hidden",
+        ],
+    },
+    CommandSpec {
+        name: "reload",
+        short: &[],
+        usage: "/reload",
+        summary: "重新扫描当前文件夹，不打断播放",
+        details: "使用上次 /folder 的参数重新扫描同一文件夹: 新歌曲追加到列表末尾，已删除的歌曲被移除，正在播放的歌曲和播放模式保持不变。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "watch",
+        short: &[],
+        usage: "/watch [on|off]",
+        summary: "监视文件夹变化并自动更新播放列表",
+        details: "开启后监视当前文件夹，文件增加、删除或重命名时自动更新播放列表，不打断播放。\n短时间内的连续变化（如批量复制）会合并后一次更新。不带参数时切换，默认值见配置项 scanning.watch。",
+        examples: &["/watch on", "/watch off"],
+    },
+    CommandSpec {
+        name: "list",
+        short: &["ls"],
+        usage: "/list [page] [compact|normal|wide]",
+        summary: "分页列出播放列表",
+        details: "列出播放列表中的一页歌曲，>> 标记当前歌曲。不指定页码时显示当前歌曲所在的一页。每页默认 20 首，可以用 /listpage 修改。\n显示格式：compact 只显示文件名（默认），normal 增加时长，wide 增加艺术家和时长。",
+        examples: &["/list", "/list 2", "/list wide"],
+    },
+    CommandSpec {
+        name: "listpage",
+        short: &[],
+        usage: "/listpage <N>",
+        summary: "设置 /list 每页显示的歌曲数",
+        details: "设置 /list 每页显示的歌曲数。",
+        examples: &["/listpage 30"],
+    },
+    CommandSpec {
+        name: "snapshot",
+        short: &[],
+        usage: "/snapshot",
+        summary: "保存播放列表快照",
+        details: "把播放列表中每个文件的修改时间保存到 ~/.beatcli/snapshot.json，之后可以用 /diff 比较。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "diff",
+        short: &[],
+        usage: "/diff",
+        summary: "显示文件夹相对快照的变化",
+        details: "重新扫描当前文件夹，列出相对上次 /snapshot 新增、删除和修改过的歌曲。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "add",
+        short: &[],
+        usage: "/add <path>",
+        summary: "追加歌曲文件或文件夹",
+        details: "追加单个歌曲文件或整个文件夹（递归）到播放列表末尾，已在列表中的歌曲会被跳过。路径规则与 /folder 相同。",
+        examples: &["/add D:\\Music\\new.mp3", "/add \"D:\\My Music\\新专辑\""],
+    },
+    CommandSpec {
+        name: "merge",
+        short: &[],
+        usage: "/merge <file>",
+        summary: "合并 m3u/m3u8/xspf 播放列表或 JSON 会话",
+        details: "读取 m3u、m3u8 或 xspf 播放列表文件，或含 items 列表的 JSON 会话文件（如 ~/.beatcli/.checkpoint.json），把其中存在且支持的歌曲追加到播放列表，跳过重复项。",
+        examples: &["/merge D:\\list.m3u"],
+    },
+    CommandSpec {
+        name: "copyto",
+        short: &[],
+        usage: "/copyto <path> [symlink] [lrc]",
+        summary: "把播放列表中的歌曲复制到文件夹",
+        details: "把播放列表中的歌曲按顺序复制到指定文件夹，文件夹不存在时自动创建，例如用来准备 U 盘。\n目标文件夹中已有同名文件时跳过该歌曲。\nsymlink（或 --symlink）创建指向原文件的符号链接而不是复制；lrc（或 --copy-lrc）同时复制同名的 LRC 歌词文件。",
+        examples: &["/copyto E:\\Music", "/copyto ~/usb/list symlink lrc"],
+    },
+    CommandSpec {
+        name: "playlist",
+        short: &[],
+        usage: "/playlist merge <file>",
+        summary: "同 /merge",
+        details: "播放列表文件相关的子命令，目前只有 merge，与 /merge 相同。",
+        examples: &["/playlist merge D:\\list.xspf"],
+    },
+    CommandSpec {
+        name: "search",
+        short: &[],
+        usage: "/search <keyword>",
+        summary: "搜索歌曲",
+        details: "按文件名搜索播放列表（不区分大小写），列出匹配的歌曲和序号。",
+        examples: &["/search 夜曲"],
+    },
+    CommandSpec {
+        name: "play",
+        short: &[],
+        usage: "/play <N|from-to|name>",
+        summary: "播放第 N 首(从1开始)或按名称播放，默认播放第一首",
+        details: "播放第 N 首歌曲（从 1 开始），省略 N 时播放第一首。这首歌上次没有播完时，会提示用 /continue 从上次位置继续。\n参数为范围（如 3-7）时播放第 3 首，第 4-7 首依次加入播放队列。\n参数不是数字时按文件名查找（不区分大小写）：名称完全相同的优先，其次是以它开头的，最后是包含它的。最匹配的只有一首时直接播放，有多首时列出候选。",
+        examples: &["/play", "/play 3", "/play 3-7", "/play Bohemian"],
+    },
+    CommandSpec {
+        name: "random",
+        short: &["rand"],
+        usage: "/random",
+        summary: "随机播放一首",
+        details: "立即随机播放一首（不会选中当前歌曲），不改变播放模式。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "sample",
+        short: &[],
+        usage: "/sample <N|off>",
+        summary: "随机抽取 N 首歌曲加入播放队列",
+        details: "随机抽取 N 首不重复的歌曲加入播放队列。队列中的歌曲优先于播放模式依次播放，播完后恢复原来的播放模式；N 超过列表长度时抽取全部歌曲。/sample off 清空队列。",
+        examples: &["/sample 10", "/sample off"],
+    },
+    CommandSpec {
+        name: "sampleloop",
+        short: &[],
+        usage: "/sampleloop <N>",
+        summary: "同 /sample，队列播完后重新抽取",
+        details: "与 /sample 相同，但队列播完后自动重新抽取 N 首，直到 /sample off 或重新扫描文件夹。",
+        examples: &["/sampleloop 5"],
+    },
+    CommandSpec {
+        name: "goto",
+        short: &[],
+        usage: "/goto <N>",
+        summary: "定位到第 N 首但不播放",
+        details: "把列表位置移到第 N 首，但不打断正在播放的歌曲，之后从这里继续往下播放。",
+        examples: &["/goto 42"],
+    },
+    CommandSpec {
+        name: "pause",
+        short: &[],
+        usage: "/pause",
+        summary: "暂停",
+        details: "暂停当前歌曲。播放界面中也可以按空格。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "resume",
+        short: &[],
+        usage: "/resume",
+        summary: "继续",
+        details: "继续播放暂停的歌曲。播放界面中也可以按空格。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "continue",
+        short: &[],
+        usage: "/continue",
+        summary: "从上次停止的位置继续播放当前歌曲",
+        details: "从上次停止的位置继续播放当前歌曲。播放位置每 30 秒以及切换歌曲、退出时自动保存；配置 auto_resume_position = true 时会自动继续。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "seek",
+        short: &[],
+        usage: "/seek <time|+N|-N>",
+        summary: "跳转到指定位置，或前后跳转 N 秒",
+        details: "跳转到当前歌曲的指定位置（如 1:30、90s），+N / -N 从当前位置向后或向前跳转 N 秒。跳到开头之前时从头播放，跳过结尾时按播放模式切换到下一首。播放界面中也可以按 ← / →。",
+        examples: &["/seek 1:30", "/seek +30", "/seek -15"],
+    },
+    CommandSpec {
+        name: "next",
+        short: &[],
+        usage: "/next",
+        summary: "下一首",
+        details: "播放下一首: 队列中有歌曲时优先播放队列，否则按播放模式选择。播放界面中也可以按 n。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "prev",
+        short: &["back"],
+        usage: "/prev",
+        summary: "上一首",
+        details: "播放上一首，随机模式下随机选择一首。已经是第一首时不会跳到最后一首。播放界面中也可以按 p。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "mode",
+        short: &["m"],
+        usage: "/mode [Sequential|RepeatOne|Shuffle]",
+        summary: "切换播放模式",
+        details: "切换歌曲播完后的播放顺序，不带参数时显示当前模式:\n  sequential (seq)  顺序播放，播完最后一首后回到第一首\n  repeatone (one)   单曲循环，一直重复当前歌曲\n  shuffle (shu)     随机播放，一轮中每首歌只播放一次",
+        examples: &["/mode", "/mode shuffle", "/m one"],
+    },
+    CommandSpec {
+        name: "abmode",
+        short: &[],
+        usage: "/abmode",
+        summary: "切换到有声书模式",
+        details: "有声书模式按顺序播放，可以用 /speed 调整播放速度（每个文件分别记住，保存在 ~/.beatcli/audiobook_state.json），再次播放时自动从上次位置继续。\n歌曲旁有同名的 .cue 文件时，/next 和 /prev 在章节之间跳转。用 /mode 切换到其他模式即可退出。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "speed",
+        short: &[],
+        usage: "/speed <0.5..3.0>",
+        summary: "设置有声书模式的播放速度",
+        details: "设置当前文件在有声书模式下的播放速度，下次播放这个文件时沿用。新文件的默认速度由配置 playback.audiobook_speed 决定（默认 1.25）。",
+        examples: &["/speed 1.5", "/speed 1"],
+    },
+    CommandSpec {
+        name: "shuffle",
+        short: &[],
+        usage: "/shuffle",
+        summary: "随机打乱播放列表顺序",
+        details: "一次性随机打乱播放列表的顺序。与随机播放模式不同，打乱后仍按列表顺序播放。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "sort",
+        short: &[],
+        usage: "/sort <track|name>",
+        summary: "按音轨号或文件名排序",
+        details: "按文件名中的音轨号（同一文件夹内）或按文件名排序，省略参数时按音轨号排序。",
+        examples: &["/sort", "/sort name"],
+    },
+    CommandSpec {
+        name: "dedup",
+        short: &[],
+        usage: "/dedup [strict]",
+        summary: "删除重复歌曲(strict 只比较路径)",
+        details: "删除重复的歌曲。默认把去掉音轨号后同名的文件也视为重复；strict 只删除指向同一文件的重复项。",
+        examples: &["/dedup", "/dedup strict"],
+    },
+    CommandSpec {
+        name: "clear",
+        short: &[],
+        usage: "/clear",
+        summary: "清空播放列表",
+        details: "停止播放并清空播放列表，回到初始界面。播放模式和扫描设置保持不变，之后可用 /folder 或 /add 重新添加歌曲。",
+        examples: &["/clear"],
+    },
+    CommandSpec {
+        name: "remove",
+        short: &[],
+        usage: "/remove <N|from-to>",
+        summary: "从播放列表删除第 N 首或一段歌曲",
+        details: "从播放列表中删除第 N 首，或第 from 到第 to 首（含两端）歌曲，文件本身不受影响。删除了正在播放的歌曲时，接着播放删除范围之后的一首。可以用 /undo 恢复。",
+        examples: &["/remove 5", "/remove 5-10"],
+    },
+    CommandSpec {
+        name: "undo",
+        short: &[],
+        usage: "/undo",
+        summary: "撤销最近一次列表操作",
+        details: "撤销最近一次 /shuffle、/sort、/dedup、/remove 或 /clear，恢复操作前的播放列表顺序，最多可连续撤销 10 次。扫描新文件夹后之前的操作不能再撤销。",
+        examples: &["/undo"],
+    },
+    CommandSpec {
+        name: "volume",
+        short: &["vol"],
+        usage: "/volume <0..100>",
+        summary: "设置音量",
+        details: "把音量设置为 0-100。没有播放时也可以设置，下次播放时生效。",
+        examples: &["/volume 80"],
+    },
+    CommandSpec {
+        name: "volup",
+        short: &[],
+        usage: "/volup [N]",
+        summary: "音量增加 N(默认 5)",
+        details: "音量增加 N（默认 5），最大 100。播放界面中也可以按 +。",
+        examples: &["/volup", "/volup 10"],
+    },
+    CommandSpec {
+        name: "voldown",
+        short: &[],
+        usage: "/voldown [N]",
+        summary: "音量减少 N(默认 5)",
+        details: "音量减少 N（默认 5），最小 0。播放界面中也可以按 -。",
+        examples: &["/voldown 10"],
+    },
+    CommandSpec {
+        name: "lyrics",
+        short: &["lrc"],
+        usage: "/lyrics",
+        summary: "切换歌词显示",
+        details: "显示或隐藏歌词，歌词从与歌曲同名的 .lrc 文件加载，切换后的设置在换歌时保持。播放界面中也可以按 l。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "lmode",
+        short: &["lm"],
+        usage: "/lmode",
+        summary: "切换歌词显示模式(流式/清屏)",
+        details: "在流式输出（只重写播放界面中内容变化的行，不闪烁）和清屏刷新两种显示方式之间切换。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "lrcexport",
+        short: &[],
+        usage: "/lrcexport <path|.>",
+        summary: "把当前歌曲的歌词导出为 LRC 文件",
+        details: "把当前歌曲已加载的歌词（含标题、艺术家、专辑标签）写成标准 LRC 文件。\n路径为文件夹时使用与歌曲同名的文件名；为 . 时写到歌曲旁边的同名 LRC 文件（覆盖原文件）。",
+        examples: &["/lrcexport D:\\lyrics\\fixed.lrc", "/lrcexport ."],
+    },
+    CommandSpec {
+        name: "vu",
+        short: &[],
+        usage: "/vu [on|off]",
+        summary: "显示或隐藏音量电平表",
+        details: "在播放界面显示或隐藏左右声道的电平表，不带参数时切换。",
+        examples: &["/vu on", "/vu off"],
+    },
+    CommandSpec {
+        name: "viz",
+        short: &[],
+        usage: "/viz [on|off]",
+        summary: "显示或隐藏频谱面板",
+        details: "在播放界面的状态区域下方显示各频段强度的柱状图，正在播放的歌曲立即生效，不带参数时切换。会增加一些 CPU 占用，启动时也可以用 --vis 开启。",
+        examples: &["/viz", "/viz off"],
+    },
+    CommandSpec {
+        name: "compact",
+        short: &[],
+        usage: "/compact [on|off]",
+        summary: "开启或关闭紧凑显示",
+        details: "紧凑显示只用一行显示播放状态和时间，不画边框，歌词在其下方逐行显示。终端窄于 40 列时自动开启，用此命令可强制开关，不带参数时切换。",
+        examples: &["/compact", "/compact off"],
+    },
+    CommandSpec {
+        name: "quiet",
+        short: &[],
+        usage: "/quiet [on|off]",
+        summary: "开启或关闭安静模式",
+        details: "安静模式下播放界面只剩一行原地更新的状态（序号、歌名、时间和音量），提示消息逐行输出，不显示歌词。不带参数时切换，启动时也可以用 --quiet 开启。全屏界面（--tui）下不可用。",
+        examples: &["/quiet", "/quiet off"],
+    },
+    CommandSpec {
+        name: "pane",
+        short: &[],
+        usage: "/pane [on|off]",
+        summary: "显示或隐藏播放列表面板",
+        details: "在播放界面的状态区域下方显示当前歌曲前后的几首歌，当前歌曲前有 ▶ 标记。终端较矮时可以关闭，不带参数时切换。",
+        examples: &["/pane off", "/pane on"],
+    },
+    CommandSpec {
+        name: "lang",
+        short: &[],
+        usage: "/lang <zh|en>",
+        summary: "切换界面语言",
+        details: "在中文（zh）和英文（en）之间切换所有提示、帮助和播放界面的语言。默认值见配置项 ui.language 或命令行参数 --lang。",
+        examples: &["/lang en", "/lang zh"],
+    },
+    CommandSpec {
+        name: "theme",
+        short: &[],
+        usage: "/theme <name>",
+        summary: "切换配色方案",
+        details: "切换界面的配色方案并重绘播放界面：default 为默认配色，mono 不使用颜色，solarized 使用 Solarized 配色，也可以使用配置中 [ui.themes.<名称>] 定义的方案。启动时的方案见配置项 ui.theme，[ui.colors] 中的单项颜色只在启动时生效。",
+        examples: &["/theme solarized", "/theme mono", "/theme light"],
+    },
+    CommandSpec {
+        name: "fadein",
+        short: &[],
+        usage: "/fadein <time>",
+        summary: "设置新歌曲开始时的淡入时长",
+        details: "开始播放新歌曲时音量从 0 逐渐升到设定的音量，避免声音突然出现。时间可以写成 500ms、1s 等，0 为关闭。跳转和切换输出设备时不淡入。",
+        examples: &["/fadein 500ms", "/fadein 0"],
+    },
+    CommandSpec {
+        name: "fadeout",
+        short: &[],
+        usage: "/fadeout <time>",
+        summary: "设置歌曲结尾的淡出时长",
+        details: "距离歌曲结尾不足设定的时长时音量逐渐降到 0。时间可以写成 500ms、2s 等，0 为关闭。单曲循环模式下不淡出。",
+        examples: &["/fadeout 2s", "/fadeout 0"],
+    },
+    CommandSpec {
+        name: "border",
+        short: &[],
+        usage: "/border <style>",
+        summary: "切换边框样式",
+        details: "切换标题方框和分隔线使用的字符并重绘播放界面：block 为默认的 █ 方框，double 为 ╔═╗，single 为 ┌─┐，rounded 为 ╭─╮，ascii 只使用 +-|。启动时的样式见配置项 ui.border_style。",
+        examples: &["/border double", "/border ascii"],
+    },
+    CommandSpec {
+        name: "rg",
+        short: &[],
+        usage: "/rg <off|track|album>",
+        summary: "切换 ReplayGain 音量归一化",
+        details: "按文件中的 ReplayGain 标签（MP3 的 ID3v2 TXXX 帧，FLAC / Ogg 的 Vorbis 注释）调整音量，使不同歌曲的响度一致。track 按每首歌归一化，album 保持同一专辑内的相对音量，off 关闭。前置放大见配置项 replaygain_preamp，应用的增益显示在播放状态区域。",
+        examples: &["/rg track", "/rg off"],
+    },
+    CommandSpec {
+        name: "device",
+        short: &[],
+        usage: "/device <list|name>",
+        summary: "查看或切换音频输出设备",
+        details: "/device list 列出所有音频后端的输出设备，> 标记正在使用的设备；/device <名称> 切换到该设备，正在播放的歌曲从当前位置继续，选择会保存到配置项 audio_device。\nLinux 上也会列出并接受 PulseAudio / PipeWire 的 sink 名称。设备断开（如蓝牙耳机）时自动改用默认设备。",
+        examples: &["/device list", "/device \"USB Audio\""],
+    },
+    CommandSpec {
+        name: "now",
+        short: &[],
+        usage: "/now",
+        summary: "显示当前播放信息",
+        details: "显示当前歌曲、播放进度、音量和播放模式。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "debug",
+        short: &[],
+        usage: "/debug [on|off]",
+        summary: "显示或隐藏调试信息",
+        details: "在播放界面中显示调试区域：按已解码样本计算的播放位置、按经过时间估算的位置、两者的偏差和累计暂停时长，当前歌词行及其时间范围，播放模式和进度更新的频率，以及播放队列中的歌曲。报告歌词不同步等问题时可以附上这些信息，不带参数时切换。",
+        examples: &["/debug", "/debug off"],
+    },
+    CommandSpec {
+        name: "info",
+        short: &[],
+        usage: "/info",
+        summary: "显示当前歌曲的技术信息",
+        details: "显示当前歌曲的文件路径、大小、格式、采样率、声道数、码率、时长以及全部标签。支持读取 MP3 的 ID3v2 标签和 FLAC / Ogg 的 Vorbis 注释。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "alias",
+        short: &[],
+        usage: "/alias [list|<x> <cmd>|remove <x>]",
+        summary: "查看、设置或删除命令别名",
+        details: "不带参数或 list 时列出所有别名；/alias <x> <cmd>（也可以写成 /alias set <x> <cmd>）添加别名，/alias remove <x> 删除别名，修改都会保存到配置文件。别名可以带参数，可以指向另一个别名，但不能与内置命令重名、不能循环引用，嵌套也不能超过 5 层。",
+        examples: &[
+            "/alias",
+            "/alias nxt next",
+            "/alias loud volume 90",
+            "/alias remove nxt",
+        ],
+    },
+    CommandSpec {
+        name: "config",
+        short: &[],
+        usage: "/config show",
+        summary: "显示当前生效的配置",
+        details: "显示配置文件路径和当前生效的全部配置（包括默认值）。",
+        examples: &[],
+    },
+    CommandSpec {
+        name: "source",
+        short: &[],
+        usage: "/source <file>",
+        summary: "逐行执行文件中的命令",
+        details: "逐行执行文件中的命令，每条命令执行前回显。# 开头的行是注释，空行会被跳过；某条命令出错时停止执行。",
+        examples: &["/source ~/startup.txt"],
+    },
+    CommandSpec {
+        name: "sleep",
+        short: &[],
+        usage: "/sleep <time>",
+        summary: "等待一段时间再执行后面的命令",
+        details: "暂停读取后续命令，歌曲照常播放。时间可以写成 90、90s、2m 或 1m30s，适合在脚本或管道输入中让歌曲播放一段时间后再退出。\
+                  在终端中交互输入时（包括其中 /source 执行的脚本）不可用，否则等待期间无法输入命令和退出。",
+        examples: &["/sleep 30s", "/folder ~/Music; /play 1; /sleep 1m"],
+    },
+    CommandSpec {
+        name: "quit",
+        short: &["exit", "q", "e"],
+        usage: "/quit",
+        summary: "退出",
+        details: "停止播放并退出程序。播放界面中按 q。",
+        examples: &[],
+    },
+];
+
+// 所有内置命令名（含简写）
+fn builtin_names() -> impl Iterator<Item = &'static str> {
+    COMMANDS
+        .iter()
+        .flat_map(|spec| std::iter::once(spec.name).chain(spec.short.iter().copied()))
+}
+
+pub fn is_builtin_command(name: &str) -> bool {
+    let name = name.to_lowercase();
+    builtin_names().any(|n| n == name)
+}
+
+/// 按命令名或简写查找命令说明（不区分大小写，可以带开头的 /）
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    let name = name.trim_start_matches('/').to_lowercase();
+    COMMANDS
+        .iter()
+        .find(|spec| spec.name == name || spec.short.contains(&name.as_str()))
+}
+
+/// 命令的用法说明，例如 `usage_of("goto")` -> `/goto <N>`
+pub fn usage_of(name: &str) -> &'static str {
+    find_command(name).map(|spec| spec.usage).unwrap_or("")
+}
+
+// 别名最多可以嵌套的层数
+const MAX_ALIAS_DEPTH: usize = 5;
+
+/// 展开命令别名（`/v 80` -> `/volume 80`），别名可以指向另一个别名，但不能形成循环，
+/// 嵌套也不能超过 MAX_ALIAS_DEPTH 层
+pub fn expand_alias(line: &str, aliases: &BTreeMap<String, String>) -> Result<String, String> {
     let t = line.trim();
+    let Some(body) = t.strip_prefix('/') else {
+        return Ok(t.to_string());
+    };
+    let (name, args) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+    let mut name = name.to_lowercase();
+    let mut args = args.trim().to_string();
+    let mut seen: Vec<String> = Vec::new();
+
+    while !is_builtin_command(&name) {
+        let Some(target) = aliases.get(&name) else {
+            break;
+        };
+        if seen.contains(&name) {
+            return Err(tr!(Msg::AliasCycle, seen[0]));
+        }
+        if seen.len() >= MAX_ALIAS_DEPTH {
+            return Err(tr!(Msg::AliasTooDeep, seen[0], MAX_ALIAS_DEPTH));
+        }
+        seen.push(name);
+
+        let target = target.trim().trim_start_matches('/');
+        let (target_name, target_args) = target
+            .split_once(char::is_whitespace)
+            .unwrap_or((target, ""));
+        name = target_name.to_lowercase();
+        args = [target_args.trim(), args.as_str()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    if args.is_empty() {
+        Ok(format!("/{}", name))
+    } else {
+        Ok(format!("/{} {}", name, args))
+    }
+}
+
+/// 检查新别名是否合法：不能与内置命令重名，也不能形成循环
+pub fn validate_alias(
+    name: &str,
+    target: &str,
+    aliases: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    if name.is_empty() || name.contains(char::is_whitespace) || name.starts_with('/') {
+        return Err(tr!(Msg::AliasInvalidName, name));
+    }
+    if is_builtin_command(name) {
+        return Err(tr!(Msg::AliasIsBuiltin, name));
+    }
+    if target.trim().trim_start_matches('/').is_empty() {
+        return Err(tr!(Msg::AliasNoTarget));
+    }
+    let mut candidate = aliases.clone();
+    candidate.insert(name.to_lowercase(), target.to_string());
+    expand_alias(&format!("/{}", name), &candidate).map(|_| ())
+}
+
+// 拆出第一个单词，返回 (单词, 去掉首尾空白的剩余部分)
+fn split_first_word(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    let (word, rest) = s.split_once(char::is_whitespace).unwrap_or((s, ""));
+    (word, rest.trim())
+}
+
+// 按引号和反斜杠转义拆分出的字符，tokenize 和 split_commands 共用，两者对引号的理解保持一致
+enum Lexeme {
+    Literal(char), // 引号内或被转义的字符，按字面处理
+    Quote,         // 开始或结束引号的引号本身
+    Bare(char),    // 引号外未转义的字符，空白和分号在这里起分隔作用
+}
+
+// 逐字符识别引号和转义，同时给出字符在输入中的字节位置
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    quote: Option<char>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.char_indices().peekable(),
+            quote: None,
+        }
+    }
+
+    // 下一个字符是引号，且引号之后是空白、分号或行尾
+    fn closes_after_backslash(&self) -> bool {
+        let mut ahead = self.chars.clone();
+        matches!(ahead.next(), Some((_, '"')))
+            && ahead
+                .next()
+                .is_none_or(|(_, c)| c == ';' || c.is_whitespace())
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = (usize, Lexeme);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (i, c) = self.chars.next()?;
+        let lexeme = match (self.quote, c) {
+            // 单引号内全部按字面处理
+            (Some('\''), '\'') => {
+                self.quote = None;
+                Lexeme::Quote
+            }
+            (Some('\''), _) => Lexeme::Literal(c),
+            // 双引号中以反斜杠结尾的 Windows 路径（"D:\Music\"）：反斜杠后的引号之后是空白、
+            // 分号或行尾时，引号是结束引号，反斜杠按字面保留
+            (Some('"'), '\\') if self.closes_after_backslash() => Lexeme::Literal(c),
+            (_, '\\') => match self.chars.peek() {
+                Some(&(_, next)) if matches!(next, '"' | '\'' | ';') || next.is_whitespace() => {
+                    self.chars.next();
+                    Lexeme::Literal(next)
+                }
+                _ => Lexeme::Literal(c),
+            },
+            (Some('"'), '"') => {
+                self.quote = None;
+                Lexeme::Quote
+            }
+            (Some(_), _) => Lexeme::Literal(c),
+            (None, '"' | '\'') => {
+                self.quote = Some(c);
+                Lexeme::Quote
+            }
+            (None, _) => Lexeme::Bare(c),
+        };
+        Some((i, lexeme))
+    }
+}
+
+/// 类 shell 的参数拆分：支持双引号、单引号和反斜杠转义。
+/// 反斜杠只转义空白、引号和分号，其余情况按字面保留，以兼容 `C:\Music`、`\\server\share` 这类 Windows 路径；
+/// 双引号中紧接结束引号的反斜杠（`"D:\Music\"`）也按字面保留
+pub fn tokenize(args: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut has_token = false;
+
+    for (_, lexeme) in Lexer::new(args) {
+        match lexeme {
+            Lexeme::Quote => has_token = true,
+            Lexeme::Bare(c) if c.is_whitespace() => {
+                if has_token || !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            Lexeme::Bare(c) | Lexeme::Literal(c) => current.push(c),
+        }
+    }
+    if has_token || !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// 按引号外未转义的分号拆分一行输入，例如 `/folder D:\Music; /play`
+pub fn split_commands(line: &str) -> Vec<&str> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (i, lexeme) in Lexer::new(line) {
+        if let Lexeme::Bare(';') = lexeme {
+            segments.push(line[start..i].trim());
+            start = i + 1;
+        }
+    }
+    segments.push(line[start..].trim());
+    segments.retain(|s| !s.is_empty());
+    segments
+}
+
+/// 确认提示的回答：y/yes 为 true，n/no 为 false，不区分大小写
+pub fn parse_answer(line: &str) -> Option<bool> {
+    match line.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+pub fn parse_command(
+    line: &str,
+    aliases: &BTreeMap<String, String>,
+) -> Result<Command, ParseError> {
+    parse_line(line, aliases, 0)
+}
+
+// depth 为简写补全后重新解析的次数：别名指向一个只能补全回该别名的简写时（如 jump = "j"）会无限循环
+fn parse_line(
+    line: &str,
+    aliases: &BTreeMap<String, String>,
+    depth: usize,
+) -> Result<Command, ParseError> {
+    // 去掉误输入的结尾分号和逗号
+    let line = line
+        .trim()
+        .trim_end_matches([';', ',', '；', '，'])
+        .trim_end();
+    if line.is_empty() || line == "/" {
+        return Ok(Command::Noop);
+    }
+
+    // 单独输入的 y、n 回答等待确认的命令
+    if let Some(yes) = parse_answer(line) {
+        return Ok(Command::Confirm(yes));
+    }
+
+    // 单独输入的数字等同于 /play <N>，方便搜索后直接选歌
+    if line.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(Command::PlayIndex(
+            parse_index("play", Some(line))?.unwrap_or(1),
+        ));
+    }
+
+    let expanded = expand_alias(line, aliases).map_err(ParseError::InvalidAlias)?;
+    let t = expanded.as_str();
     if !t.starts_with('/') {
-        return Command::Unknown(t.to_string());
+        return Err(unknown_with_suggestion(t, t, aliases));
     }
-    let mut parts = t[1..].split_whitespace();
-    let cmd = parts.next().unwrap_or("");
-    match cmd.to_lowercase().as_str() {
-        "help" => Command::Help,
+    let (cmd, args) = split_first_word(&t[1..]);
+    let mut parts = args.split_whitespace();
+    let command = match cmd.to_lowercase().as_str() {
+        "help" => Command::Help(parts.next().map(str::to_string)),
         "quit" | "exit" | "q" | "e" => Command::Quit,
         "folder" | "f" => {
-            let rest = parts.collect::<Vec<_>>().join(" ");
+            // 路径之后可以跟 depth=N、norecurse、symlinks、hidden 或 play 选项
+            let mut tokens = tokenize(args);
+            let mut flags = FolderFlags::default();
+            while let Some(last) = tokens.last() {
+                let lower = last.to_lowercase();
+                if lower == "norecurse" {
+                    flags.depth = Some(1);
+                } else if lower == "symlinks" {
+                    flags.symlinks = true;
+                } else if lower == "hidden" {
+                    flags.hidden = true;
+                } else if lower == "play" || lower == "--play" {
+                    flags.play = Some(true);
+                } else if let Some(n) = lower.strip_prefix("depth=") {
+                    match n.parse::<usize>() {
+                        Ok(d) if d > 0 => flags.depth = Some(d),
+                        _ => {
+                            return Err(ParseError::invalid(
+                                "folder",
+                                last,
+                                Msg::ExpectDepth.text(),
+                            ));
+                        }
+                    }
+                } else {
+                    break;
+                }
+                tokens.pop();
+            }
+
+            let rest = tokens.join(" ");
             if rest.is_empty() {
-                Command::Unknown(format!(
-                    "/folder 命令需要指定路径参数，例如: /folder C:\\Music"
-                ))
-            } else {
-                Command::Folder(rest)
+                return Err(ParseError::missing("folder"));
+            }
+            Command::Folder(rest, flags)
+        }
+        "reload" => Command::Reload,
+        "list" | "ls" => {
+            // 页码和显示格式都可省略，顺序不限，例如 /list 2 wide
+            let mut page = None;
+            let mut format = ListFormat::Compact;
+            for v in parts {
+                match (v.parse::<usize>(), ListFormat::parse(v)) {
+                    (Ok(n), _) if n > 0 => page = Some(n),
+                    (_, Some(f)) => format = f,
+                    _ => return Err(ParseError::invalid("list", v, Msg::ExpectPage.text())),
+                }
+            }
+            match page {
+                Some(page) => Command::ListPage(page, format),
+                None => Command::List(format),
             }
         }
-        "list" | "ls" => Command::List,
+        "listpage" => match parts.next() {
+            Some(v) => match v.parse::<usize>() {
+                Ok(size) if size > 0 => Command::ListPageSize(size),
+                _ => return Err(ParseError::invalid("listpage", v, Msg::ExpectCount.text())),
+            },
+            None => return Err(ParseError::missing("listpage")),
+        },
         "search" => {
-            let rest = parts.collect::<Vec<_>>().join(" ");
+            let rest = tokenize(args).join(" ");
             if rest.is_empty() {
-                Command::Unknown(format!(
-                    "/search 命令需要指定搜索关键词，例如: /search 周杰伦"
-                ))
-            } else {
-                Command::Search(rest)
+                return Err(ParseError::missing("search"));
             }
+            Command::Search(rest)
         }
+        // 没有参数时播放第一首歌曲，参数不是数字时按名称查找
         "play" => {
-            if let Some(n) = parts.next() {
-                if let Ok(idx1) = n.parse::<usize>() {
-                    if idx1 == 0 {
-                        return Command::Unknown(format!("歌曲序号从 1 开始，不能为 0"));
-                    }
-                    return Command::PlayIndex(idx1);
-                }
-                // 如果解析失败，返回未知命令
-                return Command::Unknown(format!("无效的歌曲序号: {}，请输入数字", n));
+            let rest = tokenize(args).join(" ");
+            if rest.is_empty() || rest.parse::<usize>().is_ok() {
+                Command::PlayIndex(parse_index("play", parts.next())?.unwrap_or(1))
+            } else if let Ok((from, to)) = parse_range("play", &rest) {
+                Command::PlayRange(from, to)
+            } else {
+                Command::PlayName(rest)
+            }
+        }
+        "goto" => match parse_index("goto", parts.next())? {
+            Some(idx1) => Command::Goto(idx1),
+            None => return Err(ParseError::missing("goto")),
+        },
+        "random" | "rand" => Command::PlayRandom,
+        name @ ("sample" | "sampleloop") => {
+            let spec = if name == "sample" {
+                "sample"
+            } else {
+                "sampleloop"
+            };
+            match parts.next() {
+                Some(off) if off.eq_ignore_ascii_case("off") => Command::Sample(0, false),
+                Some(n) => match n.parse::<usize>() {
+                    Ok(count) if count > 0 => Command::Sample(count, spec == "sampleloop"),
+                    _ => return Err(ParseError::invalid(spec, n, Msg::ExpectCount.text())),
+                },
+                None => return Err(ParseError::missing(spec)),
             }
-            // 没有参数时播放第一首歌曲
-            Command::PlayIndex(1)
         }
         "pause" => Command::Pause,
         "resume" => Command::Resume,
+        "continue" => Command::Continue,
         "next" => Command::Next,
         "prev" | "back" => Command::Prev,
-        "mode" | "m" => match parts.next().unwrap_or("").to_lowercase().as_str() {
-            "sequential" | "seq" => Command::Mode(PlaybackMode::Sequential),
-            "repeatone" | "one" => Command::Mode(PlaybackMode::RepeatOne),
-            "shuffle" | "shu" => Command::Mode(PlaybackMode::Shuffle),
-            "" => Command::Unknown(format!(
-                "/mode 命令需要指定模式参数: sequential(顺序), repeatone(单曲循环), shuffle(随机)"
-            )),
-            invalid => Command::Unknown(format!(
-                "无效的播放模式: {}，支持: sequential, repeatone, shuffle",
-                invalid
-            )),
+        "mode" | "m" => match parts.next() {
+            Some(name) => match parse_mode(name) {
+                Some(mode) => Command::Mode(mode),
+                None => return Err(ParseError::invalid("mode", name, Msg::ExpectMode.text())),
+            },
+            None => Command::ShowMode,
         },
-        "volume" | "vol" => {
-            if let Some(v) = parts.next() {
-                if let Ok(mut vv) = v.parse::<i32>() {
-                    if vv < 0 || vv > 100 {
-                        return Command::Unknown(format!(
-                            "音量值必须在 0-100 范围内，输入的值: {}",
-                            vv
-                        ));
-                    }
-                    vv = vv.clamp(0, 100);
-                    return Command::Volume(vv as u8);
-                } else {
-                    return Command::Unknown(format!(
-                        "无效的音量值: {}，请输入 0-100 之间的数字",
-                        v
-                    ));
+        "shuffle" => Command::Shuffle,
+        "abmode" => Command::AudiobookMode,
+        "speed" => match parts.next() {
+            Some(v) => match v.trim_end_matches(['x', 'X']).parse::<f32>() {
+                Ok(speed) if (MIN_SPEED..=MAX_SPEED).contains(&speed) => Command::Speed(speed),
+                _ => return Err(ParseError::invalid("speed", v, Msg::ExpectSpeed.text())),
+            },
+            None => return Err(ParseError::missing("speed")),
+        },
+        "add" => {
+            let rest = tokenize(args).join(" ");
+            if rest.is_empty() {
+                return Err(ParseError::missing("add"));
+            }
+            Command::Add(rest)
+        }
+        "merge" => {
+            let rest = tokenize(args).join(" ");
+            if rest.is_empty() {
+                return Err(ParseError::missing("merge"));
+            }
+            Command::Merge(rest)
+        }
+        "copyto" => {
+            // 路径之后可以跟 symlink、lrc 选项
+            let mut tokens = tokenize(args);
+            let mut flags = CopyFlags::default();
+            while let Some(last) = tokens.last() {
+                match last.to_lowercase().as_str() {
+                    "symlink" | "--symlink" => flags.symlink = true,
+                    "lrc" | "--copy-lrc" => flags.lrc = true,
+                    _ => break,
                 }
+                tokens.pop();
+            }
+
+            let rest = tokens.join(" ");
+            if rest.is_empty() {
+                return Err(ParseError::missing("copyto"));
+            }
+            Command::CopyPlaylist(rest, flags)
+        }
+        "playlist" => match split_first_word(args) {
+            (sub, sub_args) if sub.eq_ignore_ascii_case("merge") => {
+                let rest = tokenize(sub_args).join(" ");
+                if rest.is_empty() {
+                    return Err(ParseError::missing("playlist"));
+                }
+                Command::Merge(rest)
+            }
+            ("", _) => return Err(ParseError::missing("playlist")),
+            (sub, _) => {
+                return Err(ParseError::invalid(
+                    "playlist",
+                    sub,
+                    Msg::ExpectPlaylistSub.text(),
+                ));
+            }
+        },
+        "snapshot" => Command::Snapshot,
+        "diff" => Command::Diff,
+        "dedup" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Dedup(false),
+            Some("strict") => Command::Dedup(true),
+            Some(other) => return Err(ParseError::invalid("dedup", other, "strict")),
+        },
+        "clear" => Command::Clear,
+        "undo" => Command::Undo,
+        "remove" => match parts.next() {
+            Some(arg) => {
+                let (from, to) = parse_range("remove", arg)?;
+                Command::RemoveRange(from, to)
+            }
+            None => return Err(ParseError::missing("remove")),
+        },
+        "sort" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            Some("name") => Command::Sort(SortKey::Name),
+            Some("track") | None => Command::Sort(SortKey::TrackNumberInferred),
+            Some(invalid) => {
+                return Err(ParseError::invalid("sort", invalid, Msg::ExpectSort.text()));
+            }
+        },
+        "volume" | "vol" => match parts.next() {
+            Some(v) => match v.parse::<u8>() {
+                Ok(vv) if vv <= 100 => Command::Volume(vv),
+                _ => return Err(ParseError::invalid("volume", v, Msg::ExpectVolume.text())),
+            },
+            None => return Err(ParseError::missing("volume")),
+        },
+        name @ ("volup" | "voldown") => {
+            let spec = if name == "volup" { "volup" } else { "voldown" };
+            let step = match parts.next() {
+                None => VOLUME_STEP,
+                Some(n) => match n.parse::<u8>() {
+                    Ok(step) if (1..=100).contains(&step) => step,
+                    _ => return Err(ParseError::invalid(spec, n, Msg::ExpectStep.text())),
+                },
+            };
+            if spec == "volup" {
+                Command::VolumeUp(step)
+            } else {
+                Command::VolumeDown(step)
             }
-            Command::Unknown(format!("/volume 命令需要指定音量值，例如: /volume 80"))
         }
         "lyrics" | "lrc" => Command::Lyrics,
         "lmode" | "lm" => Command::LyricsMode,
+        "lrcexport" => {
+            let rest = tokenize(args).join(" ");
+            if rest.is_empty() {
+                return Err(ParseError::missing("lrcexport"));
+            }
+            Command::LyricsExport(rest)
+        }
+        "vu" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Vu(None),
+            Some("on") => Command::Vu(Some(true)),
+            Some("off") => Command::Vu(Some(false)),
+            Some(other) => return Err(ParseError::invalid("vu", other, Msg::ExpectOnOff.text())),
+        },
+        "debug" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Debug(None),
+            Some("on") => Command::Debug(Some(true)),
+            Some("off") => Command::Debug(Some(false)),
+            Some(other) => {
+                return Err(ParseError::invalid("debug", other, Msg::ExpectOnOff.text()));
+            }
+        },
+        "viz" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Viz(None),
+            Some("on") => Command::Viz(Some(true)),
+            Some("off") => Command::Viz(Some(false)),
+            Some(other) => return Err(ParseError::invalid("viz", other, Msg::ExpectOnOff.text())),
+        },
+        "compact" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Compact(None),
+            Some("on") => Command::Compact(Some(true)),
+            Some("off") => Command::Compact(Some(false)),
+            Some(other) => {
+                return Err(ParseError::invalid(
+                    "compact",
+                    other,
+                    Msg::ExpectOnOff.text(),
+                ));
+            }
+        },
+        "quiet" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Quiet(None),
+            Some("on") => Command::Quiet(Some(true)),
+            Some("off") => Command::Quiet(Some(false)),
+            Some(other) => {
+                return Err(ParseError::invalid("quiet", other, Msg::ExpectOnOff.text()));
+            }
+        },
+        "pane" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Pane(None),
+            Some("on") => Command::Pane(Some(true)),
+            Some("off") => Command::Pane(Some(false)),
+            Some(other) => return Err(ParseError::invalid("pane", other, Msg::ExpectOnOff.text())),
+        },
+        "watch" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None => Command::Watch(None),
+            Some("on") => Command::Watch(Some(true)),
+            Some("off") => Command::Watch(Some(false)),
+            Some(other) => {
+                return Err(ParseError::invalid("watch", other, Msg::ExpectOnOff.text()));
+            }
+        },
+        "lang" => match parts.next() {
+            None => return Err(ParseError::missing("lang")),
+            Some(v) => match Lang::parse(v) {
+                Some(lang) => Command::Lang(lang),
+                None => return Err(ParseError::invalid("lang", v, Msg::ExpectLang.text())),
+            },
+        },
+        // 自定义方案在配置中，执行时再检查名称
+        "theme" => match parts.next() {
+            None => return Err(ParseError::missing("theme")),
+            Some(v) => Command::Theme(v.to_lowercase()),
+        },
+        "fadein" => match parts.next() {
+            Some(v) => match parse_duration(v) {
+                Some(duration) => Command::FadeIn(duration),
+                None => return Err(ParseError::invalid("fadein", v, Msg::ExpectDuration.text())),
+            },
+            None => return Err(ParseError::missing("fadein")),
+        },
+        "fadeout" => match parts.next() {
+            Some(v) => match parse_duration(v) {
+                Some(duration) => Command::FadeOut(duration),
+                None => {
+                    return Err(ParseError::invalid(
+                        "fadeout",
+                        v,
+                        Msg::ExpectDuration.text(),
+                    ));
+                }
+            },
+            None => return Err(ParseError::missing("fadeout")),
+        },
+        "border" => match parts.next() {
+            None => return Err(ParseError::missing("border")),
+            Some(v) => match BorderStyle::parse(v) {
+                Some(style) => Command::Border(style),
+                None => return Err(ParseError::invalid("border", v, Msg::ExpectBorder.text())),
+            },
+        },
+        "rg" => match parts.next() {
+            None => return Err(ParseError::missing("rg")),
+            Some(v) => match RgMode::parse(v) {
+                Some(mode) => Command::ReplayGain(mode),
+                None => return Err(ParseError::invalid("rg", v, Msg::ExpectRgMode.text())),
+            },
+        },
         "now" => Command::Now,
-        _ => Command::Unknown(t.to_string()),
+        "info" => Command::Info,
+        "alias" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None | Some("list") => Command::AliasList,
+            Some("remove" | "rm") => match parts.next() {
+                Some(name) => Command::AliasRemove(name.trim_start_matches('/').to_lowercase()),
+                None => return Err(ParseError::missing("alias")),
+            },
+            // set 可以省略：/alias nxt next 等同于 /alias set nxt next
+            Some(word) => {
+                let name = if word == "set" {
+                    parts.next()
+                } else {
+                    Some(word)
+                };
+                match (name, parts.collect::<Vec<_>>().join(" ")) {
+                    (Some(name), target) if !target.is_empty() => {
+                        Command::AliasSet(name.to_lowercase(), target)
+                    }
+                    _ => return Err(ParseError::missing("alias")),
+                }
+            }
+        },
+        "device" => {
+            let rest = tokenize(args).join(" ");
+            match rest.as_str() {
+                "" => return Err(ParseError::missing("device")),
+                "list" => Command::DeviceList,
+                _ => Command::DeviceSelect(rest),
+            }
+        }
+        "source" => {
+            let rest = tokenize(args).join(" ");
+            if rest.is_empty() {
+                return Err(ParseError::missing("source"));
+            }
+            Command::Source(rest)
+        }
+        "seek" => match parts.next() {
+            Some(v) => match parse_seek(v) {
+                Some(command) => command,
+                None => return Err(ParseError::invalid("seek", v, Msg::ExpectSeek.text())),
+            },
+            None => return Err(ParseError::missing("seek")),
+        },
+        "sleep" => match parts.next() {
+            Some(v) => match parse_duration(v) {
+                Some(duration) => Command::Sleep(duration),
+                None => return Err(ParseError::invalid("sleep", v, Msg::ExpectDuration.text())),
+            },
+            None => return Err(ParseError::missing("sleep")),
+        },
+        "config" => match parts.next().map(|s| s.to_lowercase()).as_deref() {
+            None | Some("show") => Command::ConfigShow,
+            Some(other) => {
+                return Err(ParseError::invalid(
+                    "config",
+                    other,
+                    Msg::ExpectConfigSub.text(),
+                ));
+            }
+        },
+        _ => return resolve_abbreviation(t, cmd, args, aliases, depth),
+    };
+    Ok(command)
+}
+
+/// 命令名前缀的补全结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandCompletion {
+    Exact(String),          // 与某个命令名完全相同
+    Unique(String),         // 只有一个命令以它开头
+    Ambiguous(Vec<String>), // 多个命令以它开头，按名称排序
+    NoMatch,
+}
+
+/// 在 candidates 中补全命令名前缀（不区分大小写），行输入的简写和补全共用
+pub fn complete_command(prefix: &str, candidates: &[&str]) -> CommandCompletion {
+    let prefix = prefix.to_lowercase();
+    if prefix.is_empty() {
+        return CommandCompletion::NoMatch;
+    }
+    if candidates.contains(&prefix.as_str()) {
+        return CommandCompletion::Exact(prefix);
+    }
+    let mut matches: Vec<String> = candidates
+        .iter()
+        .filter(|name| name.starts_with(&prefix))
+        .map(|name| name.to_string())
+        .collect();
+    matches.sort();
+    matches.dedup();
+    match matches.len() {
+        0 => CommandCompletion::NoMatch,
+        1 => CommandCompletion::Unique(matches.remove(0)),
+        _ => CommandCompletion::Ambiguous(matches),
+    }
+}
+
+// 不是完整命令名时按前缀补全：唯一匹配时按补全后的命令解析，多个匹配时列出候选，
+// 没有匹配时按编辑距离给出建议。只用正式命令名和别名补全，简写本身已经足够短
+fn resolve_abbreviation(
+    input: &str,
+    cmd: &str,
+    args: &str,
+    aliases: &BTreeMap<String, String>,
+    depth: usize,
+) -> Result<Command, ParseError> {
+    let names: Vec<&str> = COMMANDS
+        .iter()
+        .map(|spec| spec.name)
+        .chain(aliases.keys().map(String::as_str))
+        .collect();
+    match complete_command(cmd, &names) {
+        CommandCompletion::Unique(name) if depth >= MAX_ALIAS_DEPTH => {
+            Err(ParseError::InvalidAlias(tr!(Msg::AliasCycle, name)))
+        }
+        CommandCompletion::Unique(name) => {
+            parse_line(&format!("/{} {}", name, args), aliases, depth + 1)
+        }
+        CommandCompletion::Ambiguous(candidates) => Err(ParseError::AmbiguousCommand {
+            input: format!("/{}", cmd),
+            candidates,
+        }),
+        CommandCompletion::Exact(_) | CommandCompletion::NoMatch => {
+            Err(unknown_with_suggestion(input, &input[1..], aliases))
+        }
+    }
+}
+
+/// 解析播放模式名称及其简写（seq、one、shu），不区分大小写
+pub fn parse_mode(name: &str) -> Option<PlaybackMode> {
+    match name.to_lowercase().as_str() {
+        "sequential" | "seq" => Some(PlaybackMode::Sequential),
+        "repeatone" | "one" => Some(PlaybackMode::RepeatOne),
+        "shuffle" | "shu" => Some(PlaybackMode::Shuffle),
+        _ => None,
+    }
+}
+
+/// 解析时间长度：90、90s、2m、1m30s、1h、500ms（不带单位时为秒）
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim().to_lowercase();
+    if let Ok(secs) = s.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = s.as_str();
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let number: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let part = match &rest[..unit_len] {
+            "h" => Duration::from_secs(number.checked_mul(3600)?),
+            "m" => Duration::from_secs(number.checked_mul(60)?),
+            "s" => Duration::from_secs(number),
+            "ms" => Duration::from_millis(number),
+            _ => return None,
+        };
+        total = total.checked_add(part)?;
+        rest = &rest[unit_len..];
+    }
+    (!s.is_empty()).then_some(total)
+}
+
+// /seek 的参数：+N、-N 为相对秒数，1:30、1:02:03 或 90s 等为绝对位置
+fn parse_seek(arg: &str) -> Option<Command> {
+    if let Some(rest) = arg.strip_prefix('+') {
+        return Some(Command::SeekRelative(parse_duration(rest)?.as_secs() as i64));
+    }
+    if let Some(rest) = arg.strip_prefix('-') {
+        return Some(Command::SeekRelative(
+            -(parse_duration(rest)?.as_secs() as i64),
+        ));
+    }
+    if arg.contains(':') {
+        let mut secs = 0u64;
+        for part in arg.split(':') {
+            secs = secs.checked_mul(60)?.checked_add(part.parse().ok()?)?;
+        }
+        return Some(Command::Seek(Duration::from_secs(secs)));
+    }
+    parse_duration(arg).map(Command::Seek)
+}
+
+// 解析从 1 开始的歌曲序号，未提供时返回 None
+fn parse_index(cmd: &'static str, arg: Option<&str>) -> Result<Option<usize>, ParseError> {
+    match arg.map(|n| (n, n.parse::<usize>())) {
+        None => Ok(None),
+        Some((_, Ok(idx1))) if idx1 > 0 => Ok(Some(idx1)),
+        Some((n, _)) => Err(ParseError::invalid(cmd, n, Msg::ExpectIndex.text())),
+    }
+}
+
+// 解析歌曲序号或范围，例如 5（返回 (5, 5)）或 5-10；起止顺序由执行命令时检查
+fn parse_range(cmd: &'static str, arg: &str) -> Result<(usize, usize), ParseError> {
+    let invalid = || ParseError::invalid(cmd, arg, Msg::ExpectRange.text());
+    let (from, to) = arg.split_once('-').unwrap_or((arg, arg));
+    match (from.trim().parse::<usize>(), to.trim().parse::<usize>()) {
+        (Ok(from), Ok(to)) if from > 0 && to > 0 => Ok((from, to)),
+        _ => Err(invalid()),
+    }
+}
+
+// 未知命令的最大容错编辑距离
+const SUGGESTION_MAX_DISTANCE: usize = 2;
+// 距离相同时最多列出的候选数
+const SUGGESTION_MAX_CANDIDATES: usize = 3;
+
+// 生成未知命令错误，若有相近的命令则附带建议（保留用户输入的参数）
+fn unknown_with_suggestion(
+    input: &str,
+    body: &str,
+    aliases: &BTreeMap<String, String>,
+) -> ParseError {
+    let (word, args) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+    let candidates = suggest_commands(word, aliases);
+    let args = args.trim();
+    let suggestion = (!candidates.is_empty()).then(|| {
+        candidates
+            .iter()
+            .map(|c| {
+                if args.is_empty() {
+                    format!("/{}", c)
+                } else {
+                    format!("/{} {}", c, args)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(Msg::Or.text())
+    });
+    ParseError::UnknownCommand {
+        input: input.to_string(),
+        suggestion,
+    }
+}
+
+/// 在内置命令和别名中查找与输入最接近的命令名
+pub fn suggest_commands(word: &str, aliases: &BTreeMap<String, String>) -> Vec<String> {
+    let word = word.to_lowercase();
+    if word.is_empty() {
+        return Vec::new();
+    }
+
+    let mut best = SUGGESTION_MAX_DISTANCE;
+    let mut candidates: Vec<&str> = Vec::new();
+    let names = builtin_names()
+        .map(|n| -> &str { n })
+        .chain(aliases.keys().map(String::as_str));
+    for name in names {
+        let distance = osa_distance(&word, name);
+        // 至少一半以上的字母要对得上，否则 "x"、"xyz" 之类的输入会匹配到 /viz 等短命令
+        if distance * 2 >= name.chars().count() || distance > best {
+            continue;
+        }
+        if distance < best {
+            best = distance;
+            candidates.clear();
+        }
+        if !candidates.contains(&name) {
+            candidates.push(name);
+        }
+    }
+
+    candidates.truncate(SUGGESTION_MAX_CANDIDATES);
+    candidates.into_iter().map(str::to_string).collect()
+}
+
+// 最优字符串对齐距离（OSA，受限的 Damerau-Levenshtein 距离）：在插入、删除、替换之外，
+// 相邻两个字符对调（如 lsit → list）也算一次编辑，常见的手误因此只差 1
+fn osa_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.iter().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+            if i > 0 && j > 0 && ca == b[j - 1] && a[i - 1] == cb {
+                curr[j + 1] = curr[j + 1].min(before[j - 1] + 1);
+            }
+        }
+        before = std::mem::replace(&mut prev, curr);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aliases(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, target)| (name.to_string(), target.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn alias_to_its_own_abbreviation_is_rejected() {
+        let aliases = aliases(&[("jump", "j")]);
+        assert!(matches!(
+            parse_command("/jump", &aliases),
+            Err(ParseError::InvalidAlias(_))
+        ));
+    }
+
+    #[test]
+    fn suggestions_stop_at_the_maximum_distance() {
+        let none = BTreeMap::new();
+        assert_eq!(suggest_commands("volme", &none), ["volume"]);
+        assert_eq!(suggest_commands("vxlxme", &none), ["volume"]);
+        assert!(suggest_commands("vxxxme", &none).is_empty());
+    }
+
+    #[test]
+    fn quoted_or_escaped_semicolons_do_not_split_commands() {
+        assert_eq!(
+            split_commands("/folder D:\\Music; /play"),
+            ["/folder D:\\Music", "/play"]
+        );
+        assert_eq!(
+            split_commands("/search \"a;b\"; /list"),
+            ["/search \"a;b\"", "/list"]
+        );
+        assert_eq!(
+            split_commands("/search 'a;b'; /list"),
+            ["/search 'a;b'", "/list"]
+        );
+        assert_eq!(split_commands("/search a\\;b"), ["/search a\\;b"]);
+        assert_eq!(tokenize("'a;b'"), ["a;b"]);
+        assert_eq!(tokenize("a\\;b"), ["a;b"]);
+        // 双引号中以反斜杠结尾的路径
+        assert_eq!(
+            split_commands(r#"/folder "C:\"; /play"#),
+            [r#"/folder "C:\""#, "/play"]
+        );
+        assert_eq!(
+            split_commands(r#"/folder "D:\Music\";/play"#),
+            [r#"/folder "D:\Music\""#, "/play"]
+        );
+        assert_eq!(tokenize(r#""C:\""#), [r"C:\"]);
+    }
+
+    #[test]
+    fn tokenize_handles_quotes_and_escapes() {
+        assert_eq!(tokenize(r#""My Music" x"#), ["My Music", "x"]);
+        assert_eq!(tokenize(r"~/My\ Music"), ["~/My Music"]);
+        assert_eq!(tokenize(r"'It''s'"), ["Its"]);
+        assert_eq!(
+            tokenize(r"C:\Music \\server\share"),
+            [r"C:\Music", r"\\server\share"]
+        );
+        assert_eq!(tokenize(r#""""#), [""]);
+        assert_eq!(tokenize(r#""D:\Music\""#), [r"D:\Music\"]);
+        assert_eq!(
+            tokenize(r#""D:\My Music\" depth=2"#),
+            [r"D:\My Music\", "depth=2"]
+        );
+        assert_eq!(tokenize(r#""say \"hi\"!""#), [r#"say "hi"!"#]);
+    }
+
+    fn parse(line: &str) -> Result<Command, ParseError> {
+        parse_command(line, &BTreeMap::new())
+    }
+
+    #[test]
+    fn parses_each_command() {
+        assert!(matches!(parse("/help seek"), Ok(Command::Help(Some(c))) if c == "seek"));
+        assert!(matches!(parse("/q"), Ok(Command::Quit)));
+        assert!(matches!(
+            parse("/folder \"My Music\" depth=2 play"),
+            Ok(Command::Folder(path, FolderFlags { depth: Some(2), play: Some(true), .. }))
+                if path == "My Music"
+        ));
+        assert!(matches!(
+            parse("/list 2 wide"),
+            Ok(Command::ListPage(2, ListFormat::Wide))
+        ));
+        assert!(matches!(
+            parse("/ls"),
+            Ok(Command::List(ListFormat::Compact))
+        ));
+        assert!(matches!(parse("/search 夜曲"), Ok(Command::Search(k)) if k == "夜曲"));
+        assert!(matches!(parse("/play"), Ok(Command::PlayIndex(1))));
+        assert!(matches!(parse("/play 3-5"), Ok(Command::PlayRange(3, 5))));
+        assert!(matches!(parse("/play night song"), Ok(Command::PlayName(n)) if n == "night song"));
+        assert!(matches!(parse("/goto 7"), Ok(Command::Goto(7))));
+        assert!(matches!(
+            parse("/sampleloop 4"),
+            Ok(Command::Sample(4, true))
+        ));
+        assert!(matches!(
+            parse("/sample off"),
+            Ok(Command::Sample(0, false))
+        ));
+        assert!(matches!(
+            parse("/mode shu"),
+            Ok(Command::Mode(PlaybackMode::Shuffle))
+        ));
+        assert!(matches!(parse("/mode"), Ok(Command::ShowMode)));
+        assert!(matches!(parse("/speed 1.5x"), Ok(Command::Speed(s)) if s == 1.5));
+        assert!(matches!(parse("/seek +10"), Ok(Command::SeekRelative(10))));
+        assert!(
+            matches!(parse("/seek 1:30"), Ok(Command::Seek(d)) if d == Duration::from_secs(90))
+        );
+        assert!(matches!(parse("/dedup strict"), Ok(Command::Dedup(true))));
+        assert!(matches!(
+            parse("/remove 2-4"),
+            Ok(Command::RemoveRange(2, 4))
+        ));
+        assert!(matches!(
+            parse("/sort name"),
+            Ok(Command::Sort(SortKey::Name))
+        ));
+        assert!(matches!(parse("/vol 80"), Ok(Command::Volume(80))));
+        assert!(matches!(
+            parse("/volup"),
+            Ok(Command::VolumeUp(VOLUME_STEP))
+        ));
+        assert!(matches!(parse("/vu off"), Ok(Command::Vu(Some(false)))));
+        assert!(
+            matches!(parse("/fadein 2s"), Ok(Command::FadeIn(d)) if d == Duration::from_secs(2))
+        );
+        assert!(matches!(
+            parse("/copyto /tmp/out symlink lrc"),
+            Ok(Command::CopyPlaylist(dest, CopyFlags { symlink: true, lrc: true })) if dest == "/tmp/out"
+        ));
+        assert!(
+            matches!(parse("/alias n next"), Ok(Command::AliasSet(n, t)) if n == "n" && t == "next")
+        );
+        assert!(matches!(parse("/alias rm /n"), Ok(Command::AliasRemove(n)) if n == "n"));
+        assert!(matches!(parse("/device list"), Ok(Command::DeviceList)));
+        assert!(matches!(parse("/config"), Ok(Command::ConfigShow)));
+    }
+
+    #[test]
+    fn unknown_commands() {
+        assert_eq!(
+            parse("/zzzz").unwrap_err(),
+            ParseError::UnknownCommand {
+                input: "/zzzz".to_string(),
+                suggestion: None,
+            }
+        );
+        assert_eq!(
+            parse("/volme 50").unwrap_err(),
+            ParseError::UnknownCommand {
+                input: "/volme 50".to_string(),
+                suggestion: Some("/volume 50".to_string()),
+            }
+        );
+        // 不以 / 开头的输入也按未知命令处理
+        assert!(matches!(
+            parse("hello"),
+            Err(ParseError::UnknownCommand { input, .. }) if input == "hello"
+        ));
+    }
+
+    #[test]
+    fn missing_arguments() {
+        for (line, cmd) in [
+            ("/volume", "volume"),
+            ("/goto", "goto"),
+            ("/folder", "folder"),
+            ("/folder depth=2", "folder"),
+            ("/search", "search"),
+            ("/seek", "seek"),
+            ("/remove", "remove"),
+            ("/sample", "sample"),
+            ("/alias x", "alias"),
+            ("/lrcexport", "lrcexport"),
+        ] {
+            assert_eq!(
+                parse(line).unwrap_err(),
+                ParseError::MissingArgument {
+                    cmd,
+                    usage: usage_of(cmd),
+                },
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        for (line, cmd, value) in [
+            ("/volume 101", "volume", "101"),
+            ("/goto 0", "goto", "0"),
+            ("/mode loud", "mode", "loud"),
+            ("/folder x depth=0", "folder", "depth=0"),
+            ("/speed 9", "speed", "9"),
+            ("/seek soon", "seek", "soon"),
+            ("/vu maybe", "vu", "maybe"),
+            ("/dedup all", "dedup", "all"),
+            ("/remove 0-3", "remove", "0-3"),
+            ("/fadein fast", "fadein", "fast"),
+        ] {
+            assert!(
+                matches!(
+                    parse(line),
+                    Err(ParseError::InvalidArgument { cmd: c, value: v, .. }) if c == cmd && v == value
+                ),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn ambiguous_prefix() {
+        assert_eq!(
+            parse("/sa 3").unwrap_err(),
+            ParseError::AmbiguousCommand {
+                input: "/sa".to_string(),
+                candidates: vec!["sample".to_string(), "sampleloop".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn alias_cycle_is_an_invalid_alias() {
+        let aliases = aliases(&[("a", "b"), ("b", "a")]);
+        assert!(matches!(
+            parse_command("/a", &aliases),
+            Err(ParseError::InvalidAlias(_))
+        ));
+    }
+
+    #[test]
+    fn bare_slash_and_trailing_separators() {
+        assert!(matches!(parse("/"), Ok(Command::Noop)));
+        assert!(matches!(parse("/  "), Ok(Command::Noop)));
+        assert!(matches!(parse("   "), Ok(Command::Noop)));
+        assert!(matches!(parse("/help;"), Ok(Command::Help(None))));
+        assert!(matches!(parse("/next ,"), Ok(Command::Next)));
+        assert!(matches!(parse("/vol 30；"), Ok(Command::Volume(30))));
+    }
+
+    #[test]
+    fn bare_number_plays_that_index() {
+        assert!(matches!(parse("12"), Ok(Command::PlayIndex(12))));
+        assert!(matches!(parse(" 12 "), Ok(Command::PlayIndex(12))));
+        assert!(matches!(
+            parse("0"),
+            Err(ParseError::InvalidArgument { cmd: "play", .. })
+        ));
+        assert!(matches!(
+            parse("12abc"),
+            Err(ParseError::UnknownCommand { input, .. }) if input == "12abc"
+        ));
+    }
+
+    #[test]
+    fn unknown_commands_suggest_the_closest_name() {
+        let suggestion = |line| match parse(line) {
+            Err(ParseError::UnknownCommand { suggestion, .. }) => suggestion,
+            other => panic!("{line}: {other:?}"),
+        };
+        assert_eq!(suggestion("/lsit").as_deref(), Some("/list"));
+        assert_eq!(suggestion("/LSIT").as_deref(), Some("/list"));
+        // /paus 是 /pause 的唯一前缀，直接执行；打错字母顺序时给出建议
+        assert!(matches!(parse("/paus"), Ok(Command::Pause)));
+        assert_eq!(suggest_commands("paus", &BTreeMap::new()), ["pause"]);
+        assert_eq!(suggestion("/pasue").as_deref(), Some("/pause"));
+        assert_eq!(suggestion("/xyz"), None);
+        assert_eq!(osa_distance("lsit", "list"), 1);
+        assert_eq!(osa_distance("lsit", "quit"), 2);
+        assert_eq!(osa_distance("", "abc"), 3);
+        assert_eq!(osa_distance("kitten", "sitting"), 3);
+        // 对调过的字符不能再参与编辑，与完整的 Damerau-Levenshtein 距离（2）不同
+        assert_eq!(osa_distance("ca", "abc"), 3);
+    }
+
+    #[test]
+    fn ranges_start_at_one() {
+        assert!(matches!(parse("/remove 3"), Ok(Command::RemoveRange(3, 3))));
+        assert!(matches!(
+            parse("/remove 2-4"),
+            Ok(Command::RemoveRange(2, 4))
+        ));
+        // 起止顺序在执行时对照列表检查
+        assert!(matches!(
+            parse("/remove 5-2"),
+            Ok(Command::RemoveRange(5, 2))
+        ));
+        assert!(matches!(parse("/play 5-2"), Ok(Command::PlayRange(5, 2))));
+        for arg in ["0", "0-2", "2-0", "a-b", "3-", "-3", "1-2-3"] {
+            assert!(
+                matches!(
+                    parse(&format!("/remove {arg}")),
+                    Err(ParseError::InvalidArgument { cmd: "remove", .. })
+                ),
+                "{arg}"
+            );
+        }
+        assert!(matches!(
+            parse("/remove"),
+            Err(ParseError::MissingArgument { .. })
+        ));
     }
 }
@@ -0,0 +1,513 @@
+use crate::audiobook::{MAX_SPEED, MIN_SPEED};
+use crate::i18n::{Lang, Msg, tr};
+use crate::playlist::{PlaybackMode, ScanOptions};
+use crate::replaygain::{MAX_PREAMP, MIN_PREAMP, RgMode};
+use crate::ui::{BorderStyle, ThemePreset};
+use crossterm::style::Color;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CONFIG_FILE_NAME: &str = "beatcli.toml";
+
+/// 用户配置（beatcli.toml）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub playback: PlaybackConfig,
+    pub ui: UiConfig,
+    pub scanning: ScanningConfig,
+    pub paths: PathsConfig,
+    pub modes: ModesConfig,
+    pub aliases: BTreeMap<String, String>, // 命令别名，例如 n = "next"
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            playback: PlaybackConfig::default(),
+            ui: UiConfig::default(),
+            scanning: ScanningConfig::default(),
+            paths: PathsConfig::default(),
+            modes: ModesConfig::default(),
+            aliases: default_aliases(),
+        }
+    }
+}
+
+// 配置中没有 [aliases] 部分时使用的别名
+fn default_aliases() -> BTreeMap<String, String> {
+    [("ff", "next"), ("rew", "prev")]
+        .into_iter()
+        .map(|(name, target)| (name.to_string(), target.to_string()))
+        .collect()
+}
+
+/// [playback] 播放相关设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlaybackConfig {
+    pub volume: u8,                   // 启动时的音量（0-100）
+    pub mode: PlaybackMode,           // 启动时的播放模式
+    pub autoplay: bool,               // 加载默认文件夹后自动播放第一首
+    pub play_after_scan: bool,        // /folder 扫描到歌曲后自动开始播放
+    pub auto_resume_position: bool,   // 重新播放听过一部分的歌曲时自动从上次位置继续
+    pub auto_recover: bool,           // 上次异常退出时不询问，直接从检查点恢复
+    pub audiobook_speed: f32,         // 有声书模式下新文件的默认播放速度
+    pub audio_device: Option<String>, // 输出设备名称，未设置时使用系统默认设备
+    pub replaygain_mode: RgMode,      // ReplayGain 音量归一化：off、track 或 album
+    pub replaygain_preamp: f32,       // ReplayGain 的前置放大（dB），只用于带标签的歌曲
+    pub crossfade_ms: u64, // 切换到下一首时两首歌重叠淡入淡出的时长（毫秒），0 为不交叉淡化
+    pub fade_in_ms: u64,   // 新歌曲开始时的淡入时长（毫秒），0 为不淡入
+    pub fade_out_ms: u64,  // 歌曲结尾的淡出时长（毫秒），0 为不淡出
+}
+
+impl Default for PlaybackConfig {
+    fn default() -> Self {
+        Self {
+            volume: 50,
+            mode: PlaybackMode::default(),
+            autoplay: false,
+            play_after_scan: false,
+            auto_resume_position: false,
+            auto_recover: false,
+            audiobook_speed: 1.25,
+            audio_device: None,
+            replaygain_mode: RgMode::default(),
+            replaygain_preamp: 0.0,
+            crossfade_ms: 0,
+            fade_in_ms: 0,
+            fade_out_ms: 0,
+        }
+    }
+}
+
+/// [ui] 界面相关设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    pub width: usize,              // 界面宽度（字符数），0 为跟随终端宽度
+    pub lyric_window: usize,       // 当前歌词行上下各显示的行数
+    pub interlude_ms: u64, // 距离当前歌词行超过这个时长且下一行还没开始时显示间奏标记，0 为不显示
+    pub stream_lyrics: bool, // 默认使用流式歌词输出
+    pub status_bar: bool,  // 在终端最后一行显示状态栏
+    pub alt_screen: bool,  // 播放界面使用终端的备用屏幕，退出后恢复原来的内容
+    pub color: bool,       // 输出颜色；--no-color 或 NO_COLOR 环境变量也可以关闭
+    pub tui: bool,         // 使用基于 ratatui 的全屏界面
+    pub language: Lang,    // 界面语言：zh-CN 或 en-US
+    pub theme: String,     // 配色方案：default、mono、solarized 或 [ui.themes] 中定义的名称
+    pub border_style: BorderStyle, // 标题方框和分隔线的样式
+    pub colors: ColorsConfig,
+    pub themes: BTreeMap<String, ColorsConfig>, // 自定义配色方案，未设置的颜色使用 default 方案
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            width: 0,
+            lyric_window: 3,
+            interlude_ms: 8000,
+            stream_lyrics: true,
+            status_bar: false,
+            alt_screen: true,
+            color: true,
+            tui: false,
+            language: Lang::default(),
+            theme: ThemePreset::default().name().to_string(),
+            border_style: BorderStyle::default(),
+            colors: ColorsConfig::default(),
+            themes: BTreeMap::new(),
+        }
+    }
+}
+
+/// [ui.colors] 覆盖配色方案中的单项颜色，颜色为名称（如 cyan、dark_green）或 #ff6600 形式的 RGB，
+/// 未设置的项使用方案的颜色。[ui.themes.<名称>] 使用同样的键
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorsConfig {
+    pub title: Option<String>,         // 标题和状态区域
+    pub accent: Option<String>,        // 歌词区域的标题和边框
+    pub success: Option<String>,       // 成功提示
+    pub error: Option<String>,         // 错误提示
+    pub info: Option<String>,          // 一般提示
+    pub lyric_current: Option<String>, // 当前歌词行
+    pub lyric_dim: Option<String>,     // 其他歌词行
+    pub interlude: Option<String>,     // 歌词间隔中的间奏标记
+    pub progress: Option<String>,      // 进度条和频谱面板
+}
+
+/// [scanning] 文件夹扫描设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanningConfig {
+    pub extensions: Vec<String>,  // 作为歌曲加载的扩展名
+    pub ignore: Vec<String>,      // 忽略的路径（glob，相对扫描的文件夹）
+    pub follow_symlinks: bool,    // 是否跟随符号链接
+    pub include_hidden: bool,     // 是否包含隐藏文件和文件夹
+    pub max_depth: Option<usize>, // /folder 默认的递归深度，未设置时不限
+    pub max_entries: usize,       // 单次扫描最多遍历的条目数
+    pub timeout_secs: u64,        // 单次扫描的最长时间（秒）
+    pub watch: bool,              // 扫描后监视文件夹，自动更新播放列表
+    pub split_cue: bool,          // 按 cue 文件把整张专辑的单个文件拆分为音轨
+}
+
+impl Default for ScanningConfig {
+    fn default() -> Self {
+        Self {
+            extensions: ["mp3", "flac", "wav", "ogg", "m4a", "aac"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            // 常见的非音乐目录；不带 **/ 的只在扫描的根目录下生效（如对 / 扫描时的 proc）
+            ignore: [
+                "**/node_modules",
+                "**/$RECYCLE.BIN",
+                "**/System Volume Information",
+                "Windows",
+                "Program Files*",
+                "proc",
+                "sys",
+                "dev",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            follow_symlinks: false,
+            include_hidden: false,
+            max_depth: None,
+            max_entries: 50_000,
+            timeout_secs: 10,
+            watch: false,
+            split_cue: true,
+        }
+    }
+}
+
+impl ScanningConfig {
+    /// 转换为扫描使用的选项，无效的忽略模式会被跳过
+    pub fn scan_options(&self) -> ScanOptions {
+        let mut ignore = GlobSetBuilder::new();
+        for pattern in &self.ignore {
+            if let Ok(glob) = Glob::new(pattern) {
+                ignore.add(glob);
+            }
+        }
+        ScanOptions {
+            extensions: self.extensions.clone(),
+            ignore: ignore.build().unwrap_or_else(|_| GlobSet::empty()),
+            follow_symlinks: self.follow_symlinks,
+            include_hidden: self.include_hidden,
+            max_entries: self.max_entries,
+            timeout: Duration::from_secs(self.timeout_secs),
+            split_cue: self.split_cue,
+        }
+    }
+}
+
+/// [paths] 路径设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    pub music_folder: Option<String>, // 启动时自动加载的音乐文件夹
+}
+
+/// [modes.<模式>] 各播放模式覆盖的设置，未设置的项使用 [playback] 中的值
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModesConfig {
+    pub sequential: ModeConfig,
+    pub repeatone: ModeConfig,
+    pub shuffle: ModeConfig,
+    pub audiobook: ModeConfig,
+}
+
+impl ModesConfig {
+    pub fn get(&self, mode: PlaybackMode) -> &ModeConfig {
+        match mode {
+            PlaybackMode::Sequential => &self.sequential,
+            PlaybackMode::RepeatOne => &self.repeatone,
+            PlaybackMode::Shuffle => &self.shuffle,
+            PlaybackMode::Audiobook => &self.audiobook,
+        }
+    }
+}
+
+/// 一个播放模式覆盖的设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModeConfig {
+    pub volume: Option<u8>,       // 切换到该模式时的音量（0-100）
+    pub fade_in_ms: Option<u64>,  // 该模式下的淡入时长（毫秒）
+    pub fade_out_ms: Option<u64>, // 该模式下的淡出时长（毫秒）
+}
+
+/// 读取配置时发现的问题，显示时使用当前界面语言
+#[derive(Debug)]
+pub enum ConfigWarning {
+    Unreadable(PathBuf, String),
+    Syntax(PathBuf, String),
+    UnknownKey(String),
+    NotTable(String),
+    Invalid(String, String), // 键名、解析错误
+    OutOfRange(&'static str, String, String),
+    TooSmall(&'static str, usize, usize),
+    NotPositive(&'static str),
+    UnknownColor(String, String),
+    UnknownTheme(String),
+    InvalidPattern(String, String),
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            ConfigWarning::Unreadable(path, e) => tr!(Msg::CfgUnreadable, path.display(), e),
+            ConfigWarning::Syntax(path, e) => tr!(Msg::CfgSyntax, path.display(), e),
+            ConfigWarning::UnknownKey(key) => tr!(Msg::CfgUnknownKey, key),
+            ConfigWarning::NotTable(name) => tr!(Msg::CfgNotTable, name, name),
+            ConfigWarning::Invalid(key, e) => format!("{}: {}", key, e),
+            ConfigWarning::OutOfRange(key, value, range) => {
+                tr!(Msg::CfgOutOfRange, key, value, range)
+            }
+            ConfigWarning::TooSmall(key, value, min) => tr!(Msg::CfgTooSmall, key, value, min),
+            ConfigWarning::NotPositive(key) => tr!(Msg::CfgMustBePositive, key),
+            ConfigWarning::UnknownColor(key, value) => tr!(Msg::CfgUnknownColor, key, value),
+            ConfigWarning::UnknownTheme(name) => tr!(Msg::CfgUnknownTheme, name),
+            ConfigWarning::InvalidPattern(pattern, e) => {
+                tr!(Msg::CfgInvalidPattern, pattern, e)
+            }
+        };
+        f.write_str(&text)
+    }
+}
+
+impl Config {
+    /// 配置文件路径：平台配置目录下的 BeatCLI/beatcli.toml
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("BeatCLI").join(CONFIG_FILE_NAME))
+    }
+
+    /// 读取配置文件，文件不存在时使用默认配置。
+    /// 无效的配置项会被忽略（使用默认值），并在返回的警告列表中说明是哪一项
+    pub fn load() -> (Self, Vec<ConfigWarning>) {
+        let mut warnings = Vec::new();
+        let Some(path) = Self::path() else {
+            return (Self::default(), warnings);
+        };
+        if !path.exists() {
+            return (Self::default(), warnings);
+        }
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) => {
+                warnings.push(ConfigWarning::Unreadable(path.clone(), e.to_string()));
+                return (Self::default(), warnings);
+            }
+        };
+        let mut root = match content.parse::<toml::Table>() {
+            Ok(t) => t,
+            Err(e) => {
+                warnings.push(ConfigWarning::Syntax(path.clone(), e.message().to_string()));
+                return (Self::default(), warnings);
+            }
+        };
+
+        let mut config = Self {
+            playback: load_section(&mut root, "playback", &mut warnings),
+            ui: load_section(&mut root, "ui", &mut warnings),
+            scanning: load_section(&mut root, "scanning", &mut warnings),
+            paths: load_section(&mut root, "paths", &mut warnings),
+            modes: load_section(&mut root, "modes", &mut warnings),
+            aliases: if root.contains_key("aliases") {
+                load_section(&mut root, "aliases", &mut warnings)
+            } else {
+                default_aliases()
+            },
+        };
+        for key in root.keys() {
+            warnings.push(ConfigWarning::UnknownKey(key.clone()));
+        }
+        config.validate(&mut warnings);
+        (config, warnings)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!(tr!(Msg::NoConfigDir)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    // 检查取值范围等无法由类型表达的约束，不合法的值恢复为默认值
+    fn validate(&mut self, warnings: &mut Vec<ConfigWarning>) {
+        if self.playback.volume > 100 {
+            warnings.push(ConfigWarning::OutOfRange(
+                "playback.volume",
+                self.playback.volume.to_string(),
+                "0-100".to_string(),
+            ));
+            self.playback.volume = PlaybackConfig::default().volume;
+        }
+        if !(MIN_SPEED..=MAX_SPEED).contains(&self.playback.audiobook_speed) {
+            warnings.push(ConfigWarning::OutOfRange(
+                "playback.audiobook_speed",
+                self.playback.audiobook_speed.to_string(),
+                format!("{}-{}", MIN_SPEED, MAX_SPEED),
+            ));
+            self.playback.audiobook_speed = PlaybackConfig::default().audiobook_speed;
+        }
+        if !(MIN_PREAMP..=MAX_PREAMP).contains(&self.playback.replaygain_preamp) {
+            warnings.push(ConfigWarning::OutOfRange(
+                "playback.replaygain_preamp",
+                self.playback.replaygain_preamp.to_string(),
+                format!("{}-{}", MIN_PREAMP, MAX_PREAMP),
+            ));
+            self.playback.replaygain_preamp = PlaybackConfig::default().replaygain_preamp;
+        }
+        for (name, mode) in [
+            ("modes.sequential.volume", &mut self.modes.sequential),
+            ("modes.repeatone.volume", &mut self.modes.repeatone),
+            ("modes.shuffle.volume", &mut self.modes.shuffle),
+            ("modes.audiobook.volume", &mut self.modes.audiobook),
+        ] {
+            if let Some(volume) = mode.volume.filter(|v| *v > 100) {
+                warnings.push(ConfigWarning::OutOfRange(
+                    name,
+                    volume.to_string(),
+                    "0-100".to_string(),
+                ));
+                mode.volume = None;
+            }
+        }
+        if self.ui.width != 0 && self.ui.width < 40 {
+            warnings.push(ConfigWarning::TooSmall("ui.width", self.ui.width, 40));
+            self.ui.width = UiConfig::default().width;
+        }
+        if self.scanning.max_entries == 0 {
+            warnings.push(ConfigWarning::NotPositive("scanning.max_entries"));
+            self.scanning.max_entries = ScanningConfig::default().max_entries;
+        }
+        if self.scanning.timeout_secs == 0 {
+            warnings.push(ConfigWarning::NotPositive("scanning.timeout_secs"));
+            self.scanning.timeout_secs = ScanningConfig::default().timeout_secs;
+        }
+        if self.scanning.max_depth == Some(0) {
+            warnings.push(ConfigWarning::NotPositive("scanning.max_depth"));
+            self.scanning.max_depth = None;
+        }
+
+        check_colors("ui.colors", &mut self.ui.colors, warnings);
+        self.ui.themes = std::mem::take(&mut self.ui.themes)
+            .into_iter()
+            .map(|(name, mut colors)| {
+                check_colors(&format!("ui.themes.{}", name), &mut colors, warnings);
+                (name.to_lowercase(), colors)
+            })
+            .collect();
+        self.ui.theme = self.ui.theme.trim().to_lowercase();
+        if ThemePreset::parse(&self.ui.theme).is_none()
+            && !self.ui.themes.contains_key(&self.ui.theme)
+        {
+            warnings.push(ConfigWarning::UnknownTheme(self.ui.theme.clone()));
+            self.ui.theme = UiConfig::default().theme;
+        }
+
+        self.scanning.extensions = self
+            .scanning
+            .extensions
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+            .filter(|ext| !ext.is_empty())
+            .collect();
+        self.scanning
+            .ignore
+            .retain(|pattern| match Glob::new(pattern) {
+                Ok(_) => true,
+                Err(e) => {
+                    warnings.push(ConfigWarning::InvalidPattern(
+                        pattern.clone(),
+                        e.to_string(),
+                    ));
+                    false
+                }
+            });
+    }
+}
+
+/// 解析颜色名称（black、red、dark_red、grey 等）或 #ff6600 形式的 RGB
+pub fn parse_color(name: &str) -> Option<Color> {
+    let name = name.trim().to_lowercase();
+    if let Some(hex) = name.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+    Color::try_from(name.as_str()).ok()
+}
+
+// 无法解析的颜色报告后丢弃，使用方案中的颜色
+fn check_colors(section: &str, colors: &mut ColorsConfig, warnings: &mut Vec<ConfigWarning>) {
+    for (key, value) in [
+        ("title", &mut colors.title),
+        ("accent", &mut colors.accent),
+        ("success", &mut colors.success),
+        ("error", &mut colors.error),
+        ("info", &mut colors.info),
+        ("lyric_current", &mut colors.lyric_current),
+        ("lyric_dim", &mut colors.lyric_dim),
+        ("interlude", &mut colors.interlude),
+        ("progress", &mut colors.progress),
+    ] {
+        if let Some(name) = value
+            && parse_color(name).is_none()
+        {
+            warnings.push(ConfigWarning::UnknownColor(
+                format!("{}.{}", section, key),
+                name.clone(),
+            ));
+            *value = None;
+        }
+    }
+}
+
+// 逐项读取配置中的一个表：无法解析的键单独报告并丢弃，其余键照常生效
+fn load_section<T>(root: &mut toml::Table, name: &str, warnings: &mut Vec<ConfigWarning>) -> T
+where
+    T: DeserializeOwned + Default,
+{
+    let Some(value) = root.remove(name) else {
+        return T::default();
+    };
+    let toml::Value::Table(mut table) = value else {
+        warnings.push(ConfigWarning::NotTable(name.to_string()));
+        return T::default();
+    };
+
+    table.retain(|key, value| {
+        let single = toml::Table::from_iter([(key.to_string(), value.clone())]);
+        match toml::Value::Table(single).try_into::<T>() {
+            Ok(_) => true,
+            Err(e) => {
+                warnings.push(ConfigWarning::Invalid(
+                    format!("{}.{}", name, key),
+                    e.message().to_string(),
+                ));
+                false
+            }
+        }
+    });
+    toml::Value::Table(table).try_into().unwrap_or_default()
+}
@@ -0,0 +1,114 @@
+use crate::command::Command;
+
+/// 需要确认的命令接下来怎么处理
+#[derive(Debug, PartialEq)]
+pub enum Decision {
+    Run,    // 已经确认过，或脚本和管道输入中指定了 --yes
+    Ask,    // 已记下命令，提示输入 y 确认
+    Refuse, // 脚本和管道输入中没有 --yes，无法询问
+}
+
+/// 破坏性命令的确认状态：命令先记下并提示，输入 y 后重新执行，输入 n 或其他命令时取消
+#[derive(Default)]
+pub struct Confirmation {
+    pending: Option<Command>, // 等待确认的命令
+    approved: bool,           // 下一次执行的需要确认的命令已经确认过
+}
+
+impl Confirmation {
+    pub fn ask(&mut self, cmd: Command) {
+        self.pending = Some(cmd);
+        self.approved = false;
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// 取出等待确认的命令，之后重新执行它时不再询问
+    pub fn approve(&mut self) -> Option<Command> {
+        let cmd = self.pending.take()?;
+        self.approved = true;
+        Some(cmd)
+    }
+
+    /// 取消等待确认的命令
+    pub fn cancel(&mut self) -> Option<Command> {
+        self.pending.take()
+    }
+
+    /// 需要确认的命令执行时调用：已经确认过时返回 true，并清除确认标记
+    pub fn take_approval(&mut self) -> bool {
+        std::mem::take(&mut self.approved)
+    }
+
+    /// 需要确认的命令执行前调用。batch 为脚本或管道输入，无法交互询问
+    pub fn decide(&mut self, cmd: Command, batch: bool, assume_yes: bool) -> Decision {
+        if self.take_approval() {
+            return Decision::Run;
+        }
+        if batch {
+            return if assume_yes {
+                Decision::Run
+            } else {
+                Decision::Refuse
+            };
+        }
+        self.ask(cmd);
+        Decision::Ask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approved_command_runs_once() {
+        let mut confirmation = Confirmation::default();
+        assert_eq!(
+            confirmation.decide(Command::Clear, false, false),
+            Decision::Ask
+        );
+        assert!(confirmation.is_pending());
+        assert!(matches!(confirmation.approve(), Some(Command::Clear)));
+        assert!(!confirmation.is_pending());
+        assert_eq!(
+            confirmation.decide(Command::Clear, false, false),
+            Decision::Run
+        );
+        // 确认只对重新执行的那一次有效
+        assert_eq!(
+            confirmation.decide(Command::Clear, false, false),
+            Decision::Ask
+        );
+    }
+
+    #[test]
+    fn cancelled_command_is_not_approved() {
+        let mut confirmation = Confirmation::default();
+        confirmation.ask(Command::Clear);
+        assert!(matches!(confirmation.cancel(), Some(Command::Clear)));
+        assert!(!confirmation.is_pending());
+        assert!(confirmation.approve().is_none());
+        assert!(!confirmation.take_approval());
+        assert_eq!(
+            confirmation.decide(Command::Clear, false, false),
+            Decision::Ask
+        );
+    }
+
+    #[test]
+    fn batch_input_needs_assume_yes() {
+        let mut confirmation = Confirmation::default();
+        assert_eq!(
+            confirmation.decide(Command::Clear, true, false),
+            Decision::Refuse
+        );
+        assert!(!confirmation.is_pending());
+        assert_eq!(
+            confirmation.decide(Command::Clear, true, true),
+            Decision::Run
+        );
+    }
+}
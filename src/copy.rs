@@ -0,0 +1,90 @@
+use crate::command::CopyFlags;
+use crate::cue;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// /copyto 的结果
+#[derive(Debug, Default)]
+pub struct CopySummary {
+    pub copied: usize,         // 复制（或链接）的歌曲数
+    pub lyrics: usize,         // 一起复制的歌词文件数
+    pub skipped: Vec<PathBuf>, // 目标文件夹中已有同名文件而跳过的歌曲
+    pub failed: usize,         // 复制失败的歌曲数
+}
+
+/// 播放列表条目对应的音频文件，按列表顺序去重（同一整轨文件的多个 cue 音轨只复制一次）
+pub fn audio_files(items: &[PathBuf]) -> Vec<PathBuf> {
+    let mut seen = HashSet::new();
+    items
+        .iter()
+        .map(|item| cue::audio_file(item))
+        .filter(|file| seen.insert(file.clone()))
+        .collect()
+}
+
+/// 把 files 复制到 dest 文件夹，文件夹不存在时创建；同名文件不覆盖。
+/// 每处理完一首调用 progress(已处理数, 总数)
+pub fn copy_files(
+    files: &[PathBuf],
+    dest: &Path,
+    flags: CopyFlags,
+    mut progress: impl FnMut(usize, usize),
+) -> io::Result<CopySummary> {
+    fs::create_dir_all(dest)?;
+    let mut summary = CopySummary::default();
+    for (i, file) in files.iter().enumerate() {
+        match transfer(file, dest, flags.symlink) {
+            Ok(true) => {
+                summary.copied += 1;
+                let lrc = file.with_extension("lrc");
+                if flags.lrc && lrc.is_file() {
+                    match transfer(&lrc, dest, flags.symlink) {
+                        Ok(true) => summary.lyrics += 1,
+                        Ok(false) => {
+                            log::warn!("lyrics already exist in destination: {}", lrc.display())
+                        }
+                        Err(e) => log::warn!("failed to copy {}: {}", lrc.display(), e),
+                    }
+                }
+            }
+            Ok(false) => {
+                log::warn!(
+                    "skipping {}: name already exists in destination",
+                    file.display()
+                );
+                summary.skipped.push(file.clone());
+            }
+            Err(e) => {
+                log::warn!("failed to copy {}: {}", file.display(), e);
+                summary.failed += 1;
+            }
+        }
+        progress(i + 1, files.len());
+    }
+    Ok(summary)
+}
+
+// 复制或链接一个文件到 dest，目标已存在时返回 false
+fn transfer(file: &Path, dest: &Path, symlink: bool) -> io::Result<bool> {
+    let name = file
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no file name"))?;
+    let target = dest.join(name);
+    // symlink_metadata 对指向不存在文件的链接也能判断出已存在
+    if target.symlink_metadata().is_ok() {
+        return Ok(false);
+    }
+    if symlink {
+        // 链接使用绝对路径，移动目标文件夹后仍然有效
+        let source = fs::canonicalize(file)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&source, &target)?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(&source, &target)?;
+    } else {
+        fs::copy(file, &target)?;
+    }
+    Ok(true)
+}
@@ -0,0 +1,154 @@
+use crate::i18n::{Msg, tr};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// cue 文件中的一个音轨
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueTrack {
+    pub number: u32,
+    pub title: Option<String>,
+    pub file: PathBuf,         // 音轨所在的音频文件，相对路径按 cue 所在目录解析
+    pub start: Duration,       // INDEX 01 的位置
+    pub end: Option<Duration>, // 同一文件中下一音轨的开始位置，文件的最后一个音轨为 None
+}
+
+impl CueTrack {
+    /// 显示用的标题，cue 中没有 TITLE 时为 音轨 N
+    pub fn display_title(&self) -> String {
+        self.title
+            .clone()
+            .unwrap_or_else(|| tr!(Msg::CueDefaultTitle, self.number))
+    }
+}
+
+pub fn is_cue(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("cue"))
+}
+
+/// 读取 cue 文件中的音轨，按文件内的顺序排列；无法读取时返回空列表
+pub fn load(path: &Path) -> Vec<CueTrack> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let dir = path.parent().unwrap_or(Path::new(""));
+    parse(&content, dir)
+}
+
+// 解析 FILE / TRACK / TITLE / INDEX 01，每个 FILE 之后的音轨属于该文件
+fn parse(content: &str, dir: &Path) -> Vec<CueTrack> {
+    let mut tracks: Vec<CueTrack> = Vec::new();
+    let mut file: Option<PathBuf> = None;
+    let mut number: Option<u32> = None;
+    let mut title: Option<String> = None;
+    for line in content.lines() {
+        let line = line.trim();
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        match keyword.to_uppercase().as_str() {
+            // FILE "name.flac" WAVE，文件名可能带空格
+            "FILE" => {
+                let name = match rest.trim().strip_prefix('"') {
+                    Some(quoted) => quoted.split('"').next().unwrap_or(""),
+                    None => rest.split_whitespace().next().unwrap_or(""),
+                };
+                file = Some(dir.join(name));
+                number = None;
+            }
+            "TRACK" => {
+                number = rest.split_whitespace().next().and_then(|n| n.parse().ok());
+                title = None;
+            }
+            "TITLE" if number.is_some() => title = Some(rest.trim().trim_matches('"').to_string()),
+            "INDEX" => {
+                let mut parts = rest.split_whitespace();
+                if parts.next() != Some("01") {
+                    continue;
+                }
+                let (Some(n), Some(file), Some(start)) =
+                    (number, &file, parts.next().and_then(parse_time))
+                else {
+                    continue;
+                };
+                if let Some(prev) = tracks.last_mut()
+                    && prev.file == *file
+                {
+                    prev.end = Some(start);
+                }
+                tracks.push(CueTrack {
+                    number: n,
+                    title: title.take().filter(|t| !t.is_empty()),
+                    file: file.clone(),
+                    start,
+                    end: None,
+                });
+            }
+            _ => {}
+        }
+    }
+    tracks
+}
+
+// cue 时间格式 mm:ss:ff，ff 为帧（每秒 75 帧）
+fn parse_time(s: &str) -> Option<Duration> {
+    let mut parts = s.split(':').map(|p| p.parse::<u64>().ok());
+    let (m, sec, frames) = (parts.next()??, parts.next()??, parts.next()??);
+    Some(Duration::from_millis(
+        (m * 60 + sec) * 1000 + frames * 1000 / 75,
+    ))
+}
+
+/// 音轨在播放列表中的条目：cue 文件路径下的虚拟文件 "NN - 标题"，
+/// 文件名即为列表中显示的名称
+pub fn track_entry(cue: &Path, track: &CueTrack) -> PathBuf {
+    let title = track.display_title().replace(['/', '\\'], "-");
+    cue.join(format!("{:02} - {}", track.number, title))
+}
+
+/// 条目对应的 cue 音轨，普通文件返回 None
+pub fn resolve(entry: &Path) -> Option<CueTrack> {
+    let cue = entry.parent()?;
+    if !is_cue(cue) || !cue.is_file() {
+        return None;
+    }
+    let name = entry.file_name()?.to_str()?;
+    let digits = name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len());
+    let number: u32 = name[..digits].parse().ok()?;
+    load(cue).into_iter().find(|t| t.number == number)
+}
+
+/// 条目实际播放的音频文件，普通文件为其本身
+pub fn audio_file(entry: &Path) -> PathBuf {
+    resolve(entry).map_or_else(|| entry.to_path_buf(), |track| track.file)
+}
+
+/// 用 cue 的音轨替换列表中被 cue 引用的整轨文件，音轨插入在原文件的位置。
+/// 只展开列表中已有的文件，不引用列表中文件的 cue 被忽略
+pub fn expand(files: Vec<PathBuf>, cues: &[PathBuf]) -> Vec<PathBuf> {
+    let listed: HashSet<&PathBuf> = files.iter().collect();
+    let mut entries: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for cue in cues {
+        for track in load(cue) {
+            if listed.contains(&track.file) {
+                entries
+                    .entry(track.file.clone())
+                    .or_default()
+                    .push(track_entry(cue, &track));
+            }
+        }
+    }
+    if entries.is_empty() {
+        return files;
+    }
+    let mut expanded = Vec::with_capacity(files.len());
+    for file in files {
+        match entries.remove(&file) {
+            Some(tracks) => expanded.extend(tracks),
+            None => expanded.push(file),
+        }
+    }
+    expanded
+}
@@ -0,0 +1,230 @@
+use crate::player::PlayError;
+use rodio::{Decoder, Source};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::Duration;
+
+/// 解码后的音频，可直接交给 rodio 播放
+pub type AudioSource = Box<dyn Source<Item = i16> + Send>;
+
+/// 打开音频文件并从 start 开始解码，同时返回整个文件的时长（无法确定时为 None）。
+/// 开启 symphonia 功能时，Matroska 容器（webm、mka）改用 symphonia 解码
+pub fn open(path: &Path, start: Duration) -> Result<(AudioSource, Option<Duration>), PlayError> {
+    #[cfg(feature = "symphonia")]
+    if symphonia_backend::handles(path) {
+        return symphonia_backend::open(path, start);
+    }
+    let decoder = File::open(path)
+        .map_err(PlayError::Open)
+        .and_then(|f| Decoder::new(BufReader::new(f)).map_err(PlayError::Decode))?;
+    let total = decoder.total_duration();
+    Ok((Box::new(decoder.skip_duration(start)), total))
+}
+
+/// 当前构建额外支持的扩展名，扫描时与配置中的扩展名一起作为歌曲加载
+pub fn extra_extensions() -> &'static [&'static str] {
+    if cfg!(feature = "symphonia") {
+        &["webm", "mka"]
+    } else {
+        &[]
+    }
+}
+
+#[cfg(feature = "symphonia")]
+mod symphonia_backend {
+    use super::AudioSource;
+    use crate::player::PlayError;
+    use rodio::Source;
+    use std::fs::File;
+    use std::path::Path;
+    use std::time::Duration;
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::{CODEC_TYPE_NULL, CODEC_TYPE_OPUS, Decoder, DecoderOptions};
+    use symphonia::core::errors::Error;
+    use symphonia::core::formats::{FormatOptions, FormatReader, SeekMode, SeekTo};
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    // Ogg Opus 也交给 symphonia 识别，以便给出明确的错误原因
+    pub fn handles(path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .is_some_and(|e| e == "opus" || super::extra_extensions().contains(&e.as_str()))
+    }
+
+    pub fn open(
+        path: &Path,
+        start: Duration,
+    ) -> Result<(AudioSource, Option<Duration>), PlayError> {
+        let file = File::open(path).map_err(PlayError::Open)?;
+        let stream = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+        let probed = symphonia::default::get_probe()
+            .format(
+                &hint,
+                stream,
+                &FormatOptions {
+                    enable_gapless: true,
+                    ..Default::default()
+                },
+                &MetadataOptions::default(),
+            )
+            .map_err(PlayError::Symphonia)?;
+        let source = SymphoniaSource::new(probed.format, start)?;
+        let total = source.total;
+        Ok((Box::new(source), total))
+    }
+
+    // 逐个数据包解码，样本统一转换为 i16
+    struct SymphoniaSource {
+        format: Box<dyn FormatReader>,
+        decoder: Box<dyn Decoder>,
+        track_id: u32,
+        buffer: Option<SampleBuffer<i16>>,
+        pos: usize,
+        channels: u16,
+        sample_rate: u32,
+        total: Option<Duration>,
+    }
+
+    impl SymphoniaSource {
+        // 跳转到 start 并解码第一个数据包，以便确定声道数和采样率
+        fn new(mut format: Box<dyn FormatReader>, start: Duration) -> Result<Self, PlayError> {
+            let track = format
+                .tracks()
+                .iter()
+                .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+                .ok_or(PlayError::Symphonia(Error::Unsupported("no audio track")))?;
+            let params = track.codec_params.clone();
+            let track_id = track.id;
+            // symphonia 0.5 没有 Opus 解码器，单独说明原因
+            if params.codec == CODEC_TYPE_OPUS {
+                return Err(PlayError::UnsupportedCodec("Opus"));
+            }
+            let decoder = symphonia::default::get_codecs()
+                .make(&params, &DecoderOptions::default())
+                .map_err(PlayError::Symphonia)?;
+            let total = params.time_base.zip(params.n_frames).map(|(base, frames)| {
+                let time = base.calc_time(frames);
+                Duration::from_secs(time.seconds) + Duration::from_secs_f64(time.frac)
+            });
+
+            // 精确跳转到包含 start 的数据包后，丢弃该包中 start 之前的帧；
+            // 不支持跳转时从头解码丢弃
+            let mut skip_frames = 0;
+            if !start.is_zero() {
+                let seeked = format.seek(
+                    SeekMode::Accurate,
+                    SeekTo::Time {
+                        time: start.into(),
+                        track_id: Some(track_id),
+                    },
+                );
+                skip_frames = match seeked {
+                    Ok(to) => to.required_ts.saturating_sub(to.actual_ts),
+                    Err(_) => params
+                        .sample_rate
+                        .map_or(0, |rate| (start.as_secs_f64() * f64::from(rate)) as u64),
+                };
+            }
+
+            let mut source = Self {
+                format,
+                decoder,
+                track_id,
+                buffer: None,
+                pos: 0,
+                channels: params.channels.map_or(2, |c| c.count() as u16),
+                sample_rate: params.sample_rate.unwrap_or(44_100),
+                total,
+            };
+            source.decode_next();
+            let mut skip = skip_frames as usize * usize::from(source.channels);
+            while skip > 0 {
+                let len = source.buffer.as_ref().map_or(0, |b| b.len());
+                let n = skip.min(len - source.pos);
+                source.pos += n;
+                skip -= n;
+                if source.pos >= len && !source.decode_next() {
+                    break;
+                }
+            }
+            Ok(source)
+        }
+
+        // 解码下一个数据包，没有更多数据时返回 false；损坏的数据包直接跳过
+        fn decode_next(&mut self) -> bool {
+            loop {
+                let packet = match self.format.next_packet() {
+                    Ok(packet) => packet,
+                    Err(_) => return false,
+                };
+                if packet.track_id() != self.track_id {
+                    continue;
+                }
+                match self.decoder.decode(&packet) {
+                    Ok(decoded) => {
+                        let spec = *decoded.spec();
+                        let frames = decoded.capacity();
+                        let samples = frames * spec.channels.count();
+                        if self.buffer.as_ref().is_none_or(|b| b.capacity() < samples) {
+                            self.buffer = Some(SampleBuffer::new(frames as u64, spec));
+                        }
+                        let Some(buffer) = self.buffer.as_mut() else {
+                            return false;
+                        };
+                        buffer.copy_interleaved_ref(decoded);
+                        self.channels = spec.channels.count() as u16;
+                        self.sample_rate = spec.rate;
+                        self.pos = 0;
+                        if !buffer.is_empty() {
+                            return true;
+                        }
+                    }
+                    Err(Error::DecodeError(e)) => {
+                        log::debug!("skipping corrupt packet: {}", e);
+                    }
+                    Err(_) => return false,
+                }
+            }
+        }
+    }
+
+    impl Iterator for SymphoniaSource {
+        type Item = i16;
+
+        fn next(&mut self) -> Option<i16> {
+            let len = self.buffer.as_ref().map_or(0, |b| b.len());
+            if self.pos >= len && !self.decode_next() {
+                return None;
+            }
+            let sample = self.buffer.as_ref()?.samples()[self.pos];
+            self.pos += 1;
+            Some(sample)
+        }
+    }
+
+    impl Source for SymphoniaSource {
+        fn current_frame_len(&self) -> Option<usize> {
+            self.buffer.as_ref().map(|b| b.len())
+        }
+
+        fn channels(&self) -> u16 {
+            self.channels
+        }
+
+        fn sample_rate(&self) -> u32 {
+            self.sample_rate
+        }
+
+        fn total_duration(&self) -> Option<Duration> {
+            self.total
+        }
+    }
+}
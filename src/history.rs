@@ -0,0 +1,74 @@
+use crate::i18n::{Msg, tr};
+use crate::playlist::canonical_key;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const HISTORY_DIR: &str = ".beatcli";
+const HISTORY_FILE: &str = "history.json";
+
+// 距离结尾不足这么长时视为已播完，不再保存位置
+const NEAR_END: Duration = Duration::from_secs(5);
+
+/// 播放历史：规范路径 -> 上次停止的位置，用于下次从该位置继续（有声书、播客等）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    pub entries: HashMap<PathBuf, HistoryEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub last_position_ms: u64,
+}
+
+impl History {
+    /// 历史文件路径：~/.beatcli/history.json
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|dir| dir.join(HISTORY_DIR).join(HISTORY_FILE))
+    }
+
+    /// 读取历史文件，文件不存在或无法解析时返回空历史
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!(tr!(Msg::NoHomeDir)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 歌曲上次停止的位置（毫秒）
+    pub fn position(&self, path: &Path) -> Option<u64> {
+        self.entries
+            .get(&canonical_key(path))
+            .map(|e| e.last_position_ms)
+    }
+
+    /// 记录播放位置；位置为 0 或距离结尾不足 5 秒时清除记录
+    pub fn set_position(&mut self, path: &Path, ms: u64, total: Option<Duration>) {
+        let near_end = total.is_some_and(|t| Duration::from_millis(ms) + NEAR_END >= t);
+        if ms == 0 || near_end {
+            self.clear_position(path);
+            return;
+        }
+        self.entries.insert(
+            canonical_key(path),
+            HistoryEntry {
+                last_position_ms: ms,
+            },
+        );
+    }
+
+    pub fn clear_position(&mut self, path: &Path) {
+        self.entries.remove(&canonical_key(path));
+    }
+}
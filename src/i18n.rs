@@ -0,0 +1,1484 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Lang {
+    #[default]
+    #[serde(rename = "zh-CN", alias = "zh", alias = "zh-cn")]
+    Zh,
+    #[serde(rename = "en-US", alias = "en", alias = "en-us")]
+    En,
+}
+
+impl Lang {
+    /// 解析语言名，如 zh、zh-CN、en、en-US（不区分大小写）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "zh" | "zh-cn" | "cn" => Some(Lang::Zh),
+            "en" | "en-us" => Some(Lang::En),
+            _ => None,
+        }
+    }
+}
+
+static LANG: AtomicU8 = AtomicU8::new(0);
+
+/// 设置界面语言，之后的所有消息都使用该语言
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+/// 用参数依次替换模板中的 {}
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(pos) = rest.find("{}") {
+        out.push_str(&rest[..pos]);
+        match args.next() {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str("{}"),
+        }
+        rest = &rest[pos + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// 当前语言的消息文本：`tr!(Msg::Paused)`、`tr!(Msg::VolumeSet, v)`
+macro_rules! tr {
+    ($msg:expr) => {
+        $crate::i18n::Msg::text($msg).to_string()
+    };
+    ($msg:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill(
+            $crate::i18n::Msg::text($msg),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+pub(crate) use tr;
+
+/// 界面上的所有消息，模板中的 {} 按顺序替换为参数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    // 通用
+    ErrorLine,
+    Or,
+    ListSep,
+    DidYouMean,
+    Unknown,
+    UnknownFileName,
+    NoHomeDir,
+    NoConfigDir,
+
+    // 启动与命令行
+    CliUsage,
+    CliMissingValue,
+    CliInvalidVolume,
+    CliInvalidDuration,
+    CliInvalidLang,
+    CliInvalidPlay,
+    CliInvalidMode,
+    CliExtraArgument,
+    CliOnceConflict,
+    CliUnknownOption,
+    CliDurationNeedsOnce,
+    CliScriptOnly,
+    CliOnceAndScript,
+    CliFileNotFound,
+    CliPlaying,
+    CliDuration,
+    CliInterrupted,
+    ScriptNotFound,
+    ConfigWarning,
+    AliasShadowed,
+    PlayerInitFailed,
+    RedrawStats,
+
+    // 配置文件
+    CfgUnreadable,
+    CfgSyntax,
+    CfgUnknownKey,
+    CfgNotTable,
+    CfgOutOfRange,
+    CfgTooSmall,
+    CfgMustBePositive,
+    CfgUnknownColor,
+    CfgUnknownTheme,
+    ThemeUnknown,
+    CfgInvalidPattern,
+
+    // 命令解析
+    UnknownCommand,
+    AmbiguousCommand,
+    HelpHint,
+    MissingArgument,
+    InvalidArgument,
+    AliasCycle,
+    AliasTooDeep,
+    AliasInvalidName,
+    AliasIsBuiltin,
+    AliasNoTarget,
+    ExpectDepth,
+    ExpectCount,
+    ExpectMode,
+    CurrentMode,
+    ExpectSpeed,
+    ExpectPlaylistSub,
+    ExpectSort,
+    ExpectVolume,
+    ExpectStep,
+    ExpectOnOff,
+    ExpectConfigSub,
+    ExpectIndex,
+    ExpectRange,
+    ExpectPage,
+    ExpectLang,
+    ExpectRgMode,
+    ExpectBorder,
+    ExpectDuration,
+    ExpectSeek,
+
+    // 帮助
+    HelpCommands,
+    HelpFooter,
+    HelpIsAlias,
+    HelpNotFound,
+    HelpSeeAll,
+    HelpUsage,
+    HelpShort,
+    HelpExamples,
+
+    // 脚本
+    ScriptTooDeep,
+    ScriptUnreadable,
+    ScriptStopped,
+    SleepInteractive,
+
+    // 文件夹与播放列表
+    PathEmpty,
+    PathNotFound,
+    NotAFolder,
+    DepthUnlimited,
+    DepthLevels,
+    SkippedUnsupported,
+    SkippedHidden,
+    NoAudioFound,
+    ScanFound,
+    ScanProgress,
+    BarVolume,
+    BarMode,
+    SkippedLinks,
+    ScanFailed,
+    ScanStopped,
+    ScanLimitEntries,
+    ScanLimitTimeout,
+    NoFolderYet,
+    Reloaded,
+    FolderChanged,
+    WatchOff,
+    Watching,
+    WatchPending,
+    WatchFailed,
+    EmptyPlaylistHint,
+    PlaylistHeader,
+    ListMore,
+    ListColumnName,
+    ListColumnArtist,
+    ListColumnDuration,
+    ListPageOutOfRange,
+    ListPageSizeSet,
+    PlaylistEmpty,
+    IndexOutOfRange,
+    SearchNone,
+    SearchResults,
+    SearchHint,
+    PlayNameAmbiguous,
+    Shuffled,
+    SortName,
+    SortTrack,
+    Sorted,
+    NoDuplicates,
+    DuplicatesRemoved,
+    PlaylistAlreadyEmpty,
+    PlaylistCleared,
+    UndoShuffle,
+    UndoSort,
+    UndoDedup,
+    UndoClear,
+    UndoRemove,
+    InvalidRange,
+    SongRemoved,
+    RangeRemoved,
+    RangeQueued,
+    UndoDone,
+    NothingToUndo,
+    NotPlayable,
+    AddDone,
+    UnsupportedPlaylist,
+    PlaylistReadFailed,
+    MergeDone,
+    CopyProgress,
+    CopyDone,
+    LinkDone,
+    CopyLyrics,
+    CopySkipped,
+    CopyErrors,
+    CopyFailed,
+    SnapshotSaved,
+    SnapshotSaveFailed,
+    NoFolderScanned,
+    SnapshotReadFailed,
+    DiffScanStopped,
+    DiffNone,
+    DiffHeader,
+    DiffAdded,
+    DiffRemoved,
+    DiffModified,
+    DiffCount,
+    DiffMore,
+
+    // 播放控制
+    OnlyOneSongReplay,
+    OnlyOneSongNext,
+    OnlyOneSongPrev,
+    QueueCleared,
+    SampleAdded,
+    SampleLoopHint,
+    GotoDone,
+    NothingPlaying,
+    NothingPlayingHint,
+    Paused,
+    Resumed,
+    NoSavedPosition,
+    ResumedFrom,
+    SeekTo,
+    SongMissing,
+    OpenFailed,
+    DecodeFailed,
+    #[cfg_attr(not(feature = "symphonia"), allow(dead_code))]
+    UnsupportedCodec,
+    NowPlaying,
+    LyricsLoadedTag,
+    ResumingFrom,
+    ResumePrompt,
+    RecoverPrompt,
+    Recovered,
+    SwitchedNext,
+    SwitchedPrev,
+    LastSong,
+    FirstSong,
+    NoNextSong,
+    NoPrevSong,
+    VolumeSet,
+    DeviceNotFound,
+    DeviceNone,
+    DeviceHeader,
+    DeviceSwitched,
+    DeviceSwitchFailed,
+    DeviceSaveFailed,
+    DeviceFallback,
+    DeviceLost,
+
+    // 播放模式
+    ModeSequential,
+    ModeRepeatOne,
+    ModeShuffle,
+    ModeAudiobook,
+    ModeNameSequential,
+    ModeNameRepeatOne,
+    ModeNameShuffle,
+    ModeNameAudiobook,
+    AlreadyInMode,
+    SwitchedMode,
+    ModeSettingsApplied,
+    ModeVolume,
+    ModeFadeIn,
+    ModeFadeOut,
+    SpeedNeedsAudiobook,
+    NoCurrentFile,
+    SpeedSet,
+    ChapterJump,
+    ChapterDefaultTitle,
+    CueDefaultTitle,
+    ChapterProgress,
+    AudiobookStatus,
+
+    // 歌词与显示
+    LyricsNeedPlaying,
+    LyricsNothingToExport,
+    ConfirmPrompt,
+    ConfirmBatch,
+    ConfirmCancelled,
+    NothingToConfirm,
+    ConfirmClear,
+    ConfirmOverwrite,
+    LyricsExported,
+    LyricsExportFailed,
+    LyricsShown,
+    LyricsHidden,
+    LyricsEmptyFile,
+    LyricsLoaded,
+    LyricsNoFile,
+    LyricsStatus,
+    LyricsModeNeedPlaying,
+    LyricsStreamUnsupported,
+    LyricsStream,
+    LyricsClear,
+    LyricsModeSwitched,
+    VuOn,
+    VuOff,
+    VizOn,
+    VizOff,
+    DebugOn,
+    DebugOff,
+    DebugHeader,
+    DebugClock,
+    DebugLyrics,
+    DebugState,
+    DebugQueue,
+    CompactOn,
+    CompactOff,
+    QuietOn,
+    QuietOff,
+    QuietTui,
+    OverlayHint,
+    PaneOn,
+    PaneOff,
+    PaneHeader,
+    LangSwitched,
+    ThemeSwitched,
+    BorderSwitched,
+    LoggingTo,
+    LoggingFailed,
+    FadeInSet,
+    FadeInOff,
+    FadeOutSet,
+    FadeOutOff,
+    RgOff,
+    RgTrack,
+    RgAlbum,
+    RgApplied,
+    RgNoTags,
+    VolumeWithGain,
+
+    // 别名与配置
+    NoAliases,
+    AliasHeader,
+    AliasSet,
+    AliasSaveFailed,
+    AliasRemoved,
+    AliasRemoveSaveFailed,
+    AliasNotFound,
+    ConfigCurrent,
+    ConfigShowFailed,
+
+    // /now
+    NowTitle,
+    InfoTitle,
+    InfoFile,
+    InfoPath,
+    InfoSize,
+    InfoFormat,
+    InfoAudio,
+    InfoSampleRate,
+    InfoChannels,
+    InfoBitrate,
+    InfoDuration,
+    InfoTags,
+    InfoNoTags,
+    InfoUnknown,
+    NowBasics,
+    NowSong,
+    NowState,
+    NowIndex,
+    NowMode,
+    NowVolume,
+    NowPosition,
+    NowLyricsSection,
+    NowCurrentLyrics,
+    NowLyricsLoaded,
+    NowLyricsEmpty,
+    NowLyricsMissing,
+    NowLyricsOff,
+
+    // 播放界面
+    WelcomeHint,
+    HotkeyHint,
+    StatusHeader,
+    StatusNow,
+    StatusNext,
+    StatusLine,
+    StatusVolume,
+    PlayStatePlaying,
+    PlayStatePaused,
+    PlayStateStopped,
+    NotPlaying,
+    NoneYet,
+    LyricsHeader,
+    TuiMessageTitle,
+    TuiInputTitle,
+    TuiUnsupported,
+    LyricsIntro,
+    GoodbyeTitle,
+    GoodbyeLine1,
+    GoodbyeLine2,
+}
+
+impl Msg {
+    pub fn text(self) -> &'static str {
+        match lang() {
+            Lang::Zh => zh(self),
+            Lang::En => en(self),
+        }
+    }
+}
+
+fn zh(msg: Msg) -> &'static str {
+    use Msg::*;
+    match msg {
+        ErrorLine => "错误: {}",
+        Or => " 或 ",
+        ListSep => "、",
+        DidYouMean => "，你是想输入 {} 吗?",
+        Unknown => "未知",
+        UnknownFileName => "(未知文件名)",
+        NoHomeDir => "无法确定用户目录",
+        NoConfigDir => "无法确定配置目录",
+
+        CliUsage => {
+            "\
+用法: BeatCLI [选项]
+
+选项:
+  --once <file>       播放一个文件后退出，不进入交互界面
+  --volume <0-100>    初始音量
+  --duration <secs>   配合 --once，最多播放的秒数
+  --script <file>     启动后逐行执行文件中的命令，再进入交互界面
+  --keep-going        配合 --script，命令出错时继续执行后面的命令
+  --quit-after        配合 --script，执行完后直接退出
+  --lang <zh|en>      界面语言
+  --folder <dir>      启动时加载的音乐文件夹（也可以直接写在参数末尾）
+  --play <N>          启动后播放第 N 首
+  --mode <mode>       启动时的播放模式：sequential、repeatone 或 shuffle
+  --vis               在播放界面显示频谱面板（会增加一些 CPU 占用）
+  --tui               使用全屏界面：状态、进度、播放列表和歌词分区显示，底部输入命令
+  --quiet             安静模式：播放时只显示一行原地更新的状态，不显示边框和歌词
+  --no-color          不输出颜色（也可以设置 NO_COLOR 环境变量）
+  --verbose           把调试日志写入 ~/.beatcli/beatcli.log（也可以设置 RUST_LOG=debug 等）
+  -y, --yes           脚本和管道输入中自动确认 /clear 等需要确认的命令
+  -h, --help          显示此帮助
+
+音频输出设备在配置项 audio_device 或 /device 中设置，/device list 列出可用设备；
+Linux 上也可以使用 PulseAudio / PipeWire 的 sink 名称（见 pactl list short sinks）"
+        }
+        CliMissingValue => "{} 缺少参数",
+        CliInvalidVolume => "--volume 的参数无效: {}，应为 0-100",
+        CliInvalidDuration => "--duration 的参数无效: {}，应为正整数秒数",
+        CliInvalidLang => "--lang 的参数无效: {}，应为 zh 或 en",
+        CliInvalidPlay => "--play 的参数无效: {}，应为从 1 开始的歌曲序号",
+        CliInvalidMode => "--mode 的参数无效: {}，应为 sequential、repeatone 或 shuffle",
+        CliExtraArgument => "多余的参数: {}",
+        CliOnceConflict => "--folder、--play 和 --mode 不能与 --once 一起使用",
+        CliUnknownOption => "未知选项: {}",
+        CliDurationNeedsOnce => "--duration 只能与 --once 一起使用",
+        CliScriptOnly => "--keep-going 和 --quit-after 只能与 --script 一起使用",
+        CliOnceAndScript => "--once 和 --script 不能同时使用",
+        CliFileNotFound => "错误: 文件不存在: {}",
+        CliPlaying => "正在播放: {}",
+        CliDuration => "时长: {}",
+        CliInterrupted => "已中断",
+        ScriptNotFound => "错误: 脚本文件不存在: {}",
+        ConfigWarning => "警告: 已忽略无效的配置项 {}",
+        AliasShadowed => "警告: 别名 '{}' 与内置命令同名，已忽略",
+        PlayerInitFailed => "错误: 播放器初始化失败: {}",
+        RedrawStats => "播放界面重绘: 完整 {} 次，局部 {} 次",
+
+        CfgUnreadable => "无法读取 {}: {}",
+        CfgSyntax => "{} 格式错误: {}",
+        CfgUnknownKey => "{}: 未知的配置项",
+        CfgNotTable => "{}: 应为表（[{}]）",
+        CfgOutOfRange => "{}: {} 超出 {} 范围",
+        CfgTooSmall => "{}: {} 太小，至少为 {}",
+        CfgMustBePositive => "{}: 必须大于 0",
+        CfgUnknownColor => "{}: 未知的颜色 '{}'",
+        CfgUnknownTheme => "ui.theme: 未知的配色方案 '{}'，使用 default",
+        ThemeUnknown => "未知的配色方案 '{}'，可用的方案: {}",
+        CfgInvalidPattern => "scanning.ignore: 无效的模式 '{}': {}",
+
+        UnknownCommand => "未知命令: {}",
+        AmbiguousCommand => "命令 {} 不明确，可能是: {}",
+        HelpHint => "\n输入 /help 查看帮助。",
+        MissingArgument => "/{} 命令缺少参数\n用法: {}",
+        InvalidArgument => "/{} 的参数无效: {}，应为 {}\n用法: {}",
+        AliasCycle => "别名 '{}' 存在循环引用",
+        AliasTooDeep => "别名 '{}' 嵌套超过 {} 层",
+        AliasInvalidName => "无效的别名: {}",
+        AliasIsBuiltin => "别名 '{}' 与内置命令同名",
+        AliasNoTarget => "别名需要指定对应的命令",
+        ExpectDepth => "大于 0 的扫描深度",
+        ExpectCount => "大于 0 的歌曲数",
+        ExpectMode => "sequential、repeatone 或 shuffle",
+        CurrentMode => "当前播放模式: {}（可选: {}）",
+        ExpectSpeed => "0.5-3.0 之间的数字",
+        ExpectPlaylistSub => "子命令 merge",
+        ExpectSort => "name 或 track",
+        ExpectVolume => "0-100 之间的数字",
+        ExpectStep => "1-100 之间的数字",
+        ExpectOnOff => "on 或 off",
+        ExpectConfigSub => "子命令 show",
+        ExpectIndex => "从 1 开始的歌曲序号",
+        ExpectRange => "歌曲序号或范围，例如 5 或 5-10",
+        ExpectPage => "从 1 开始的页码，或 compact、normal、wide",
+        ExpectLang => "zh 或 en",
+        ExpectRgMode => "off、track 或 album",
+        ExpectBorder => "block、double、single、rounded 或 ascii",
+        ExpectDuration => "时间长度，如 90、30s、2m、1m30s 或 500ms",
+        ExpectSeek => "时间位置（如 1:30、90s）或 +N / -N 秒",
+
+        HelpCommands => "常用命令",
+        HelpFooter => {
+            "\
+输入 /help <命令> 查看命令的详细说明，例如: /help mode
+多条命令可用分号分隔，例如: /folder D:\\Music; /mode shuffle; /play
+播放界面热键: 空格 暂停/继续  n/p 下/上一首  +/- 音量
+              ←/→ 快退/快进  l 歌词  : 输入命令  q 退出
+"
+        }
+        HelpIsAlias => "/{} 是 {} 的别名\n\n",
+        HelpNotFound => "没有关于 '{}' 的帮助",
+        HelpSeeAll => "\n输入 /help 查看所有命令。",
+        HelpUsage => "用法: {}\n",
+        HelpShort => "简写: {}\n",
+        HelpExamples => "\n示例:\n",
+
+        ScriptTooDeep => "脚本嵌套超过 {} 层: {}",
+        ScriptUnreadable => "无法读取脚本 {}: {}",
+        ScriptStopped => "脚本 {} 第 {} 行出错，已停止执行",
+        SleepInteractive => "/sleep 只能在脚本和管道输入中使用，在终端中等待时无法输入命令",
+
+        PathEmpty => "路径不能为空，请指定有效的文件夹路径",
+        PathNotFound => "路径不存在: {}",
+        NotAFolder => "路径不是一个文件夹: {}",
+        DepthUnlimited => "不限",
+        DepthLevels => "{} 层",
+        SkippedUnsupported => "，跳过 {} 个不支持的文件",
+        SkippedHidden => "，已跳过 {} 个隐藏文件",
+        NoAudioFound => "文件夹 '{}' 中没有找到支持的音频文件{}（扫描深度: {}）",
+        ScanFound => "扫描到 {} 首{}: {}",
+        ScanProgress => "正在扫描… 已发现 {} 个文件 / {} 首音频",
+        BarVolume => "音量: {}",
+        BarMode => "模式: {}",
+        SkippedLinks => {
+            "跳过了 {} 个指向文件夹的符号链接，使用 /folder <path> symlinks 可跟随链接扫描"
+        }
+        ScanFailed => "扫描失败: {}",
+        ScanStopped => {
+            "扫描提前停止（{}），只保留了已找到的 {} 首\n可在配置文件 [scanning] 中调整 max_entries 和 timeout_secs"
+        }
+        ScanLimitEntries => "已遍历 {} 个条目",
+        ScanLimitTimeout => "扫描时间超过 {} 秒",
+        NoFolderYet => "尚未选择文件夹，请先使用 /folder <path>",
+        Reloaded => "已重新扫描 {}: 新增 {} 首，移除 {} 首，共 {} 首",
+        FolderChanged => "文件夹有变化: 新增 {} 首，移除 {} 首，共 {} 首",
+        WatchOff => "已关闭文件夹监视",
+        Watching => "正在监视 {}",
+        WatchPending => "已开启文件夹监视，使用 /folder 选择文件夹后生效",
+        WatchFailed => "无法监视文件夹 {}: {}",
+        EmptyPlaylistHint => "(空播放列表)\n请先使用 /folder <path> 选择目录",
+        PlaylistHeader => "播放列表 (第 {}/{} 页，共 {} 首)\n",
+        ListMore => "[ /list {} 查看下一页 ]",
+        ListColumnName => "文件名",
+        ListColumnArtist => "艺术家",
+        ListColumnDuration => "时长",
+        ListPageOutOfRange => "页码超出范围，播放列表共 {} 页",
+        ListPageSizeSet => "/list 每页显示 {} 首",
+        PlaylistEmpty => "播放列表为空，请先使用 /folder 添加歌曲",
+        IndexOutOfRange => "歌曲序号超出范围，当前播放列表有 {} 首歌曲，请输入 1-{} 之间的数字",
+        SearchNone => "没有找到包含 '{}' 的歌曲",
+        SearchResults => "搜索 '{}' 的结果：\n",
+        SearchHint => "\n使用 /play <N> 播放指定歌曲",
+        PlayNameAmbiguous => "有 {} 首歌曲匹配 '{}'：\n",
+        Shuffled => "已随机打乱播放列表",
+        SortName => "文件名",
+        SortTrack => "音轨号",
+        Sorted => "播放列表已按{}排序",
+        NoDuplicates => "没有找到重复项",
+        DuplicatesRemoved => "删除了 {} 个重复项",
+        PlaylistAlreadyEmpty => "播放列表已为空",
+        PlaylistCleared => "已清空播放列表（{} 首）",
+        UndoShuffle => "打乱播放列表",
+        UndoSort => "按{}排序",
+        UndoDedup => "删除 {} 个重复项",
+        UndoClear => "清空播放列表（{} 首）",
+        UndoRemove => "删除 {} 首歌曲",
+        InvalidRange => "无效的范围 {}-{}：起始序号不能大于结束序号",
+        SongRemoved => "已删除第 {} 首歌曲",
+        RangeRemoved => "已删除第 {}-{} 首共 {} 首歌曲",
+        RangeQueued => "第 {}-{} 首已加入播放队列（{} 首）",
+        UndoDone => "已撤销: {}",
+        NothingToUndo => "没有可以撤销的操作",
+        NotPlayable => "不是可播放的音频文件或文件夹: {}",
+        AddDone => "已添加 {} 首，跳过 {} 重复，共 {} 首",
+        UnsupportedPlaylist => "不支持的播放列表格式，支持: m3u, m3u8, xspf, json",
+        PlaylistReadFailed => "读取播放列表失败: {}: {}",
+        MergeDone => "合并完成，新增 {} 首，跳过 {} 重复，共 {} 首",
+        CopyProgress => "正在复制: {}/{}",
+        CopyDone => "已复制 {} 首歌曲到 {}",
+        LinkDone => "已为 {} 首歌曲在 {} 创建符号链接",
+        CopyLyrics => "，歌词 {} 个",
+        CopySkipped => "，跳过 {} 个同名文件（如 {}）",
+        CopyErrors => "，{} 首复制失败（详见日志）",
+        CopyFailed => "无法复制到 {}: {}",
+        SnapshotSaved => "已保存 {} 首歌曲的快照: {}",
+        SnapshotSaveFailed => "保存快照失败: {}",
+        NoFolderScanned => "还没有扫描过文件夹，请先使用 /folder <path>",
+        SnapshotReadFailed => "读取快照失败，请先使用 /snapshot 保存: {}",
+        DiffScanStopped => "扫描提前停止（{}），无法与快照比较",
+        DiffNone => "与快照相比没有变化",
+        DiffHeader => "与快照相比共有 {} 处变化:\n",
+        DiffAdded => "新增",
+        DiffRemoved => "删除",
+        DiffModified => "修改",
+        DiffCount => "  {}: {} 首\n",
+        DiffMore => "    ... 等 {} 首\n",
+
+        OnlyOneSongReplay => "只有一首歌曲，重新播放当前歌曲",
+        OnlyOneSongNext => "只有一首歌曲，无法切换到下一首",
+        OnlyOneSongPrev => "只有一首歌曲，无法切换到上一首",
+        QueueCleared => "已清空队列（{} 首）",
+        SampleAdded => "已添加 {} 首随机曲目到队列",
+        SampleLoopHint => "，播完后将重新抽取（/sample off 停止）",
+        GotoDone => "列表位置已定位到第 {} 首: {}",
+        NothingPlaying => "没有正在播放的歌曲",
+        NothingPlayingHint => "当前没有播放歌曲，使用 /play 开始播放",
+        Paused => "已暂停",
+        Resumed => "继续播放",
+        NoSavedPosition => "当前歌曲没有保存的播放位置",
+        ResumedFrom => "从 {} 继续播放",
+        SeekTo => "跳转到 {}",
+        SongMissing => "歌曲文件不存在: {}",
+        OpenFailed => "无法打开文件: {}",
+        DecodeFailed => "无法解码: {}",
+        UnsupportedCodec => "无法解码: 不支持 {} 编码",
+        NowPlaying => "开始播放: {}",
+        LyricsLoadedTag => " | 已加载歌词",
+        ResumingFrom => " | 从上次位置 {} 继续",
+        ResumePrompt => "\n(从上次位置 {} 继续? 输入 /continue)",
+        RecoverPrompt => "上次会话未正常退出，是否恢复？[y/N] ",
+        Recovered => "已从检查点恢复上次未正常退出的会话（{} 首歌曲）",
+        SwitchedNext => "已切换到下一首: {}",
+        SwitchedPrev => "已切换到上一首: {}",
+        LastSong => "已经是最后一首，顺序播放模式下不循环",
+        FirstSong => "已经是第一首，当前播放模式下不会跳到最后一首",
+        NoNextSong => "无法获取下一首歌曲",
+        NoPrevSong => "无法获取上一首歌曲",
+        VolumeSet => "音量 {} {}%",
+        DeviceNotFound => "找不到音频输出设备: {}",
+        DeviceNone => "没有找到可用的音频输出设备",
+        DeviceHeader => "音频输出设备:\n",
+        DeviceSwitched => "已切换到音频设备: {}",
+        DeviceSwitchFailed => "无法切换音频设备: {}",
+        DeviceSaveFailed => "已切换到音频设备 {}，但保存配置失败: {}",
+        DeviceFallback => "无法使用音频设备 '{}'，已改用默认设备: {}",
+        DeviceLost => "音频设备已断开，已改用默认设备",
+
+        ModeSequential => "顺序播放模式",
+        ModeRepeatOne => "单曲循环模式",
+        ModeShuffle => "随机播放模式",
+        ModeAudiobook => "有声书模式",
+        ModeNameSequential => "顺序播放",
+        ModeNameRepeatOne => "单曲循环",
+        ModeNameShuffle => "随机播放",
+        ModeNameAudiobook => "有声书",
+        AlreadyInMode => "已经是{}",
+        SwitchedMode => "已切换到{}",
+        ModeSettingsApplied => "，模式设置: {}",
+        ModeVolume => "音量 {}%",
+        ModeFadeIn => "淡入 {}ms",
+        ModeFadeOut => "淡出 {}ms",
+        SpeedNeedsAudiobook => "播放速度只能在有声书模式下调整，请先使用 /abmode",
+        NoCurrentFile => "当前没有正在播放的文件",
+        SpeedSet => "播放速度设置为 {}",
+        ChapterJump => "第 {}/{} 章: {}",
+        ChapterDefaultTitle => "第 {} 章",
+        CueDefaultTitle => "音轨 {}",
+        ChapterProgress => "章节 {}/{}",
+        AudiobookStatus => "有声书模式 ({})",
+
+        LyricsNeedPlaying => "当前没有播放歌曲，无法操作歌词显示",
+        LyricsNothingToExport => "当前歌曲没有加载歌词，无法导出",
+        ConfirmPrompt => "{}，确定吗？输入 y 确认，n 取消",
+        ConfirmBatch => "{}，需要确认；非交互执行时请使用 --yes 启动",
+        ConfirmCancelled => "已取消",
+        NothingToConfirm => "没有需要确认的操作",
+        ConfirmClear => "将清空播放列表中的 {} 首歌曲",
+        ConfirmOverwrite => "将覆盖已有的文件 {}",
+        LyricsExported => "已导出 {} 行歌词到 {}",
+        LyricsExportFailed => "无法写入歌词文件 {}: {}",
+        LyricsShown => "已显示",
+        LyricsHidden => "已隐藏",
+        LyricsEmptyFile => "歌词{}，但歌词文件为空",
+        LyricsLoaded => "歌词{}，已加载 {} 行歌词",
+        LyricsNoFile => "歌词{}，但未找到歌词文件",
+        LyricsStatus => "歌词{}",
+        LyricsModeNeedPlaying => "当前没有播放歌曲，无法切换歌词显示模式",
+        LyricsStreamUnsupported => "终端不支持 ANSI 转义序列，只能使用清屏模式显示歌词",
+        LyricsStream => "流式输出",
+        LyricsClear => "清屏刷新",
+        LyricsModeSwitched => "歌词显示模式已切换为: {}",
+        VuOn => "VU 表已开启",
+        VuOff => "VU 表已关闭",
+        VizOn => "频谱面板已开启",
+        VizOff => "频谱面板已关闭",
+        DebugOn => "已开启调试信息",
+        DebugOff => "已关闭调试信息",
+        DebugHeader => "🔧 调试信息",
+        DebugClock => "  解码 {}  计时 {}  偏差 {}  累计暂停 {}",
+        DebugLyrics => "  歌词行 {}  时间范围 {}",
+        DebugState => "  模式 {}  进度更新 {} 次/秒",
+        DebugQueue => "  队列 {}",
+        CompactOn => "已开启紧凑显示",
+        CompactOff => "已关闭紧凑显示",
+        QuietOn => "已开启安静模式",
+        QuietOff => "已关闭安静模式",
+        QuietTui => "全屏界面不支持安静模式",
+        OverlayHint => "按任意键返回播放界面",
+        PaneOn => "已显示播放列表面板",
+        PaneOff => "已隐藏播放列表面板",
+        PaneHeader => "📃 播放列表",
+        LangSwitched => "界面语言已切换为中文",
+        ThemeSwitched => "配色方案已切换为 {}",
+        BorderSwitched => "边框样式已切换为 {}",
+        LoggingTo => "日志写入 {}",
+        LoggingFailed => "无法创建日志文件，不记录日志",
+        FadeInSet => "淡入时长: {}ms",
+        FadeInOff => "淡入已关闭",
+        FadeOutSet => "淡出时长: {}ms",
+        FadeOutOff => "淡出已关闭",
+        RgOff => "ReplayGain 已关闭",
+        RgTrack => "ReplayGain: 按音轨归一化",
+        RgAlbum => "ReplayGain: 按专辑归一化",
+        RgApplied => "，当前歌曲 {}",
+        RgNoTags => "，当前歌曲没有 ReplayGain 标签",
+        VolumeWithGain => "{}% (RG {})",
+
+        NoAliases => "当前没有别名，使用 /alias <别名> <命令> 添加",
+        AliasHeader => "命令别名:\n",
+        AliasSet => "已设置别名: /{} -> {}",
+        AliasSaveFailed => "已设置别名 /{}，但保存配置失败: {}",
+        AliasRemoved => "已删除别名 /{}",
+        AliasRemoveSaveFailed => "已删除别名 /{}，但保存配置失败: {}",
+        AliasNotFound => "没有名为 /{} 的别名",
+        ConfigCurrent => "当前配置（{}）:\n{}",
+        ConfigShowFailed => "无法显示配置: {}",
+
+        NowTitle => "🎵 当前播放信息",
+        InfoTitle => "🔍 音轨信息",
+        InfoFile => "文件",
+        InfoPath => "  路径: {}\n",
+        InfoSize => "  大小: {}\n",
+        InfoFormat => "  格式: {}\n\n",
+        InfoAudio => "音频",
+        InfoSampleRate => "  采样率: {}\n",
+        InfoChannels => "  声道: {}\n",
+        InfoBitrate => "  码率: {}\n",
+        InfoDuration => "  时长: {}\n\n",
+        InfoTags => "标签",
+        InfoNoTags => "  （没有标签）\n",
+        InfoUnknown => "未知",
+        NowBasics => "基本信息",
+        NowSong => "  歌曲: {}\n",
+        NowState => "  状态: {}\n",
+        NowIndex => "  序号: {} / {}\n",
+        NowMode => "  模式: {}\n",
+        NowVolume => "  音量: {}\n",
+        NowPosition => "  播放时间: {}\n\n",
+        NowLyricsSection => "歌词信息",
+        NowCurrentLyrics => "当前歌词",
+        NowLyricsLoaded => "  歌词: 已加载 ({} 行)\n\n",
+        NowLyricsEmpty => "  歌词: 文件为空\n",
+        NowLyricsMissing => "  歌词: 未找到歌词文件\n",
+        NowLyricsOff => "  歌词: 已关闭\n",
+
+        WelcomeHint => "输入 /help 查看命令，/folder <path> 选择音乐目录",
+        HotkeyHint => "空格 暂停/继续  n/p 下/上一首  +/- 音量  l 歌词  : 命令  q 退出",
+        StatusHeader => "🎵 播放状态",
+        StatusNow => "当前播放:",
+        StatusNext => "下一首:",
+        StatusLine => "  {}    播放模式: {}    播放列表: {} 首",
+        StatusVolume => "  音量 {} {}",
+        PlayStatePlaying => "▶ 播放中",
+        PlayStatePaused => "⏸ 已暂停",
+        PlayStateStopped => "⏹ 已停止",
+        NotPlaying => "(未播放)",
+        NoneYet => "(无)",
+        LyricsHeader => "🎶 歌词",
+        TuiMessageTitle => "消息",
+        TuiInputTitle => "命令（回车执行，Esc 清空，Ctrl+C 退出）",
+        TuiUnsupported => "全屏界面需要在终端中运行，已改用普通界面",
+        LyricsIntro => "🎵 间奏",
+        GoodbyeTitle => "🎵 感谢使用 BeatCLI",
+        GoodbyeLine1 => "再见，下次再见！",
+        GoodbyeLine2 => "希望音乐带给您美好的时光 🎶",
+    }
+}
+
+fn en(msg: Msg) -> &'static str {
+    use Msg::*;
+    match msg {
+        ErrorLine => "Error: {}",
+        Or => " or ",
+        ListSep => ", ",
+        DidYouMean => ". Did you mean {}?",
+        Unknown => "unknown",
+        UnknownFileName => "(unknown file name)",
+        NoHomeDir => "cannot determine the home directory",
+        NoConfigDir => "cannot determine the config directory",
+
+        CliUsage => {
+            "\
+Usage: BeatCLI [options]
+
+Options:
+  --once <file>       play one file and exit without the interactive screen
+  --volume <0-100>    initial volume
+  --duration <secs>   with --once, play at most this many seconds
+  --script <file>     run the commands in a file on startup, then go interactive
+  --keep-going        with --script, keep running after a command fails
+  --quit-after        with --script, exit when the script is done
+  --lang <zh|en>      interface language
+  --folder <dir>      music folder to load on startup (may also be given as the last argument)
+  --play <N>          start playing song N
+  --mode <mode>       playback mode on startup: sequential, repeatone or shuffle
+  --vis               show a spectrum panel on the playing screen (uses some extra CPU)
+  --tui               use the full-screen interface with panels and a command box at the bottom
+  --quiet             quiet mode: show a single status line updated in place, no boxes or lyrics
+  --no-color          do not output colors (setting NO_COLOR works too)
+  --verbose           write debug logs to ~/.beatcli/beatcli.log (or set RUST_LOG=debug etc.)
+  -y, --yes           in scripts and piped input, confirm commands such as /clear automatically
+  -h, --help          show this help
+
+The audio output device is set with audio_device in the config or /device; /device list
+shows the available devices. On Linux, PulseAudio / PipeWire sink names work too
+(see pactl list short sinks)"
+        }
+        CliMissingValue => "{} needs a value",
+        CliInvalidVolume => "invalid value for --volume: {}, expected 0-100",
+        CliInvalidDuration => {
+            "invalid value for --duration: {}, expected a whole number of seconds"
+        }
+        CliInvalidLang => "invalid value for --lang: {}, expected zh or en",
+        CliInvalidPlay => "invalid value for --play: {}, expected a song number starting from 1",
+        CliInvalidMode => "invalid value for --mode: {}, expected sequential, repeatone or shuffle",
+        CliExtraArgument => "unexpected argument: {}",
+        CliOnceConflict => "--folder, --play and --mode cannot be used with --once",
+        CliUnknownOption => "unknown option: {}",
+        CliDurationNeedsOnce => "--duration can only be used with --once",
+        CliScriptOnly => "--keep-going and --quit-after can only be used with --script",
+        CliOnceAndScript => "--once and --script cannot be used together",
+        CliFileNotFound => "Error: file not found: {}",
+        CliPlaying => "Playing: {}",
+        CliDuration => "Duration: {}",
+        CliInterrupted => "Interrupted",
+        ScriptNotFound => "Error: script file not found: {}",
+        ConfigWarning => "Warning: ignored invalid config entry {}",
+        AliasShadowed => {
+            "Warning: alias '{}' has the same name as a built-in command and was ignored"
+        }
+        PlayerInitFailed => "Error: failed to initialize the player: {}",
+        RedrawStats => "Playing screen redraws: {} full, {} partial",
+
+        CfgUnreadable => "cannot read {}: {}",
+        CfgSyntax => "{} has a syntax error: {}",
+        CfgUnknownKey => "{}: unknown key",
+        CfgNotTable => "{}: should be a table ([{}])",
+        CfgOutOfRange => "{}: {} is outside the range {}",
+        CfgTooSmall => "{}: {} is too small, the minimum is {}",
+        CfgMustBePositive => "{}: must be greater than 0",
+        CfgUnknownColor => "{}: unknown color '{}'",
+        CfgUnknownTheme => "ui.theme: unknown color theme '{}', using default",
+        ThemeUnknown => "Unknown color theme '{}'; available themes: {}",
+        CfgInvalidPattern => "scanning.ignore: invalid pattern '{}': {}",
+
+        UnknownCommand => "Unknown command: {}",
+        AmbiguousCommand => "Ambiguous command {}, could be: {}",
+        HelpHint => "\nType /help for help.",
+        MissingArgument => "/{} needs an argument\nUsage: {}",
+        InvalidArgument => "Invalid argument for /{}: {}, expected {}\nUsage: {}",
+        AliasCycle => "alias '{}' refers back to itself",
+        AliasTooDeep => "alias '{}' is nested more than {} levels deep",
+        AliasInvalidName => "invalid alias name: {}",
+        AliasIsBuiltin => "alias '{}' has the same name as a built-in command",
+        AliasNoTarget => "an alias needs a command to expand to",
+        ExpectDepth => "a scan depth greater than 0",
+        ExpectCount => "a song count greater than 0",
+        ExpectMode => "sequential, repeatone or shuffle",
+        CurrentMode => "Current playback mode: {} (options: {})",
+        ExpectSpeed => "a number between 0.5 and 3.0",
+        ExpectPlaylistSub => "the subcommand merge",
+        ExpectSort => "name or track",
+        ExpectVolume => "a number between 0 and 100",
+        ExpectStep => "a number between 1 and 100",
+        ExpectOnOff => "on or off",
+        ExpectConfigSub => "the subcommand show",
+        ExpectIndex => "a song number starting from 1",
+        ExpectRange => "a song number or range, e.g. 5 or 5-10",
+        ExpectPage => "a page number starting from 1, or compact, normal or wide",
+        ExpectLang => "zh or en",
+        ExpectRgMode => "off, track or album",
+        ExpectBorder => "block, double, single, rounded or ascii",
+        ExpectDuration => "a length of time such as 90, 30s, 2m, 1m30s or 500ms",
+        ExpectSeek => "a position (such as 1:30 or 90s) or +N / -N seconds",
+
+        HelpCommands => "Commands",
+        HelpFooter => {
+            "\
+Type /help <command> for details, e.g. /help mode
+Separate several commands with semicolons, e.g. /folder D:\\Music; /mode shuffle; /play
+Hotkeys while playing: Space pause/resume  n/p next/previous  +/- volume
+                       ←/→ seek  l lyrics  : enter a command  q quit
+"
+        }
+        HelpIsAlias => "/{} is an alias for {}\n\n",
+        HelpNotFound => "No help for '{}'",
+        HelpSeeAll => "\nType /help to see all commands.",
+        HelpUsage => "Usage: {}\n",
+        HelpShort => "Short: {}\n",
+        HelpExamples => "\nExamples:\n",
+
+        ScriptTooDeep => "Scripts nested more than {} levels deep: {}",
+        ScriptUnreadable => "Cannot read script {}: {}",
+        ScriptStopped => "Script {} failed at line {}, stopped",
+        SleepInteractive => {
+            "/sleep only works in scripts and piped input; while it waits the terminal cannot take commands"
+        }
+
+        PathEmpty => "The path is empty, please specify a folder",
+        PathNotFound => "Path does not exist: {}",
+        NotAFolder => "Not a folder: {}",
+        DepthUnlimited => "unlimited",
+        DepthLevels => "{} levels",
+        SkippedUnsupported => ", skipped {} unsupported files",
+        SkippedHidden => ", skipped {} hidden files",
+        NoAudioFound => "No supported audio files found in '{}'{} (scan depth: {})",
+        ScanFound => "Found {} songs{}: {}",
+        ScanProgress => "Scanning… {} files seen / {} audio",
+        BarVolume => "Vol: {}",
+        BarMode => "Mode: {}",
+        SkippedLinks => {
+            "Skipped {} symbolic links to folders, use /folder <path> symlinks to follow them"
+        }
+        ScanFailed => "Scan failed: {}",
+        ScanStopped => {
+            "The scan stopped early ({}), keeping the {} songs found so far\nAdjust max_entries and timeout_secs under [scanning] in the config file"
+        }
+        ScanLimitEntries => "visited {} entries",
+        ScanLimitTimeout => "scanning took longer than {} seconds",
+        NoFolderYet => "No folder selected yet, use /folder <path> first",
+        Reloaded => "Rescanned {}: {} added, {} removed, {} in total",
+        FolderChanged => "Folder changed: {} added, {} removed, {} in total",
+        WatchOff => "Folder watching is off",
+        Watching => "Watching {}",
+        WatchPending => "Folder watching is on, it starts once you choose a folder with /folder",
+        WatchFailed => "Cannot watch folder {}: {}",
+        EmptyPlaylistHint => "(empty playlist)\nUse /folder <path> to choose a folder first",
+        PlaylistHeader => "Playlist (page {}/{}, {} songs)\n",
+        ListMore => "[ /list {} for more ]",
+        ListColumnName => "Name",
+        ListColumnArtist => "Artist",
+        ListColumnDuration => "Time",
+        ListPageOutOfRange => "Page out of range: the playlist has {} pages",
+        ListPageSizeSet => "/list now shows {} songs per page",
+        PlaylistEmpty => "The playlist is empty, use /folder to add songs first",
+        IndexOutOfRange => {
+            "Song number out of range: the playlist has {} songs, enter a number between 1 and {}"
+        }
+        SearchNone => "No songs matching '{}'",
+        SearchResults => "Results for '{}':\n",
+        SearchHint => "\nUse /play <N> to play a song",
+        PlayNameAmbiguous => "{} songs match '{}':\n",
+        Shuffled => "Shuffled the playlist",
+        SortName => "file name",
+        SortTrack => "track number",
+        Sorted => "Sorted the playlist by {}",
+        NoDuplicates => "No duplicates found",
+        DuplicatesRemoved => "Removed {} duplicates",
+        PlaylistAlreadyEmpty => "The playlist is already empty",
+        PlaylistCleared => "Cleared the playlist ({} songs)",
+        UndoShuffle => "shuffle the playlist",
+        UndoSort => "sort by {}",
+        UndoDedup => "remove {} duplicates",
+        UndoClear => "clear the playlist ({} songs)",
+        UndoRemove => "remove {} songs",
+        InvalidRange => "Invalid range {}-{}: the start must not be greater than the end",
+        SongRemoved => "Removed song {}",
+        RangeRemoved => "Removed songs {}-{} ({} songs)",
+        RangeQueued => "Queued songs {}-{} ({} songs)",
+        UndoDone => "Undone: {}",
+        NothingToUndo => "Nothing to undo",
+        NotPlayable => "Not a playable audio file or folder: {}",
+        AddDone => "Added {} songs, skipped {} duplicates, {} in total",
+        UnsupportedPlaylist => "unsupported playlist format, supported: m3u, m3u8, xspf, json",
+        PlaylistReadFailed => "Failed to read playlist: {}: {}",
+        MergeDone => "Merged: {} added, {} duplicates skipped, {} in total",
+        CopyProgress => "Copying: {}/{}",
+        CopyDone => "Copied {} songs to {}",
+        LinkDone => "Linked {} songs into {}",
+        CopyLyrics => ", {} lyrics files",
+        CopySkipped => ", skipped {} existing names (e.g. {})",
+        CopyErrors => ", {} failed (see the log)",
+        CopyFailed => "Cannot copy to {}: {}",
+        SnapshotSaved => "Saved a snapshot of {} songs: {}",
+        SnapshotSaveFailed => "Failed to save the snapshot: {}",
+        NoFolderScanned => "No folder has been scanned yet, use /folder <path> first",
+        SnapshotReadFailed => "Failed to read the snapshot, save one with /snapshot first: {}",
+        DiffScanStopped => "The scan stopped early ({}), cannot compare with the snapshot",
+        DiffNone => "No changes since the snapshot",
+        DiffHeader => "{} changes since the snapshot:\n",
+        DiffAdded => "Added",
+        DiffRemoved => "Removed",
+        DiffModified => "Modified",
+        DiffCount => "  {}: {} songs\n",
+        DiffMore => "    ... {} in total\n",
+
+        OnlyOneSongReplay => "Only one song, playing it again",
+        OnlyOneSongNext => "Only one song, there is no next song",
+        OnlyOneSongPrev => "Only one song, there is no previous song",
+        QueueCleared => "Cleared the queue ({} songs)",
+        SampleAdded => "Added {} random songs to the queue",
+        SampleLoopHint => ", a new sample is drawn when they finish (/sample off to stop)",
+        GotoDone => "Moved the list position to #{}: {}",
+        NothingPlaying => "Nothing is playing",
+        NothingPlayingHint => "Nothing is playing, use /play to start",
+        Paused => "Paused",
+        Resumed => "Resumed",
+        NoSavedPosition => "No saved position for the current song",
+        ResumedFrom => "Resumed from {}",
+        SeekTo => "Jumped to {}",
+        SongMissing => "Song file not found: {}",
+        OpenFailed => "cannot open file: {}",
+        DecodeFailed => "cannot decode: {}",
+        UnsupportedCodec => "cannot decode: the {} codec is not supported",
+        NowPlaying => "Playing: {}",
+        LyricsLoadedTag => " | lyrics loaded",
+        ResumingFrom => " | resuming from {}",
+        ResumePrompt => "\n(Resume from {}? Type /continue)",
+        RecoverPrompt => "The last session did not exit cleanly. Recover it? [y/N] ",
+        Recovered => "Recovered the last session from its checkpoint ({} songs)",
+        SwitchedNext => "Next song: {}",
+        SwitchedPrev => "Previous song: {}",
+        LastSong => "This is the last song, sequential mode does not wrap around",
+        FirstSong => {
+            "This is the first song; the current mode does not wrap around to the last one"
+        }
+        NoNextSong => "Cannot find the next song",
+        NoPrevSong => "Cannot find the previous song",
+        VolumeSet => "Volume {} {}%",
+        DeviceNotFound => "audio output device not found: {}",
+        DeviceNone => "No audio output devices found",
+        DeviceHeader => "Audio output devices:\n",
+        DeviceSwitched => "Switched to audio device: {}",
+        DeviceSwitchFailed => "Cannot switch the audio device: {}",
+        DeviceSaveFailed => "Switched to audio device {}, but saving the config failed: {}",
+        DeviceFallback => "Cannot use audio device '{}', using the default device: {}",
+        DeviceLost => "The audio device was disconnected, switched to the default device",
+
+        ModeSequential => "sequential mode",
+        ModeRepeatOne => "repeat-one mode",
+        ModeShuffle => "shuffle mode",
+        ModeAudiobook => "audiobook mode",
+        ModeNameSequential => "Sequential",
+        ModeNameRepeatOne => "Repeat one",
+        ModeNameShuffle => "Shuffle",
+        ModeNameAudiobook => "Audiobook",
+        AlreadyInMode => "Already in {}",
+        SwitchedMode => "Switched to {}",
+        ModeSettingsApplied => "; mode settings: {}",
+        ModeVolume => "volume {}%",
+        ModeFadeIn => "fade-in {}ms",
+        ModeFadeOut => "fade-out {}ms",
+        SpeedNeedsAudiobook => "Speed can only be changed in audiobook mode, use /abmode first",
+        NoCurrentFile => "No file is playing",
+        SpeedSet => "Speed set to {}",
+        ChapterJump => "Chapter {}/{}: {}",
+        ChapterDefaultTitle => "Chapter {}",
+        CueDefaultTitle => "Track {}",
+        ChapterProgress => "Chapter {}/{}",
+        AudiobookStatus => "Audiobook ({})",
+
+        LyricsNeedPlaying => "Nothing is playing, cannot toggle lyrics",
+        LyricsNothingToExport => "The current song has no lyrics loaded to export",
+        ConfirmPrompt => "{}. Are you sure? Type y to confirm, n to cancel",
+        ConfirmBatch => {
+            "{} needs confirmation; start with --yes to confirm automatically when not interactive"
+        }
+        ConfirmCancelled => "Cancelled",
+        NothingToConfirm => "Nothing to confirm",
+        ConfirmClear => "This clears the {} songs in the playlist",
+        ConfirmOverwrite => "This overwrites the existing file {}",
+        LyricsExported => "Exported {} lyrics lines to {}",
+        LyricsExportFailed => "Cannot write lyrics file {}: {}",
+        LyricsShown => "shown",
+        LyricsHidden => "hidden",
+        LyricsEmptyFile => "Lyrics {}, but the lyrics file is empty",
+        LyricsLoaded => "Lyrics {}, {} lines loaded",
+        LyricsNoFile => "Lyrics {}, but no lyrics file was found",
+        LyricsStatus => "Lyrics {}",
+        LyricsModeNeedPlaying => "Nothing is playing, cannot switch the lyrics display mode",
+        LyricsStreamUnsupported => {
+            "This terminal does not support ANSI escape sequences; lyrics can only use the clear-screen mode"
+        }
+        LyricsStream => "streaming",
+        LyricsClear => "full redraw",
+        LyricsModeSwitched => "Lyrics display mode: {}",
+        VuOn => "VU meter on",
+        VuOff => "VU meter off",
+        VizOn => "Spectrum panel on",
+        VizOff => "Spectrum panel off",
+        DebugOn => "Debug info on",
+        DebugOff => "Debug info off",
+        DebugHeader => "🔧 Debug",
+        DebugClock => "  decoded {}  clock {}  drift {}  paused {}",
+        DebugLyrics => "  lyric line {}  range {}",
+        DebugState => "  mode {}  progress updates {}/s",
+        DebugQueue => "  queue {}",
+        CompactOn => "Compact display on",
+        CompactOff => "Compact display off",
+        QuietOn => "Quiet mode on",
+        QuietOff => "Quiet mode off",
+        QuietTui => "Quiet mode is not available in the full-screen interface",
+        OverlayHint => "Press any key to return to the playing screen",
+        PaneOn => "Playlist pane shown",
+        PaneOff => "Playlist pane hidden",
+        PaneHeader => "📃 Playlist",
+        LangSwitched => "Interface language switched to English",
+        ThemeSwitched => "Color theme switched to {}",
+        BorderSwitched => "Border style switched to {}",
+        LoggingTo => "Logging to {}",
+        LoggingFailed => "Cannot create the log file, logging is disabled",
+        FadeInSet => "Fade-in: {}ms",
+        FadeInOff => "Fade-in off",
+        FadeOutSet => "Fade-out: {}ms",
+        FadeOutOff => "Fade-out off",
+        RgOff => "ReplayGain off",
+        RgTrack => "ReplayGain: track normalization",
+        RgAlbum => "ReplayGain: album normalization",
+        RgApplied => ", current song {}",
+        RgNoTags => ", the current song has no ReplayGain tags",
+        VolumeWithGain => "{}% (RG {})",
+
+        NoAliases => "No aliases yet, add one with /alias <alias> <command>",
+        AliasHeader => "Command aliases:\n",
+        AliasSet => "Alias set: /{} -> {}",
+        AliasSaveFailed => "Alias /{} set, but saving the config failed: {}",
+        AliasRemoved => "Alias /{} removed",
+        AliasRemoveSaveFailed => "Alias /{} removed, but saving the config failed: {}",
+        AliasNotFound => "There is no alias named /{}",
+        ConfigCurrent => "Current config ({}):\n{}",
+        ConfigShowFailed => "Cannot show the config: {}",
+
+        NowTitle => "🎵 Now Playing",
+        InfoTitle => "🔍 Track Info",
+        InfoFile => "File",
+        InfoPath => "  Path: {}\n",
+        InfoSize => "  Size: {}\n",
+        InfoFormat => "  Format: {}\n\n",
+        InfoAudio => "Audio",
+        InfoSampleRate => "  Sample rate: {}\n",
+        InfoChannels => "  Channels: {}\n",
+        InfoBitrate => "  Bitrate: {}\n",
+        InfoDuration => "  Duration: {}\n\n",
+        InfoTags => "Tags",
+        InfoNoTags => "  (no tags)\n",
+        InfoUnknown => "unknown",
+        NowBasics => "Basics",
+        NowSong => "  Song: {}\n",
+        NowState => "  State: {}\n",
+        NowIndex => "  Number: {} / {}\n",
+        NowMode => "  Mode: {}\n",
+        NowVolume => "  Volume: {}\n",
+        NowPosition => "  Position: {}\n\n",
+        NowLyricsSection => "Lyrics",
+        NowCurrentLyrics => "Current lyrics",
+        NowLyricsLoaded => "  Lyrics: loaded ({} lines)\n\n",
+        NowLyricsEmpty => "  Lyrics: the file is empty\n",
+        NowLyricsMissing => "  Lyrics: no lyrics file found\n",
+        NowLyricsOff => "  Lyrics: off\n",
+
+        WelcomeHint => "Type /help for commands, /folder <path> to choose a music folder",
+        HotkeyHint => "Space pause/resume  n/p next/prev  +/- volume  l lyrics  : command  q quit",
+        StatusHeader => "🎵 Playback",
+        StatusNow => "Now playing:",
+        StatusNext => "Next:",
+        StatusLine => "  {}    Mode: {}    Playlist: {} songs",
+        StatusVolume => "  Volume {} {}",
+        PlayStatePlaying => "▶ Playing",
+        PlayStatePaused => "⏸ Paused",
+        PlayStateStopped => "⏹ Stopped",
+        NotPlaying => "(not playing)",
+        NoneYet => "(none)",
+        LyricsHeader => "🎶 Lyrics",
+        TuiMessageTitle => "Messages",
+        TuiInputTitle => "Command (Enter to run, Esc to clear, Ctrl+C to quit)",
+        TuiUnsupported => "The full-screen interface needs a terminal; using the normal interface",
+        LyricsIntro => "🎵 Intro",
+        GoodbyeTitle => "🎵 Thanks for using BeatCLI",
+        GoodbyeLine1 => "Goodbye, see you next time!",
+        GoodbyeLine2 => "Hope the music made your day 🎶",
+    }
+}
+
+/// 命令的英文简介和详细说明，中文见 command::COMMANDS
+pub fn command_doc_en(name: &str) -> Option<(&'static str, &'static str)> {
+    let doc = match name {
+        "help" => (
+            "Show help",
+            "Without an argument, lists all commands; /help <command> shows the details and examples of one command. The name may be given with or without /, and short forms work too.",
+        ),
+        "folder" => (
+            "Choose a music folder",
+            "Scans a folder (recursively by default) and replaces the playlist with the songs found; the playback mode returns to the configured default.\nQuote paths that contain spaces, or escape the spaces with a backslash; ~, $VAR and %VAR% are expanded.\nOptions: depth=N limits the recursion depth, norecurse scans only the folder itself, symlinks follows symbolic links, hidden includes hidden files, play (or --play) starts playing as soon as songs are found (a random one in shuffle mode); the default comes from playback.play_after_scan.",
+        ),
+        "reload" => (
+            "Rescan the current folder without interrupting playback",
+            "Rescans the same folder with the options of the last /folder: new songs are appended, deleted songs are removed, and the current song and playback mode stay as they are.",
+        ),
+        "watch" => (
+            "Watch the folder and update the playlist automatically",
+            "While on, watches the current folder and updates the playlist when files are added, deleted or renamed, without interrupting playback.\nBursts of changes (such as a bulk copy) are merged into one update. Toggles without an argument; the default comes from scanning.watch in the config.",
+        ),
+        "list" => (
+            "List the playlist",
+            "Lists one page of the playlist; >> marks the current song. Without a page number it shows the page with the current song. The page size is 20 and can be changed with /listpage.\nFormats: compact shows file names only (default), normal adds the duration, wide adds the artist and duration.",
+        ),
+        "listpage" => (
+            "Set the page size of /list",
+            "Sets how many songs /list shows per page.",
+        ),
+        "snapshot" => (
+            "Save a playlist snapshot",
+            "Saves the modification time of every file in the playlist to ~/.beatcli/snapshot.json so /diff can compare against it later.",
+        ),
+        "diff" => (
+            "Show folder changes since the snapshot",
+            "Rescans the current folder and lists the songs added, deleted and modified since the last /snapshot.",
+        ),
+        "add" => (
+            "Append a song file or folder",
+            "Appends a single song file or a whole folder (recursively) to the end of the playlist, skipping songs already in it. Paths follow the same rules as /folder.",
+        ),
+        "merge" => (
+            "Merge an m3u/m3u8/xspf playlist",
+            "Reads an m3u, m3u8 or xspf playlist file and appends the existing, supported songs in it to the playlist, skipping duplicates.",
+        ),
+        "copyto" => (
+            "Copy the playlist's songs to a folder",
+            "Copies the songs in the playlist, in order, to the given folder, creating it if needed, e.g. to prepare a USB drive.\nSongs whose file name already exists in the destination are skipped.\nsymlink (or --symlink) creates symbolic links to the original files instead of copying; lrc (or --copy-lrc) also copies the matching LRC lyrics files.",
+        ),
+        "playlist" => (
+            "Same as /merge",
+            "Subcommands for playlist files; currently only merge, which is the same as /merge.",
+        ),
+        "search" => (
+            "Search for songs",
+            "Searches the playlist by file name (case-insensitive) and lists the matching songs with their numbers.",
+        ),
+        "play" => (
+            "Play song N (from 1) or by name, the first song by default",
+            "Plays song N (counting from 1), or the first song when N is omitted. If the song was not finished last time, offers to resume it with /continue.\nWith a range such as 3-7, plays song 3 and queues songs 4-7 in order.\nA non-numeric argument is matched against file names (case-insensitive): an exact name wins, then names starting with it, then names containing it. A single best match is played right away; otherwise the candidates are listed.",
+        ),
+        "random" => (
+            "Play a random song",
+            "Immediately plays a random song (never the current one) without changing the playback mode.",
+        ),
+        "sample" => (
+            "Queue N random songs",
+            "Picks N distinct random songs and queues them. Queued songs play before the playback mode takes over again; if N exceeds the playlist length, every song is picked. /sample off clears the queue.",
+        ),
+        "sampleloop" => (
+            "Like /sample, drawing again when the queue runs out",
+            "Same as /sample, but draws another N songs whenever the queue runs out, until /sample off or a new folder scan.",
+        ),
+        "goto" => (
+            "Move to song N without playing it",
+            "Moves the list position to song N without interrupting the current song; playback continues from there afterwards.",
+        ),
+        "pause" => (
+            "Pause",
+            "Pauses the current song. You can also press Space on the playing screen.",
+        ),
+        "resume" => (
+            "Resume",
+            "Resumes a paused song. You can also press Space on the playing screen.",
+        ),
+        "continue" => (
+            "Resume the current song from where it last stopped",
+            "Plays the current song from the position where it last stopped. Positions are saved every 30 seconds, when switching songs and on exit; with auto_resume_position = true this happens automatically.",
+        ),
+        "seek" => (
+            "Jump to a position, or N seconds back or forward",
+            "Jumps to a position in the current song (such as 1:30 or 90s); +N / -N jumps N seconds forward or back from the current position. Jumping before the start plays from the beginning; jumping past the end moves on to the next song according to the playback mode. You can also press ← / → on the playing screen.",
+        ),
+        "next" => (
+            "Next song",
+            "Plays the next song: queued songs first, otherwise according to the playback mode. You can also press n on the playing screen.",
+        ),
+        "prev" => (
+            "Previous song",
+            "Plays the previous song, or a random one in shuffle mode. On the first song it does not wrap around to the last one. You can also press p on the playing screen.",
+        ),
+        "mode" => (
+            "Change the playback mode",
+            "Sets what plays after a song finishes; shows the current mode without an argument:\n  sequential (seq)  in order, back to the first song after the last\n  repeatone (one)   repeat the current song\n  shuffle (shu)     random order, each song once per round",
+        ),
+        "abmode" => (
+            "Switch to audiobook mode",
+            "Audiobook mode plays in order, lets you change the speed with /speed (remembered per file in ~/.beatcli/audiobook_state.json), and always resumes from the last position.\nWhen a .cue file with the same name sits next to the file, /next and /prev jump between chapters. Switch to another mode with /mode to leave it.",
+        ),
+        "speed" => (
+            "Set the playback speed in audiobook mode",
+            "Sets the audiobook speed of the current file, which is reused the next time the file plays. New files use playback.audiobook_speed from the config (1.25 by default).",
+        ),
+        "shuffle" => (
+            "Shuffle the playlist order",
+            "Shuffles the order of the playlist once. Unlike shuffle mode, playback then follows the new list order.",
+        ),
+        "sort" => (
+            "Sort by track number or file name",
+            "Sorts by the track number in the file name (within each folder) or by file name; sorts by track number when the argument is omitted.",
+        ),
+        "dedup" => (
+            "Remove duplicate songs (strict compares paths only)",
+            "Removes duplicate songs. By default files with the same name apart from the track number count as duplicates; strict only removes entries pointing to the same file.",
+        ),
+        "clear" => (
+            "Clear the playlist",
+            "Stops playback, empties the playlist and returns to the start screen. The playback mode and scan settings are kept; add songs again with /folder or /add.",
+        ),
+        "remove" => (
+            "Remove song N or a range of songs from the playlist",
+            "Removes song N, or songs from to to (inclusive), from the playlist; the files themselves are untouched. If the current song is removed, playback continues with the song after the range. /undo brings them back.",
+        ),
+        "undo" => (
+            "Undo the last playlist change",
+            "Undoes the last /shuffle, /sort, /dedup, /remove or /clear and restores the previous playlist order; up to 10 steps can be undone. Scanning a new folder discards the undo history.",
+        ),
+        "volume" => (
+            "Set the volume",
+            "Sets the volume to 0-100. Works when nothing is playing too, taking effect on the next song.",
+        ),
+        "volup" => (
+            "Raise the volume by N (default 5)",
+            "Raises the volume by N (5 by default), up to 100. You can also press + on the playing screen.",
+        ),
+        "voldown" => (
+            "Lower the volume by N (default 5)",
+            "Lowers the volume by N (5 by default), down to 0. You can also press - on the playing screen.",
+        ),
+        "lyrics" => (
+            "Toggle lyrics",
+            "Shows or hides the lyrics, which are loaded from the .lrc file with the same name as the song. The choice is kept when the track changes. You can also press l on the playing screen.",
+        ),
+        "lmode" => (
+            "Toggle the lyrics display mode (streaming/redraw)",
+            "Switches between streaming output (rewrites only the lines of the playing screen that changed, without flicker) and clearing and redrawing the whole screen.",
+        ),
+        "lrcexport" => (
+            "Export the current song's lyrics as an LRC file",
+            "Writes the loaded lyrics of the current song (including the title, artist and album tags) as a standard LRC file.\nIf the path is a folder, the file is named after the song; with . it is written next to the song as its LRC file (replacing the existing one).",
+        ),
+        "vu" => (
+            "Show or hide the level meter",
+            "Shows or hides the left/right channel level meter on the playing screen; toggles without an argument.",
+        ),
+        "viz" => (
+            "Show or hide the spectrum panel",
+            "Shows a bar chart of the frequency bands below the status area of the playing screen, taking effect on the current song right away; toggles without an argument. Uses some extra CPU; --vis turns it on at startup.",
+        ),
+        "compact" => (
+            "Turn the compact display on or off",
+            "The compact display shows playback state and time on a single line without boxes, with lyrics listed below it. It turns on automatically when the terminal is narrower than 40 columns; this command forces it on or off, and toggles without an argument.",
+        ),
+        "quiet" => (
+            "Turn quiet mode on or off",
+            "Quiet mode replaces the playing screen with a single status line updated in place (song number, name, time and volume); messages print as single lines and lyrics are hidden. Toggles without an argument; --quiet turns it on at startup. Not available with --tui.",
+        ),
+        "pane" => (
+            "Show or hide the playlist pane",
+            "Shows the songs around the current one below the status area of the playing screen, with ▶ marking the current song. Turn it off on short terminals; toggles without an argument.",
+        ),
+        "lang" => (
+            "Switch the interface language",
+            "Switches all messages, help and the playing screen between Chinese (zh) and English (en). The default comes from ui.language in the config or --lang.",
+        ),
+        "theme" => (
+            "Switch the color theme",
+            "Switches the color theme and repaints the playing screen: default is the standard palette, mono uses no colors, solarized uses the Solarized palette. The startup theme is set with ui.theme; single colors in [ui.colors] only apply at startup.",
+        ),
+        "fadein" => (
+            "Set the fade-in length for new songs",
+            "When a new song starts, the volume rises from 0 to the set volume to avoid a sudden onset. Write the time as 500ms, 1s and so on; 0 turns it off. Seeking and switching the output device do not fade in.",
+        ),
+        "fadeout" => (
+            "Set the fade-out length at the end of songs",
+            "When less than this time is left in a song, the volume falls to 0. Write the time as 500ms, 2s and so on; 0 turns it off. There is no fade-out in RepeatOne mode.",
+        ),
+        "border" => (
+            "Switch the border style",
+            "Switches the characters of the title box and separator lines and repaints the playing screen: block is the default █ box, double uses ╔═╗, single uses ┌─┐, rounded uses ╭─╮ and ascii uses only +-|. The startup style is set with ui.border_style.",
+        ),
+        "rg" => (
+            "Switch ReplayGain volume normalization",
+            "Adjusts the volume from the ReplayGain tags in the file (ID3v2 TXXX frames in MP3, Vorbis comments in FLAC / Ogg) so that songs play at a similar loudness. track normalizes each song, album keeps the relative loudness within an album, off disables it. The preamp comes from replaygain_preamp in the config; the applied gain is shown in the status area.",
+        ),
+        "device" => (
+            "List or switch the audio output device",
+            "/device list shows the output devices of every audio backend; > marks the device in use. /device <name> switches to that device, the current song continues from where it was, and the choice is saved to audio_device in the config.\nOn Linux, PulseAudio / PipeWire sink names are listed and accepted too. If the device disappears (for example Bluetooth headphones disconnect), playback moves to the default device.",
+        ),
+        "now" => (
+            "Show what is playing",
+            "Shows the current song, position, volume and playback mode.",
+        ),
+        "debug" => (
+            "Show or hide debug info",
+            "Shows a debug section on the playing screen: the position computed from decoded samples, the position estimated from elapsed time, the drift between them and the total paused time; the current lyric line and its time range; the playback mode and how often progress is updated; and the songs in the queue. Useful when reporting lyrics going out of sync; toggles without an argument.",
+        ),
+        "info" => (
+            "Show technical details of the current song",
+            "Shows the file path, size, format, sample rate, channels, bitrate, duration and all tags of the current song. Reads ID3v2 tags in MP3 files and Vorbis comments in FLAC / Ogg files.",
+        ),
+        "alias" => (
+            "List, set or remove command aliases",
+            "Without an argument or with list, lists all aliases; /alias <x> <cmd> (or /alias set <x> <cmd>) adds one and /alias remove <x> deletes one, saving the change to the config file. Aliases may carry arguments and may point to another alias, but cannot shadow built-in commands, cannot refer back to themselves and cannot be nested more than 5 levels deep.",
+        ),
+        "config" => (
+            "Show the effective configuration",
+            "Shows the config file path and the complete effective configuration, defaults included.",
+        ),
+        "source" => (
+            "Run the commands in a file",
+            "Runs the commands in a file line by line, echoing each one first. Lines starting with # are comments and blank lines are skipped; stops at the first failing command.",
+        ),
+        "sleep" => (
+            "Wait before running the next command",
+            "Stops reading further commands for a while; the song keeps playing. The time may be written as 90, 90s, 2m or 1m30s, handy in scripts or piped input to let a song play before quitting.",
+        ),
+        "quit" => (
+            "Quit",
+            "Stops playback and exits. Press q on the playing screen.",
+        ),
+        _ => return None,
+    };
+    Some(doc)
+}
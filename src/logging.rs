@@ -0,0 +1,131 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use parking_lot::Mutex;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const LOG_DIR: &str = ".beatcli";
+const LOG_FILE: &str = "beatcli.log";
+// 日志超过这个大小时改名为 beatcli.log.1（覆盖更早的一份），再写入新文件
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+/// 日志级别：RUST_LOG 设置为 error / warn / info / debug / trace 时使用该级别，
+/// 否则 --verbose 时为 debug；都没有时返回 None，不记录日志
+pub fn level_from_env(verbose: bool) -> Option<LevelFilter> {
+    let env = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|v| LevelFilter::from_str(v.trim()).ok());
+    match env {
+        Some(LevelFilter::Off) => None,
+        Some(level) => Some(level),
+        None => verbose.then_some(LevelFilter::Debug),
+    }
+}
+
+/// 日志文件路径：~/.beatcli/beatcli.log
+pub fn log_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|dir| dir.join(LOG_DIR).join(LOG_FILE))
+}
+
+/// 开始把日志写入文件，成功时返回日志文件路径
+pub fn init(level: LevelFilter) -> Option<PathBuf> {
+    let path = log_path()?;
+    fs::create_dir_all(path.parent()?).ok()?;
+    let file = open(&path)?;
+    let logger = FileLogger {
+        path: path.clone(),
+        file: Mutex::new(file),
+    };
+    log::set_logger(Box::leak(Box::new(logger))).ok()?;
+    log::set_max_level(level);
+    Some(path)
+}
+
+fn open(path: &Path) -> Option<File> {
+    OpenOptions::new().create(true).append(true).open(path).ok()
+}
+
+struct FileLogger {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileLogger {
+    // 当前文件过大时轮换到 .1，写入失败不影响播放
+    fn rotate_if_needed(&self, file: &mut File) {
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if size < MAX_LOG_SIZE {
+            return;
+        }
+        let rotated = self.path.with_extension("log.1");
+        if fs::rename(&self.path, rotated).is_ok()
+            && let Some(new_file) = open(&self.path)
+        {
+            *file = new_file;
+        }
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let thread = std::thread::current();
+        let line = format!(
+            "{} {:<5} [{}] {}: {}\n",
+            timestamp(),
+            record.level(),
+            thread.name().unwrap_or("-"),
+            record.target(),
+            record.args()
+        );
+        let mut file = self.file.lock();
+        self.rotate_if_needed(&mut file);
+        let _ = file.write_all(line.as_bytes());
+    }
+
+    fn flush(&self) {
+        let _ = self.file.lock().flush();
+    }
+}
+
+// UTC 时间，例如 2024-05-01 12:34:56.789
+fn timestamp() -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let secs = now.as_secs();
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03}",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60,
+        now.subsec_millis()
+    )
+}
+
+// 1970-01-01 起的天数换算为公历日期（Howard Hinnant 的 civil_from_days 算法）
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
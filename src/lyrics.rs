@@ -1,10 +1,15 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-#[derive(Default, Clone, Debug)]
+// 最多缓存的歌词文件数，超出时丢弃最久没有用到的
+const CACHE_CAPACITY: usize = 64;
+
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct Lyrics {
     pub lines: Vec<(u128, String)>, // 毫秒时间戳 -> 歌词行
+    // LRC 元数据标签，暂未在界面中展示，/lrcexport 时写回
     pub title: Option<String>,
     pub artist: Option<String>,
     pub album: Option<String>,
@@ -13,56 +18,52 @@ pub struct Lyrics {
 impl Lyrics {
     /// 解析同名 LRC 文件
     pub fn load_from_path(audio_path: &Path) -> Option<Self> {
-        let mut lrc_path = audio_path.to_path_buf();
-        lrc_path.set_extension("lrc");
-
+        let lrc_path = lrc_path(audio_path);
         if !lrc_path.exists() {
             return None;
         }
 
-        let file = File::open(&lrc_path).ok()?;
-        let reader = BufReader::new(file);
+        // 无效的 UTF-8 字节替换为 �，不影响其他行
+        let bytes = fs::read(&lrc_path).ok()?;
+        Some(Self::parse(&String::from_utf8_lossy(&bytes)))
+    }
+
+    /// 解析 LRC 文本，歌词行按时间排序
+    pub fn parse(text: &str) -> Self {
         let mut lines = vec![];
         let mut title = None;
         let mut artist = None;
         let mut album = None;
 
-        for line_result in reader.lines() {
-            let line = match line_result {
-                Ok(l) => l,
-                Err(_) => continue, // 跳过读取错误的行
-            };
-
+        for line in text.lines() {
             let line = line.trim();
             if line.is_empty() {
                 continue;
             }
 
             // 处理元数据标签
-            if line.starts_with('[') && line.contains(']') {
-                if let Some(end) = line.find(']') {
-                    let tag_content = &line[1..end];
-                    let text_content = line[end + 1..].trim();
-
-                    // 尝试解析时间戳
-                    if let Some(ms) = parse_timestamp(tag_content) {
-                        if !text_content.is_empty() {
-                            lines.push((ms, text_content.to_string()));
-                        }
-                    } else {
-                        // 处理元数据标签
-                        match tag_content.to_lowercase().as_str() {
-                            s if s.starts_with("ti:") => {
-                                title = Some(s[3..].trim().to_string());
-                            }
-                            s if s.starts_with("ar:") => {
-                                artist = Some(s[3..].trim().to_string());
-                            }
-                            s if s.starts_with("al:") => {
-                                album = Some(s[3..].trim().to_string());
-                            }
-                            _ => {} // 忽略其他标签
-                        }
+            if line.starts_with('[')
+                && line.contains(']')
+                && let Some(end) = line.find(']')
+            {
+                let tag_content = &line[1..end];
+
+                // 尝试解析时间戳。副歌等重复的歌词可以在行首写多个时间戳，
+                // 例如 [00:30.00][02:00.00]副歌，每个时间戳各对应一行
+                if parse_timestamp(tag_content).is_some() {
+                    let (stamps, text_content) = leading_timestamps(line);
+                    if !text_content.is_empty() {
+                        lines.extend(stamps.into_iter().map(|ms| (ms, text_content.to_string())));
+                    }
+                } else {
+                    // 处理元数据标签，标签名不区分大小写，值保留原样
+                    let (key, value) = tag_content.split_once(':').unwrap_or((tag_content, ""));
+                    let value = Some(value.trim().to_string());
+                    match key.trim().to_lowercase().as_str() {
+                        "ti" => title = value,
+                        "ar" => artist = value,
+                        "al" => album = value,
+                        _ => {} // 忽略其他标签
                     }
                 }
             }
@@ -71,22 +72,66 @@ impl Lyrics {
         // 按时间顺序排序
         lines.sort_by_key(|(ms, _)| *ms);
 
-        Some(Lyrics {
+        Lyrics {
             lines,
             title,
             artist,
             album,
-        })
+        }
     }
 
-    /// 根据毫秒时间返回当前行索引
-    pub fn current_line_index(&self, millis: u128) -> usize {
+    /// 转换为 LRC 文本：先写 ti、ar、al 元数据，再按 [mm:ss.xx] 时间戳逐行写歌词；
+    /// 时间不是 10 毫秒的整数倍时写成 [mm:ss.xxx]，保证重新解析后时间不变
+    pub fn to_lrc_string(&self) -> String {
+        let mut out = String::new();
+        for (tag, value) in [
+            ("ti", &self.title),
+            ("ar", &self.artist),
+            ("al", &self.album),
+        ] {
+            if let Some(value) = value {
+                out.push_str(&format!("[{}:{}]\n", tag, value));
+            }
+        }
+        for (ms, text) in &self.lines {
+            let (min, sec, frac) = (ms / 60_000, ms / 1000 % 60, ms % 1000);
+            if frac % 10 == 0 {
+                out.push_str(&format!(
+                    "[{:02}:{:02}.{:02}]{}\n",
+                    min,
+                    sec,
+                    frac / 10,
+                    text
+                ));
+            } else {
+                out.push_str(&format!("[{:02}:{:02}.{:03}]{}\n", min, sec, frac, text));
+            }
+        }
+        out
+    }
+
+    /// 根据毫秒时间返回正在唱的行，第一行开始之前（前奏）返回 None
+    pub fn active_line(&self, millis: u128) -> Option<usize> {
         self.lines
             .iter()
             .enumerate()
             .rfind(|(_, (ts, _))| *ts <= millis)
             .map(|(idx, _)| idx)
-            .unwrap_or(0)
+    }
+
+    /// 是否处在两行歌词之间的长间隔（如间奏）中：距离当前行开始已超过 threshold_ms，下一行还没开始
+    pub fn is_in_gap(&self, millis: u128, threshold_ms: u128) -> bool {
+        let Some(idx) = self.active_line(millis) else {
+            return false;
+        };
+        let start = self.lines[idx].0;
+        let has_next = self.lines.get(idx + 1).is_some_and(|(ts, _)| *ts > millis);
+        has_next && millis - start > threshold_ms
+    }
+
+    /// 根据毫秒时间返回当前行索引，前奏中为第一行
+    pub fn current_line_index(&self, millis: u128) -> usize {
+        self.active_line(millis).unwrap_or(0)
     }
 
     pub fn len(&self) -> usize {
@@ -98,6 +143,76 @@ impl Lyrics {
     }
 }
 
+/// 歌曲对应的 LRC 文件：同名、扩展名为 lrc
+pub fn lrc_path(audio_path: &Path) -> PathBuf {
+    audio_path.with_extension("lrc")
+}
+
+// 缓存的歌词，last_used 越大表示越近用到
+struct CachedLyrics {
+    lyrics: Option<Lyrics>,
+    modified: Option<SystemTime>, // 读取时 LRC 文件的修改时间，没有 LRC 文件时为 None
+    last_used: u64,
+}
+
+/// 已解析的歌词，来回切歌时不必每次重新读取 LRC 文件
+#[derive(Default)]
+pub struct LyricsCache {
+    entries: HashMap<PathBuf, CachedLyrics>,
+    clock: u64,
+}
+
+impl LyricsCache {
+    /// 取出歌曲的歌词，没有缓存或 LRC 文件有变化（修改、新增或删除）时重新读取
+    pub fn get(&mut self, audio_path: &Path) -> Option<Lyrics> {
+        let modified = std::fs::metadata(lrc_path(audio_path))
+            .and_then(|m| m.modified())
+            .ok();
+        self.clock += 1;
+        if let Some(cached) = self.entries.get_mut(audio_path)
+            && cached.modified == modified
+        {
+            cached.last_used = self.clock;
+            return cached.lyrics.clone();
+        }
+
+        let lyrics = Lyrics::load_from_path(audio_path);
+        if self.entries.len() >= CACHE_CAPACITY
+            && !self.entries.contains_key(audio_path)
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(path, _)| path.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(
+            audio_path.to_path_buf(),
+            CachedLyrics {
+                lyrics: lyrics.clone(),
+                modified,
+                last_used: self.clock,
+            },
+        );
+        lyrics
+    }
+}
+
+// 拆出行首连续的 [时间戳]，返回各时间戳和之后的歌词文字
+fn leading_timestamps(line: &str) -> (Vec<u128>, &str) {
+    let mut stamps = Vec::new();
+    let mut rest = line;
+    while let Some(tag) = rest.strip_prefix('[')
+        && let Some(end) = tag.find(']')
+        && let Some(ms) = parse_timestamp(&tag[..end])
+    {
+        stamps.push(ms);
+        rest = &tag[end + 1..];
+    }
+    (stamps, rest.trim())
+}
+
 fn parse_timestamp(ts: &str) -> Option<u128> {
     // 支持格式：mm:ss.xx, mm:ss.xxx, mm:ss, m:ss.xx 等
     let mut parts = ts.split(':');
@@ -122,3 +237,57 @@ fn parse_timestamp(ts: &str) -> Option<u128> {
 
     Some(mm * 60_000 + ss * 1000 + frac)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_chorus_is_active_at_each_timestamp() {
+        let lyrics = Lyrics {
+            lines: [30_000, 120_000, 210_000]
+                .into_iter()
+                .map(|ms| (ms, "Chorus".to_string()))
+                .collect(),
+            ..Lyrics::default()
+        };
+        assert_eq!(lyrics.active_line(25_000), None);
+        assert_eq!(lyrics.current_line_index(25_000), 0);
+        assert_eq!(lyrics.current_line_index(31_000), 0);
+        assert_eq!(lyrics.current_line_index(121_000), 1);
+        assert_eq!(lyrics.current_line_index(211_000), 2);
+    }
+
+    #[test]
+    fn line_with_several_timestamps_becomes_several_lines() {
+        let lyrics = Lyrics::parse("[00:10.00]Verse\n[00:30.00][02:00.00]Chorus\n[01:00.50]Bridge");
+        let lines: Vec<(u128, &str)> = lyrics
+            .lines
+            .iter()
+            .map(|(ms, text)| (*ms, text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (10_000, "Verse"),
+                (30_000, "Chorus"),
+                (60_500, "Bridge"),
+                (120_000, "Chorus"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lrc_string_round_trips_through_parse() {
+        let text = "[ti:Night Song]\n[ar:Some Artist]\n[al:Album: Deluxe]\n\
+                    [00:01.50]Two digits\n[00:02.345]Three digits\n[01:02.00][03:04.01]Twice";
+        let lyrics = Lyrics::parse(text);
+        assert_eq!(lyrics.title.as_deref(), Some("Night Song"));
+        assert_eq!(lyrics.album.as_deref(), Some("Album: Deluxe"));
+
+        let exported = lyrics.to_lrc_string();
+        assert!(exported.contains("[00:01.50]Two digits\n"));
+        assert!(exported.contains("[00:02.345]Three digits\n"));
+        assert_eq!(Lyrics::parse(&exported), lyrics);
+    }
+}
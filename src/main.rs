@@ -1,29 +1,105 @@
+mod audiobook;
+mod checkpoint;
+mod cli;
 mod command;
+mod config;
+mod confirm;
+mod copy;
+mod cue;
+mod decode;
+mod history;
+mod i18n;
+mod logging;
 mod lyrics;
+mod metadata;
+mod meter;
 mod player;
 mod playlist;
+mod replaygain;
+mod snapshot;
+mod tui;
 mod ui;
+mod undo;
+mod watcher;
+
+use crate::audiobook::{AudiobookState, chapter_index, format_speed, load_chapters};
+use crate::checkpoint::{CHECKPOINT_INTERVAL, Checkpoint};
+use crate::command::{
+    COMMANDS, Command, CommandSpec, CopyFlags, FolderFlags, HotkeyAction, expand_alias,
+    find_command, is_builtin_command, parse_command, parse_hotkey, split_commands,
+    suggest_commands, validate_alias,
+};
+use crate::config::Config;
+use crate::confirm::{Confirmation, Decision};
+use crate::history::History;
+use crate::i18n::{Msg, tr};
+use crate::lyrics::{Lyrics, LyricsCache};
+use crate::metadata::{MetadataCache, TrackInfo, format_size};
+use crate::player::{PlayState, Player, enumerate_pulse_sinks};
+use crate::playlist::{
+    ListFormat, PlaybackMode, Playlist, ScanLimit, SortKey, collect_audio_files,
+    load_playlist_file, resolve_path,
+};
+use crate::replaygain::{RgMode, format_gain, gain_db};
+use crate::snapshot::Snapshot;
+use crate::tui::Tui;
+use crate::ui::{
+    DebugInfo, FlashLevel, Renderer, Screen, Theme, ThemePreset, UiState, UiStyle, VIS_BARS,
+    Visualizer, border_line, center_line, divider, fit_width, format_time, spinner_frame, ui_width,
+    volume_bar,
+};
+use crate::undo::{UndoAction, UndoStack};
+use crate::watcher::FolderWatcher;
 
-use crate::command::{Command, parse_command};
-use crate::lyrics::Lyrics;
-use crate::player::Player;
-use crate::playlist::{PlaybackMode, Playlist};
-use crate::ui::{FlashLevel, Screen, UiState, show_goodbye_message};
-
-use crossbeam_channel::{Receiver, Sender, select, unbounded};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, bounded, select, unbounded};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
 use parking_lot::Mutex;
 use std::{
-    io::{self, BufRead, Write},
-    sync::Arc,
+    collections::BTreeMap,
+    io::{self, BufRead, IsTerminal, Write},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
+use unicode_width::UnicodeWidthStr;
+
+// /diff 每类变化最多列出的文件数
+const DIFF_LIST_LIMIT: usize = 10;
+// /list 默认每页显示的歌曲数
+const LIST_PAGE_SIZE: usize = 20;
+// /play <name> 有多首同样匹配时最多列出的候选数
+const PLAY_NAME_CANDIDATES: usize = 10;
+// /source 脚本最多嵌套的层数
+const MAX_SCRIPT_DEPTH: usize = 8;
+// 播放过程中保存播放位置的间隔
+const POSITION_SAVE_INTERVAL: Duration = Duration::from_secs(30);
+// 检查选择的输出设备是否仍然存在的间隔
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 
 // 应用状态
 #[derive(Clone)]
 struct AppState {
     ui: Arc<Mutex<UiState>>,
     playlist: Arc<Mutex<Playlist>>,
+    config: Arc<Mutex<Config>>,
+    history: Arc<Mutex<History>>,
+    audiobook: Arc<Mutex<AudiobookState>>,
+    watcher: Arc<Mutex<Option<FolderWatcher>>>, // 当前文件夹的监视器，未开启监视时为 None
+    metadata: Arc<Mutex<MetadataCache>>,        // /list 显示的时长和艺术家
+    lyrics: Arc<Mutex<LyricsCache>>,            // 已解析的歌词
+    undo: Arc<Mutex<UndoStack>>,                // /undo 可以撤销的列表操作
+    confirm: Arc<Mutex<Confirmation>>,          // 等待输入 y 确认的命令
+    scripts_running: Arc<AtomicUsize>,          // 正在执行的脚本数，脚本中的命令不能交互确认
+    interactive: Arc<AtomicBool>,               // 输入线程正在读取终端输入，此时不能 /sleep
+    assume_yes: bool,                           // --yes：非交互执行时自动确认
+    // UI 线程的事件通道。命令处理时拿到的是 run_command 的临时通道，后台线程需要用这个
+    ui_tx: Sender<AppEvent>,
+    background: Arc<Mutex<Vec<thread::JoinHandle<()>>>>, // 后台任务（/copyto），退出前等待完成
 }
 
 // 应用事件
@@ -32,43 +108,186 @@ enum AppEvent {
     // UI事件
     ShowMessage(String, FlashLevel),
     UpdatePlayingState(usize, String, String), // index, current, next
+    UpdateNext(String),                        // 只更新“下一首”，正在播放的歌曲不变
     UpdateLyrics(Option<Lyrics>),
-    UpdateProgress(u128),
+    UpdateProgress(u128, Option<Duration>), // 播放时间（毫秒）, 总时长
+    UpdateLevels(f32, f32),                 // 左右声道电平，用于 VU 表
+    UpdateVisualizer(Vec<f32>),             // 各频段的强度，用于频谱面板
     RefreshUI,
-
-    // 播放事件
-    PlayFile(usize),
-    PlayFinished,
+    ShowStartScreen,             // 清屏并显示启动时的初始界面
+    FolderChanged(Vec<PathBuf>), // 文件夹监视报告的变化路径
+    ScanProgress(usize, usize),  // 扫描中：已遍历的条目数, 已找到的音频数
+    ScanFinished,                // 扫描结束，清除进度行
+    ShowOverlay(String),         // 在播放界面上显示帮助页，暂停界面刷新
+    CloseOverlay,                // 关闭帮助页并恢复播放界面
 
     // 系统事件
     Shutdown,
 }
 
 fn main() -> anyhow::Result<()> {
-    let ui_state = Arc::new(Mutex::new(UiState::default()));
-    let playlist = Arc::new(Mutex::new(Playlist::default()));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (mut config, warnings) = Config::load();
+    i18n::set_lang(cli::lang_arg(&args).unwrap_or(config.ui.language));
+
+    let cli = match cli::parse_args(args) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("{}\n\n{}", tr!(Msg::ErrorLine, e), cli::usage());
+            std::process::exit(cli::EXIT_USAGE);
+        }
+    };
+    if cli.help {
+        println!("{}", cli::usage());
+        return Ok(());
+    }
+    // 默认不记录日志，--verbose 或 RUST_LOG 开启后写入 ~/.beatcli/beatcli.log
+    if let Some(level) = logging::level_from_env(cli.verbose) {
+        match logging::init(level) {
+            Some(path) => {
+                log::info!(
+                    "BeatCLI {} starting, args: {:?}",
+                    env!("CARGO_PKG_VERSION"),
+                    cli
+                );
+                eprintln!("{}", tr!(Msg::LoggingTo, path.display()));
+            }
+            None => eprintln!("{}", tr!(Msg::LoggingFailed)),
+        }
+    }
+    if let Some(path) = &cli.once {
+        std::process::exit(cli::run_once(path, &cli));
+    }
+    if let Some(script) = &cli.script
+        && !resolve_path(script).is_file()
+    {
+        eprintln!(
+            "{}",
+            tr!(Msg::ScriptNotFound, resolve_path(script).display())
+        );
+        std::process::exit(cli::EXIT_NOT_FOUND);
+    }
+
+    for warning in &warnings {
+        eprintln!("{}", tr!(Msg::ConfigWarning, warning));
+    }
+    if let Some(volume) = cli.volume {
+        config.playback.volume = volume;
+    }
+    config.ui.tui |= cli.tui;
+    config.aliases.retain(|name, _| {
+        let shadowed = is_builtin_command(name);
+        if shadowed {
+            eprintln!("{}", tr!(Msg::AliasShadowed, name));
+        }
+        !shadowed
+    });
+
+    // --no-color 或设置了 NO_COLOR 环境变量时不输出颜色（https://no-color.org/）
+    if cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        config.ui.color = false;
+    }
+    let mut style = UiStyle::from_config(&config.ui);
+    // 安静模式只用一行显示状态，优先于全屏界面
+    style.tui &= !cli.quiet;
+    ui::init_style(style);
+    // 终端不支持 ANSI 转义序列时改用清屏模式显示歌词；逐行输出时不显示播放界面，不需要提示
+    if config.ui.stream_lyrics && !ui::style().ansi && !ui::style().plain {
+        eprintln!("{}", tr!(Msg::LyricsStreamUnsupported));
+    }
+    if config.ui.tui && !ui::style().tui && !cli.quiet {
+        eprintln!("{}", tr!(Msg::TuiUnsupported));
+    }
+    // panic 时先恢复终端，否则错误信息会显示在备用屏幕上并随之消失
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        ui::restore_terminal();
+        default_hook(info);
+    }));
+    let _terminal_guard = ui::TerminalGuard;
+    ui::set_theme(Theme::from_config(&config.ui));
+    let ui_state = Arc::new(Mutex::new(UiState {
+        volume: Some(config.playback.volume),
+        mode: config.playback.mode,
+        // 不支持 ANSI 的终端（如旧版 Windows 控制台）只能使用清屏模式
+        lyrics_stream_mode: config.ui.stream_lyrics && ui::style().ansi,
+        show_lyrics: true,
+        speed: 1.0,
+        visualizer: cli.vis.then(Visualizer::default),
+        quiet_mode: cli.quiet,
+        show_pane: true,
+        list_page_size: LIST_PAGE_SIZE,
+        ..UiState::default()
+    }));
+    ui_state.lock().update_compact();
+    let mut playlist = Playlist::default();
+    playlist.mode = config.playback.mode;
+    playlist.default_mode = config.playback.mode;
+    playlist.scan_folder_options = config.scanning.scan_options();
+    let playlist = Arc::new(Mutex::new(playlist));
+    // 命令行指定的文件夹优先于配置中的默认文件夹
+    let music_folder = cli.folder.clone().or(config.paths.music_folder.clone());
+    let autoplay = config.playback.autoplay;
+    let auto_recover = config.playback.auto_recover;
+    let (cmd_tx, cmd_rx): (Sender<Command>, Receiver<Command>) = unbounded();
+    let (event_tx, event_rx): (Sender<AppEvent>, Receiver<AppEvent>) = unbounded();
     let app_state = AppState {
         ui: ui_state.clone(),
         playlist: playlist.clone(),
+        config: Arc::new(Mutex::new(config)),
+        history: Arc::new(Mutex::new(History::load())),
+        audiobook: Arc::new(Mutex::new(AudiobookState::load())),
+        watcher: Arc::new(Mutex::new(None)),
+        metadata: Arc::new(Mutex::new(MetadataCache::default())),
+        lyrics: Arc::new(Mutex::new(LyricsCache::default())),
+        undo: Arc::new(Mutex::new(UndoStack::default())),
+        confirm: Arc::new(Mutex::new(Confirmation::default())),
+        scripts_running: Arc::new(AtomicUsize::new(0)),
+        interactive: Arc::new(AtomicBool::new(false)),
+        assume_yes: cli.yes,
+        ui_tx: event_tx.clone(),
+        background: Arc::new(Mutex::new(Vec::new())),
     };
 
-    let (cmd_tx, cmd_rx): (Sender<Command>, Receiver<Command>) = unbounded();
-    let (event_tx, event_rx): (Sender<AppEvent>, Receiver<AppEvent>) = unbounded();
+    // 播放线程处理完一条命令后回执（命令是否成功），输入线程据此按顺序执行同一行的多条命令
+    let (ack_tx, ack_rx): (Sender<bool>, Receiver<bool>) = unbounded();
+
+    // 播放线程打开输出设备后报告结果，失败时主线程负责收尾退出
+    let (ready_tx, ready_rx) = bounded::<Result<(), String>>(1);
 
     // 启动播放线程
     let audio_handle = {
         let state = app_state.clone();
         let cmd_rx = cmd_rx.clone();
         let event_tx = event_tx.clone();
-        thread::spawn(move || {
+        spawn_named("audio", move || {
             let mut player = match Player::new() {
                 Ok(p) => p,
                 Err(e) => {
-                    eprintln!("错误: 播放器初始化失败: {}", e);
+                    log::error!("failed to open the default output stream: {}", e);
+                    let _ = ready_tx.send(Err(e.to_string()));
                     return;
                 }
             };
-            audio_thread(state, cmd_rx, event_tx, &mut player);
+            let _ = ready_tx.send(Ok(()));
+            let device = state.config.lock().playback.audio_device.clone();
+            if let Some(name) = device
+                && let Err(e) = player.set_device(&name)
+            {
+                log::warn!("configured device {:?} unavailable: {}", name, e);
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::DeviceFallback, name, e),
+                    FlashLevel::Error,
+                ));
+            }
+            player.set_spectrum(state.ui.lock().visualizer.is_some());
+            let playback = state.config.lock().playback.clone();
+            player.fade_in_duration = Duration::from_millis(playback.fade_in_ms);
+            player.fade_out_duration = Duration::from_millis(playback.fade_out_ms);
+            apply_mode_settings(&state, &mut player);
+            log::info!("audio thread started");
+            audio_thread(state, cmd_rx, event_tx, ack_tx, &mut player);
+            log::info!("audio thread stopped");
         })
     };
 
@@ -76,92 +295,346 @@ fn main() -> anyhow::Result<()> {
     let ui_handle = {
         let state = app_state.clone();
         let event_rx = event_rx.clone();
-        thread::spawn(move || {
+        spawn_named("ui", move || {
+            log::info!("ui thread started");
             ui_thread(state, event_rx);
+            log::info!("ui thread stopped");
+        })
+    };
+
+    // 没有播放线程时命令（包括 /quit）无人处理：关闭界面线程后退出，不启动检查点线程，
+    // 上次留下的检查点保留到下次启动
+    let init_error = match ready_rx.recv() {
+        Ok(result) => result.err(),
+        Err(e) => Some(e.to_string()),
+    };
+    if let Some(e) = init_error {
+        let _ = event_tx.send(AppEvent::Shutdown);
+        let _ = ui_handle.join();
+        let _ = audio_handle.join();
+        ui::restore_terminal();
+        eprintln!("{}", tr!(Msg::PlayerInitFailed, e));
+        log::logger().flush();
+        std::process::exit(cli::EXIT_FAILURE);
+    }
+
+    // 定期保存检查点，正常退出时关闭 checkpoint_stop 结束线程并删除检查点
+    let (checkpoint_stop, checkpoint_rx) = unbounded::<()>();
+    let checkpoint_handle = {
+        let state = app_state.clone();
+        spawn_named("checkpoint", move || {
+            checkpoint_thread(state, checkpoint_rx)
         })
     };
 
-    // 显示初始欢迎信息
-    println!("{}", help_text());
+    // 显示初始欢迎信息，全屏界面在第一次绘制时显示使用提示
+    if !ui::style().tui {
+        println!("{}", help_text());
+    }
+
+    // 上次异常退出时留下了检查点：询问是否恢复（auto_recover 时直接恢复），恢复后不再加载默认文件夹
+    let recovered = match Checkpoint::load_recent() {
+        Some(checkpoint) if auto_recover || confirm_recover() => {
+            recover_checkpoint(&app_state, &cmd_tx, &ack_rx, checkpoint);
+            true
+        }
+        Some(_) => {
+            Checkpoint::remove();
+            false
+        }
+        None => false,
+    };
+
+    // 加载默认音乐文件夹，再按命令行参数设置播放模式、开始播放
+    if !recovered && let Some(folder) = music_folder {
+        send_and_wait(
+            &cmd_tx,
+            &ack_rx,
+            // 启动时是否播放由 autoplay 和 --play 决定
+            Command::Folder(
+                folder,
+                FolderFlags {
+                    play: Some(false),
+                    ..FolderFlags::default()
+                },
+            ),
+        );
+    }
+    if let Some(mode) = cli.mode {
+        send_and_wait(&cmd_tx, &ack_rx, Command::Mode(mode));
+    }
+    if let Some(index) = cli.play {
+        send_and_wait(&cmd_tx, &ack_rx, Command::PlayIndex(index));
+    } else if autoplay && !recovered && !playlist.lock().items.is_empty() {
+        send_and_wait(&cmd_tx, &ack_rx, Command::PlayIndex(1));
+    }
+
+    // 执行 --script 指定的脚本，之后进入交互输入，除非指定了 --quit-after
+    let mut quit = false;
+    let mut script_failed = false;
+    if let Some(script) = &cli.script {
+        match run_script(
+            &app_state,
+            &cmd_tx,
+            &event_tx,
+            &ack_rx,
+            script,
+            cli.keep_going,
+            0,
+        ) {
+            ScriptOutcome::Completed => {}
+            ScriptOutcome::Failed => script_failed = true,
+            ScriptOutcome::Quit => quit = true,
+        }
+        quit |= cli.quit_after;
+    }
 
     // 主线程处理用户输入
-    input_thread(app_state, cmd_tx, event_tx)?;
+    let background = app_state.background.clone();
+    if quit {
+        let _ = cmd_tx.send(Command::Quit);
+    } else if ui::style().tui {
+        tui_input_thread(app_state, cmd_tx, event_tx, ack_rx)?;
+    } else {
+        input_thread(app_state, cmd_tx, event_tx, ack_rx)?;
+    }
+    log::info!("input finished, waiting for threads");
+
+    // 管道输入结束或 /quit 时，未完成的复制继续做完
+    let handles = std::mem::take(&mut *background.lock());
+    for handle in handles {
+        let _ = handle.join();
+    }
 
     // 等待所有线程结束
     let _ = audio_handle.join();
     let _ = ui_handle.join();
+    drop(checkpoint_stop);
+    let _ = checkpoint_handle.join();
+    Checkpoint::remove();
 
+    log::logger().flush();
+    if script_failed && cli.quit_after {
+        std::process::exit(cli::EXIT_FAILURE);
+    }
     Ok(())
 }
 
+// 检查点线程：每隔 CHECKPOINT_INTERVAL 保存一次会话状态，播放列表为空时删除检查点
+fn checkpoint_thread(state: AppState, stop_rx: Receiver<()>) {
+    while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(CHECKPOINT_INTERVAL) {
+        // 两个锁分开取，不在持有一个锁时等待另一个
+        let (items, current) = {
+            let pl = state.playlist.lock();
+            (pl.items.clone(), pl.current)
+        };
+        let checkpoint =
+            (!items.is_empty()).then(|| Checkpoint::capture(&state.ui.lock(), items, current));
+        match checkpoint {
+            Some(checkpoint) => {
+                if let Err(e) = checkpoint.save() {
+                    log::warn!("failed to save checkpoint: {}", e);
+                }
+            }
+            None => Checkpoint::remove(),
+        }
+    }
+}
+
+// 询问是否从检查点恢复，只在交互终端中询问，默认不恢复
+fn confirm_recover() -> bool {
+    if !io::stdin().is_terminal() {
+        return false;
+    }
+    print!("{}", Msg::RecoverPrompt.text());
+    io::stdout().flush().ok();
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+// 恢复检查点中的播放列表和界面设置，再从记录的位置继续播放
+fn recover_checkpoint(
+    state: &AppState,
+    cmd_tx: &Sender<Command>,
+    ack_rx: &Receiver<bool>,
+    checkpoint: Checkpoint,
+) {
+    log::info!(
+        "recovering checkpoint: {} songs, index {:?}",
+        checkpoint.items.len(),
+        checkpoint.now_index
+    );
+    let count = checkpoint.items.len();
+    let mut ui = state.ui.lock();
+    ui.volume = checkpoint.volume.or(ui.volume);
+    ui.mode = checkpoint.mode;
+    ui.show_lyrics = checkpoint.show_lyrics;
+    ui.lyrics_stream_mode = checkpoint.lyrics_stream_mode && ui::style().ansi;
+    drop(ui);
+    state
+        .playlist
+        .lock()
+        .restore(checkpoint.items, checkpoint.current, checkpoint.mode);
+
+    if let Some(idx) = checkpoint.now_index.filter(|&i| i < count) {
+        send_and_wait(cmd_tx, ack_rx, Command::PlayIndex(idx + 1));
+        if checkpoint.current_ms > 0 {
+            let position = Duration::from_millis(checkpoint.current_ms);
+            send_and_wait(cmd_tx, ack_rx, Command::Seek(position));
+        }
+    }
+    let _ = state.ui_tx.send(AppEvent::ShowMessage(
+        tr!(Msg::Recovered, count),
+        FlashLevel::Ok,
+    ));
+}
+
 // 音频播放线程
 fn audio_thread(
     state: AppState,
     cmd_rx: Receiver<Command>,
     event_tx: Sender<AppEvent>,
+    ack_tx: Sender<bool>,
     player: &mut Player,
 ) {
+    let mut position_saved_at = Instant::now();
+    let mut device_checked_at = Instant::now();
+    // 统计每秒更新进度的次数，/debug 中显示
+    let (mut ticks, mut ticks_since, mut tick_rate) = (0u32, Instant::now(), 0.0f32);
     loop {
         select! {
             recv(cmd_rx) -> cmd => {
                 match cmd {
                     Ok(Command::Quit) => {
                         // 停止播放并清理资源
+                        remember_position(&state, player);
                         player.stop();
+                        state.watcher.lock().take();
                         let _ = event_tx.send(AppEvent::Shutdown);
                         break;
                     }
                     Ok(command) => {
-                        handle_command(&state, player, command, &event_tx);
+                        let ok = run_command(&state, player, command, &event_tx);
+                        let _ = ack_tx.send(ok);
                     }
                     Err(_) => break, // Channel closed
                 }
             }
             default(Duration::from_millis(200)) => {
+                // 输出设备断开（如蓝牙耳机）时改用默认设备继续播放
+                if device_checked_at.elapsed() >= DEVICE_CHECK_INTERVAL {
+                    device_checked_at = Instant::now();
+                    if player.device_lost() {
+                        log::warn!("output device lost, switching to the default device");
+                        let msg = match player.use_default_device() {
+                            Ok(()) => tr!(Msg::DeviceLost),
+                            Err(e) => tr!(Msg::DeviceSwitchFailed, e),
+                        };
+                        let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Error));
+                    }
+                }
+
                 // 检查播放状态
                 if player.finished() {
-                    let mut pl = state.playlist.lock();
-                    if let Some(next_idx) = pl.advance_on_finished() {
-                        let path = pl.items[next_idx].clone();
-                        drop(pl);
-
-                        player.play_file(&path);
-                        let vol = state.ui.lock().volume.unwrap_or(50) as f32 / 100.0;
-                        player.set_volume(vol);
-
-                        let name = path.file_name()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or("")
-                            .to_string();
-                        let next_name = state.playlist.lock().peek_next_name();
-                        let lyrics = Lyrics::load_from_path(&path);
-
-                        // 发送UI更新事件
-                        let _ = event_tx.send(AppEvent::UpdatePlayingState(next_idx, name, next_name));
-                        let _ = event_tx.send(AppEvent::UpdateLyrics(lyrics));
-                        let _ = event_tx.send(AppEvent::RefreshUI);
+                    if !advance_track(&state, player, &event_tx, None) {
+                        // 播放列表播放完毕，状态区域显示已停止
+                        let mut ui = state.ui.lock();
+                        if ui.play_state != PlayState::Stopped {
+                            ui.play_state = PlayState::Stopped;
+                            let _ = event_tx.send(AppEvent::RefreshUI);
+                        }
                     }
                 } else {
+                    // 单曲循环时不淡出，避免每次重新开始时音量跳变
+                    if state.playlist.lock().mode != PlaybackMode::RepeatOne {
+                        // 接近结尾时提前开始播放下一首，两首歌重叠淡入淡出
+                        let crossfade = Duration::from_millis(state.config.lock().playback.crossfade_ms);
+                        match player.crossfade_due(crossfade) {
+                            Some(overlap) => {
+                                advance_track(&state, player, &event_tx, Some(overlap));
+                            }
+                            None => player.update_fade_out(),
+                        }
+                    }
+
                     // 更新播放进度
                     let current_ms = player.get_current_ms();
-                    let _ = event_tx.send(AppEvent::UpdateProgress(current_ms));
+                    ticks += 1;
+                    if ticks_since.elapsed() >= Duration::from_secs(1) {
+                        tick_rate = ticks as f32 / ticks_since.elapsed().as_secs_f32();
+                        (ticks, ticks_since) = (0, Instant::now());
+                    }
+                    let mut ui = state.ui.lock();
+                    ui.spinner_frame = spinner_frame(current_ms);
+                    if ui.debug_overlay {
+                        let queue = state
+                            .playlist
+                            .lock()
+                            .queue
+                            .iter()
+                            .map(|path| {
+                                path.file_name()
+                                    .and_then(|s| s.to_str())
+                                    .unwrap_or(Msg::UnknownFileName.text())
+                                    .to_string()
+                            })
+                            .collect();
+                        ui.debug_info = DebugInfo {
+                            decoded: player.decode_position(),
+                            clock: player.clock_position(),
+                            paused: player.paused_duration(),
+                            queue,
+                            tick_rate,
+                        };
+                    }
+                    drop(ui);
+                    let _ = event_tx.send(AppEvent::UpdateProgress(current_ms, player.total_duration()));
+                    if position_saved_at.elapsed() >= POSITION_SAVE_INTERVAL {
+                        position_saved_at = Instant::now();
+                        if state.ui.lock().play_state == PlayState::Playing {
+                            remember_position(&state, player);
+                        }
+                    }
+                    if state.ui.lock().show_vu {
+                        let (left, right) = player.levels();
+                        let _ = event_tx.send(AppEvent::UpdateLevels(left, right));
+                    }
+                    if state.ui.lock().visualizer.is_some() {
+                        let _ = event_tx.send(AppEvent::UpdateVisualizer(player.spectrum(VIS_BARS)));
+                    }
+
+                    // 章节切换时刷新状态区域
+                    let mut ui = state.ui.lock();
+                    if !ui.chapters.is_empty() {
+                        let chapter = chapter_index(&ui.chapters, Duration::from_millis(current_ms as u64));
+                        if chapter != ui.current_chapter {
+                            ui.current_chapter = chapter;
+                            let _ = event_tx.send(AppEvent::RefreshUI);
+                        }
+                    }
+                    drop(ui);
 
                     // 检查歌词是否需要更新定位（只在歌词行切换时才刷新UI）
                     let ui = state.ui.lock();
-                    if ui.show_lyrics && ui.lyrics.is_some() && ui.now_index.is_some() {
-                        if let Some(lyrics) = &ui.lyrics {
-                            let new_line_idx = lyrics.current_line_index(current_ms);
-                            let old_line_idx = ui.current_lyric_line.unwrap_or(usize::MAX);
+                    if !ui.ui_suspended && ui.show_lyrics && ui.lyrics.is_some() && ui.now_index.is_some()
+                        && let Some(lyrics) = &ui.lyrics {
+                            let new_line = lyrics.active_line(current_ms);
+                            let interlude = ui::interlude_frame(lyrics, current_ms);
 
-                            // 只有当歌词行发生变化时才刷新UI
-                            if new_line_idx != old_line_idx {
+                            // 只有当歌词行或间奏标记发生变化时才刷新UI
+                            if new_line != ui.current_lyric_line || interlude != ui.interlude_frame {
                                 drop(ui);
                                 // 更新当前歌词行索引
-                                state.ui.lock().current_lyric_line = Some(new_line_idx);
+                                let mut ui = state.ui.lock();
+                                ui.current_lyric_line = new_line;
+                                ui.interlude_frame = interlude;
+                                drop(ui);
                                 let _ = event_tx.send(AppEvent::RefreshUI);
                             }
                         }
-                    }
                 }
             }
         }
@@ -170,11 +643,38 @@ fn audio_thread(
 
 // UI线程
 fn ui_thread(state: AppState, event_rx: Receiver<AppEvent>) {
+    let mut screen: Box<dyn Renderer> = if ui::style().tui {
+        Box::new(Tui::new())
+    } else {
+        Box::new(Screen::new())
+    };
+    let screen = screen.as_mut();
     loop {
-        match event_rx.recv() {
+        // 队列中还有提示消息时，到时间后显示下一条
+        let wait = {
+            let ui = state.ui.lock();
+            ui.flash_wait().filter(|w| !w.is_zero() && !ui.ui_suspended)
+        };
+        let event = match wait {
+            Some(wait) => match event_rx.recv_timeout(wait) {
+                Err(RecvTimeoutError::Timeout) => {
+                    refresh_ui_now(&state, screen);
+                    continue;
+                }
+                event => event.map_err(|_| crossbeam_channel::RecvError),
+            },
+            None => event_rx.recv(),
+        };
+        match event {
             Ok(AppEvent::ShowMessage(msg, level)) => {
-                state.ui.lock().flash_message(Some(msg), level);
-                refresh_ui_now(&state);
+                let mut ui = state.ui.lock();
+                ui.flash_message(msg, level);
+                // 帮助页关闭后再显示
+                if ui.ui_suspended {
+                    continue;
+                }
+                drop(ui);
+                refresh_ui_now(&state, screen);
             }
             Ok(AppEvent::UpdatePlayingState(idx, current, next)) => {
                 let mut ui = state.ui.lock();
@@ -182,28 +682,114 @@ fn ui_thread(state: AppState, event_rx: Receiver<AppEvent>) {
                 ui.show_welcome = false;
                 // 不在这里刷新UI，等待ShowMessage事件一起刷新
             }
+            Ok(AppEvent::UpdateNext(next)) => {
+                state.ui.lock().next_name = next;
+            }
             Ok(AppEvent::UpdateLyrics(lyrics)) => {
                 state.ui.lock().lyrics = lyrics;
             }
-            Ok(AppEvent::UpdateProgress(ms)) => {
-                state.ui.lock().current_ms = ms;
-                // 不自动刷新UI，只有在歌词行变化时才刷新
+            Ok(AppEvent::UpdateProgress(ms, total)) => {
+                let mut ui = state.ui.lock();
+                ui.current_ms = ms;
+                ui.duration = total;
+                // 不自动刷新UI，只有在歌词行变化时才刷新；进度条原地更新。
+                // 终端大小变化后按新的宽度重绘整个播放界面
+                if ui.playing_ui_active && !ui.ui_suspended {
+                    // 调试区域每次都有变化，按行比较后重绘整个界面
+                    if screen.resized() || ui.debug_overlay {
+                        let pl_view = state.playlist.lock().clone_view();
+                        let _ = screen.refresh(&mut ui, &pl_view);
+                    } else {
+                        let _ = screen.update_progress(&ui);
+                    }
+                }
+            }
+            Ok(AppEvent::UpdateLevels(left, right)) => {
+                let mut ui = state.ui.lock();
+                ui.levels = (left, right);
+                if ui.playing_ui_active && ui.show_vu && !ui.ui_suspended {
+                    let _ = screen.update_vu_meter(&ui);
+                }
+            }
+            Ok(AppEvent::UpdateVisualizer(bars)) => {
+                let mut ui = state.ui.lock();
+                if let Some(vis) = &mut ui.visualizer {
+                    vis.update(bars);
+                }
+                if ui.playing_ui_active && !ui.ui_suspended {
+                    let _ = screen.update_visualizer(&ui);
+                }
             }
             Ok(AppEvent::RefreshUI) => {
                 // 对于 RefreshUI 事件，强制刷新播放界面
                 let mut ui = state.ui.lock();
+                if ui.ui_suspended {
+                    continue;
+                }
                 if ui.playing_ui_active {
                     let pl_view = state.playlist.lock().clone_view();
-                    if let Ok(mut screen) = Screen::new() {
-                        let _ = screen.force_refresh_playing_interface(&mut *ui, &pl_view);
-                    }
+                    let _ = screen.refresh(&mut ui, &pl_view);
                 } else {
                     drop(ui);
-                    refresh_ui_now(&state);
+                    refresh_ui_now(&state, screen);
+                }
+            }
+            Ok(AppEvent::ShowOverlay(text)) => {
+                state.ui.lock().ui_suspended = true;
+                let _ = screen.show_overlay(&text);
+            }
+            Ok(AppEvent::CloseOverlay) => {
+                let mut ui = state.ui.lock();
+                ui.ui_suspended = false;
+                if ui.playing_ui_active {
+                    let pl_view = state.playlist.lock().clone_view();
+                    let _ = screen.close_overlay(&mut ui, &pl_view);
+                }
+                // 显示帮助页期间收到的提示消息
+                if ui.flash.is_pending() {
+                    drop(ui);
+                    refresh_ui_now(&state, screen);
+                }
+            }
+            Ok(AppEvent::ShowStartScreen) => {
+                let _ = screen.show_start_screen(&help_text());
+            }
+            Ok(AppEvent::ScanProgress(seen, found)) => {
+                let ui = state.ui.lock();
+                let _ = screen.show_scan_progress(&ui, seen, found);
+            }
+            Ok(AppEvent::ScanFinished) => {
+                let _ = screen.clear_scan_progress();
+            }
+            Ok(AppEvent::FolderChanged(paths)) => {
+                let mut pl = state.playlist.lock();
+                let (added, removed) = pl.apply_folder_changes(&paths);
+                if added == 0 && removed == 0 {
+                    continue;
                 }
+                let total = pl.items.len();
+                let current = pl.current;
+                let next = pl.peek_next_name();
+                drop(pl);
+
+                let mut ui = state.ui.lock();
+                if let (Some(idx), Some(_)) = (current, ui.now_index) {
+                    let now_name = ui.now_name.clone();
+                    ui.set_now_playing(idx, now_name, next);
+                }
+                ui.flash_message(
+                    tr!(Msg::FolderChanged, added, removed, total),
+                    FlashLevel::Info,
+                );
+                drop(ui);
+                refresh_ui_now(&state, screen);
             }
             Ok(AppEvent::Shutdown) => {
-                show_goodbye_message();
+                screen.show_goodbye();
+                if std::env::var_os("BEATCLI_REDRAW_STATS").is_some() {
+                    let (full, partial) = screen.redraw_stats();
+                    eprintln!("{}", tr!(Msg::RedrawStats, full, partial));
+                }
                 break;
             }
             _ => break,
@@ -216,17 +802,42 @@ fn input_thread(
     state: AppState,
     cmd_tx: Sender<Command>,
     event_tx: Sender<AppEvent>,
+    ack_rx: Receiver<bool>,
 ) -> anyhow::Result<()> {
     let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    state.interactive.store(interactive, Ordering::SeqCst);
     let mut stdin_lock = stdin.lock();
 
     loop {
         // 只有在欢迎页或非播放模式下才显示输入提示符
         let ui = state.ui.lock();
         let should_show_prompt = ui.show_welcome || !ui.playing_ui_active;
+        let use_hotkeys = interactive && ui.playing_ui_active;
         drop(ui);
 
-        if should_show_prompt {
+        // 播放界面中使用单键热键，按 : 后临时切回行输入执行一条命令
+        let mut from_prompt = false;
+        if use_hotkeys {
+            set_hotkey_mode(&state, &event_tx, true)?;
+            match read_hotkey(&state)? {
+                HotkeyAction::Command(command) => {
+                    if matches!(command, Command::Quit) {
+                        let _ = cmd_tx.send(command);
+                        break;
+                    }
+                    send_and_wait(&cmd_tx, &ack_rx, command);
+                    thread::sleep(Duration::from_millis(100));
+                    continue;
+                }
+                HotkeyAction::Prompt => {
+                    set_hotkey_mode(&state, &event_tx, false)?;
+                    print!("\n>>: ");
+                    std::io::stdout().flush().ok();
+                    from_prompt = true;
+                }
+            }
+        } else if should_show_prompt {
             print!(">>: ");
             std::io::stdout().flush().ok();
         }
@@ -234,6 +845,8 @@ fn input_thread(
         let mut line = String::new();
         let n = stdin_lock.read_line(&mut line)?;
         if n == 0 {
+            // 输入结束（如管道中的命令已执行完）时退出，而不是一直等待
+            let _ = cmd_tx.send(Command::Quit);
             break;
         }
 
@@ -241,347 +854,1765 @@ fn input_thread(
         if line.is_empty() {
             continue;
         }
-
-        let command = parse_command(line);
-
-        if matches!(command, Command::Quit) {
-            let _ = cmd_tx.send(command);
+        if run_input_line(&state, &cmd_tx, &event_tx, &ack_rx, line, from_prompt) {
             break;
         }
 
-        let _ = cmd_tx.send(command);
-
-        // 给命令处理一些时间
+        // 给界面刷新一些时间
         thread::sleep(Duration::from_millis(100));
     }
 
+    set_hotkey_mode(&state, &event_tx, false)?;
     Ok(())
 }
 
-// 处理命令
-fn handle_command(
-    state: &AppState,
-    player: &mut Player,
-    cmd: Command,
-    event_tx: &Sender<AppEvent>,
-) {
-    match cmd {
-        Command::Help => {
-            let _ = event_tx.send(AppEvent::ShowMessage(help_text(), FlashLevel::Info));
-        }
+// 全屏界面的输入：按键逐个读入底部输入框，回车时执行其中的命令
+fn tui_input_thread(
+    state: AppState,
+    cmd_tx: Sender<Command>,
+    event_tx: Sender<AppEvent>,
+    ack_rx: Receiver<bool>,
+) -> anyhow::Result<()> {
+    terminal::enable_raw_mode()?;
+    state.interactive.store(true, Ordering::SeqCst);
+    state.ui.lock().input = Some(String::new());
+    let _ = event_tx.send(AppEvent::RefreshUI);
 
-        Command::Folder(path) => {
-            // 验证路径
-            if path.trim().is_empty() {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    "路径不能为空，请指定有效的文件夹路径".to_string(),
-                    FlashLevel::Error,
-                ));
-                return;
+    loop {
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            Event::Resize(..) => {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+                continue;
             }
+            _ => continue,
+        };
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            let _ = cmd_tx.send(Command::Quit);
+            break;
+        }
 
-            let folder_path = std::path::Path::new(&path);
-            if !folder_path.exists() {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    format!("路径不存在: {}", path),
-                    FlashLevel::Error,
-                ));
-                return;
+        let mut ui = state.ui.lock();
+        let Some(input) = ui.input.as_mut() else {
+            break;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                let line = std::mem::take(input);
+                drop(ui);
+                let _ = event_tx.send(AppEvent::RefreshUI);
+                let line = line.trim();
+                // 输入框中的命令和热键模式的提示符一样，可以省略开头的 /
+                if !line.is_empty()
+                    && run_input_line(&state, &cmd_tx, &event_tx, &ack_rx, line, true)
+                {
+                    break;
+                }
+                continue;
             }
-
-            if !folder_path.is_dir() {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    format!("路径不是一个文件夹: {}", path),
-                    FlashLevel::Error,
-                ));
-                return;
+            KeyCode::Backspace => {
+                input.pop();
             }
+            KeyCode::Esc => input.clear(),
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                input.push(c)
+            }
+            _ => continue,
+        }
+        drop(ui);
+        let _ = event_tx.send(AppEvent::RefreshUI);
+    }
+    Ok(())
+}
 
-            let mut pl = state.playlist.lock();
-            match pl.scan_folder(&path) {
-                Ok(count) => {
-                    if count == 0 {
-                        let _ = event_tx.send(AppEvent::ShowMessage(
-                            format!("文件夹 '{}' 中没有找到支持的音频文件", path),
-                            FlashLevel::Info,
-                        ));
-                    } else {
-                        let _ = event_tx.send(AppEvent::ShowMessage(
-                            format!("扫描到 {} 首歌曲", count),
-                            FlashLevel::Ok,
-                        ));
+// 执行一行输入，返回是否执行了 /quit。
+// 一行可以包含多条以分号分隔的命令，按顺序逐条执行；
+// 某条命令解析失败时报告错误，并放弃执行同一行中剩余的命令
+fn run_input_line(
+    state: &AppState,
+    cmd_tx: &Sender<Command>,
+    event_tx: &Sender<AppEvent>,
+    ack_rx: &Receiver<bool>,
+    line: &str,
+    from_prompt: bool,
+) -> bool {
+    for segment in split_commands(line) {
+        // 从热键模式进入的提示符允许省略开头的 /
+        let aliases = state.config.lock().aliases.clone();
+        let is_index = segment.chars().all(|c| c.is_ascii_digit());
+        let is_answer = command::parse_answer(segment).is_some();
+        let command = if from_prompt && !segment.starts_with('/') && !is_index && !is_answer {
+            parse_command(&format!("/{}", segment), &aliases)
+        } else {
+            parse_command(segment, &aliases)
+        };
+
+        match command {
+            Ok(Command::Noop) => {}
+            Ok(Command::Quit) => {
+                let _ = cmd_tx.send(Command::Quit);
+                return true;
+            }
+            Ok(Command::Source(path)) => {
+                match run_script(state, cmd_tx, event_tx, ack_rx, &path, false, 0) {
+                    ScriptOutcome::Completed => {}
+                    ScriptOutcome::Failed => break,
+                    ScriptOutcome::Quit => {
+                        let _ = cmd_tx.send(Command::Quit);
+                        return true;
                     }
                 }
-                Err(e) => {
-                    let _ = event_tx.send(AppEvent::ShowMessage(
-                        format!("扫描失败: {}", e),
-                        FlashLevel::Error,
-                    ));
-                }
             }
-        }
-
-        Command::List => {
-            let pl = state.playlist.lock();
-            if pl.items.is_empty() {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    "(空播放列表)\n请先使用 /folder <path> 选择目录".to_string(),
-                    FlashLevel::Info,
-                ));
-            } else {
-                let mut msg = "播放列表:\n".to_string();
-                for (i, path, is_current) in pl.list() {
-                    let name = path
-                        .file_name()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or("(未知文件名)");
-                    msg.push_str(&format_item(i, name, is_current));
+            Ok(Command::Sleep(duration)) => {
+                if !sleep(state, duration, event_tx) {
+                    break;
                 }
-                let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
             }
-        }
-
-        Command::PlayIndex(mut i) => {
-            let pl_len = state.playlist.lock().items.len();
-            if pl_len == 0 {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    "播放列表为空，请先使用 /folder 添加歌曲".to_string(),
-                    FlashLevel::Error,
-                ));
-                return;
+            Ok(command) => {
+                send_and_wait(cmd_tx, ack_rx, command);
             }
-
-            if i > pl_len {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    format!(
-                        "歌曲序号超出范围，当前播放列表有 {} 首歌曲，请输入 1-{} 之间的数字",
-                        pl_len, pl_len
-                    ),
-                    FlashLevel::Error,
-                ));
-                return;
+            Err(e) => {
+                let _ = event_tx.send(AppEvent::ShowMessage(e.to_string(), e.level()));
+                break;
             }
+        }
+    }
+    false
+}
 
-            if i > 0 && i <= pl_len {
-                i = i - 1; // 转换为0基索引
-            } else {
-                i = 0;
-            }
+// 发送命令并等待播放线程处理完成，使后续命令能看到前一条命令的效果。
+// 返回命令是否成功。不设超时：扫描大文件夹等较慢的命令超时后，迟到的回执会被当成下一条命令的，
+// 之后每条命令都与上一条的回执错开；耗时很长的 /copyto 在后台线程中进行，会立即回执。
+// 播放线程已退出时返回 false
+fn send_and_wait(cmd_tx: &Sender<Command>, ack_rx: &Receiver<bool>, command: Command) -> bool {
+    if cmd_tx.send(command).is_err() {
+        return false;
+    }
+    ack_rx.recv().unwrap_or(false)
+}
 
-            play_song(state, player, i, event_tx);
-        }
+// /sleep：暂停读取后续命令。终端交互输入时等待期间无法按键（全屏界面中连 Ctrl+C 也无效），
+// 只在启动脚本和管道输入中执行，否则报错并返回 false
+fn sleep(state: &AppState, duration: Duration, event_tx: &Sender<AppEvent>) -> bool {
+    if state.interactive.load(Ordering::SeqCst) {
+        let _ = event_tx.send(AppEvent::ShowMessage(
+            tr!(Msg::SleepInteractive),
+            FlashLevel::Error,
+        ));
+        return false;
+    }
+    thread::sleep(duration);
+    true
+}
 
-        Command::Next => {
-            if check_playlist_empty(state, event_tx) {
-                return;
-            }
-            next_song(state, player, event_tx);
-        }
+// 脚本的执行结果
+enum ScriptOutcome {
+    Completed,
+    Failed, // 有命令出错（未指定 --keep-going 时在出错处停止）
+    Quit,   // 脚本中执行了 /quit
+}
 
-        Command::Prev => {
-            if check_playlist_empty(state, event_tx) {
-                return;
-            }
-            prev_song(state, player, event_tx);
-        }
+// 脚本执行结束（包括提前返回）时减少正在执行的脚本数
+struct ScriptRunning<'a>(&'a AtomicUsize);
 
-        Command::Pause => {
+impl Drop for ScriptRunning<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// 逐行执行脚本文件中的命令（--script 和 /source），执行前回显每条命令。
+// # 开头的行是注释，空行跳过；keep_going 为 false 时在第一条出错的命令处停止
+fn run_script(
+    state: &AppState,
+    cmd_tx: &Sender<Command>,
+    event_tx: &Sender<AppEvent>,
+    ack_rx: &Receiver<bool>,
+    path: &str,
+    keep_going: bool,
+    depth: usize,
+) -> ScriptOutcome {
+    let script_path = resolve_path(path);
+    if depth >= MAX_SCRIPT_DEPTH {
+        let _ = event_tx.send(AppEvent::ShowMessage(
+            tr!(Msg::ScriptTooDeep, MAX_SCRIPT_DEPTH, script_path.display()),
+            FlashLevel::Error,
+        ));
+        return ScriptOutcome::Failed;
+    }
+    state.scripts_running.fetch_add(1, Ordering::SeqCst);
+    let _running = ScriptRunning(&state.scripts_running);
+    let content = match std::fs::read_to_string(&script_path) {
+        Ok(c) => c,
+        Err(e) => {
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::ScriptUnreadable, script_path.display(), e),
+                FlashLevel::Error,
+            ));
+            return ScriptOutcome::Failed;
+        }
+    };
+
+    let mut failed = false;
+    for (lineno, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        for segment in split_commands(line) {
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                format!("> {}", segment),
+                FlashLevel::Info,
+            ));
+            let aliases = state.config.lock().aliases.clone();
+            let ok = match parse_command(segment, &aliases) {
+                Ok(Command::Noop) => true,
+                Ok(Command::Quit) => return ScriptOutcome::Quit,
+                Ok(Command::Source(inner)) => {
+                    match run_script(
+                        state,
+                        cmd_tx,
+                        event_tx,
+                        ack_rx,
+                        &inner,
+                        keep_going,
+                        depth + 1,
+                    ) {
+                        ScriptOutcome::Completed => true,
+                        ScriptOutcome::Failed => false,
+                        ScriptOutcome::Quit => return ScriptOutcome::Quit,
+                    }
+                }
+                Ok(Command::Sleep(duration)) => sleep(state, duration, event_tx),
+                Ok(command) => send_and_wait(cmd_tx, ack_rx, command),
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(e.to_string(), e.level()));
+                    false
+                }
+            };
+            if !ok {
+                failed = true;
+                if !keep_going {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::ScriptStopped, script_path.display(), lineno + 1),
+                        FlashLevel::Error,
+                    ));
+                    return ScriptOutcome::Failed;
+                }
+                // 与交互输入一致，同一行中剩余的命令不再执行
+                break;
+            }
+        }
+    }
+
+    if failed {
+        ScriptOutcome::Failed
+    } else {
+        ScriptOutcome::Completed
+    }
+}
+
+// 切换终端 raw 模式并同步到 UI 状态，进入热键模式时重绘界面以显示按键说明
+fn set_hotkey_mode(state: &AppState, event_tx: &Sender<AppEvent>, enabled: bool) -> io::Result<()> {
+    let mut ui = state.ui.lock();
+    if ui.hotkey_mode == enabled {
+        return Ok(());
+    }
+    if enabled {
+        terminal::enable_raw_mode()?;
+    } else {
+        terminal::disable_raw_mode()?;
+    }
+    ui.hotkey_mode = enabled;
+    drop(ui);
+
+    if enabled {
+        let _ = event_tx.send(AppEvent::RefreshUI);
+    }
+    Ok(())
+}
+
+// 阻塞读取按键，直到得到一个有效的热键动作
+fn read_hotkey(state: &AppState) -> io::Result<HotkeyAction> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            let mut ui = state.ui.lock();
+            // 显示帮助页时任意键只用来返回播放界面
+            if ui.ui_suspended {
+                if key.kind == KeyEventKind::Press {
+                    ui.ui_suspended = false;
+                    let _ = state.ui_tx.send(AppEvent::CloseOverlay);
+                }
+                continue;
+            }
+            // 等待确认时 y、n 键用来确认或取消
+            if state.confirm.lock().is_pending()
+                && key.kind == KeyEventKind::Press
+                && let KeyCode::Char(c @ ('y' | 'n')) = key.code
+            {
+                return Ok(HotkeyAction::Command(Command::Confirm(c == 'y')));
+            }
+            if let Some(action) = parse_hotkey(&key, ui.play_state == PlayState::Paused) {
+                return Ok(action);
+            }
+        }
+    }
+}
+
+// 执行一条命令并把它产生的事件转发给界面，返回命令是否成功（没有报告错误）
+fn run_command(
+    state: &AppState,
+    player: &mut Player,
+    cmd: Command,
+    event_tx: &Sender<AppEvent>,
+) -> bool {
+    log::debug!("command: {:?}", cmd);
+    let (tx, rx) = unbounded();
+    // 等待确认时执行了其他命令，取消等待确认的命令
+    if !matches!(cmd, Command::Confirm(_)) && state.confirm.lock().cancel().is_some() {
+        let _ = tx.send(AppEvent::ShowMessage(
+            tr!(Msg::ConfirmCancelled),
+            FlashLevel::Info,
+        ));
+    }
+    // 显示帮助页时执行下一条命令，先恢复播放界面
+    if std::mem::take(&mut state.ui.lock().ui_suspended) {
+        let _ = tx.send(AppEvent::CloseOverlay);
+    }
+    handle_command(state, player, cmd, &tx);
+    let mut ok = true;
+    for event in rx.try_iter() {
+        if let AppEvent::ShowMessage(msg, FlashLevel::Error) = &event {
+            log::warn!("command failed: {}", msg);
+            ok = false;
+        }
+        let _ = event_tx.send(event);
+    }
+    ok
+}
+
+// 处理命令
+fn handle_command(
+    state: &AppState,
+    player: &mut Player,
+    cmd: Command,
+    event_tx: &Sender<AppEvent>,
+) {
+    match cmd {
+        Command::Help(None) => {
+            // 播放界面中把帮助显示为单独的一页，避免被歌词和进度刷新冲掉
+            let mut ui = state.ui.lock();
+            if ui.playing_ui_active && !ui.quiet_mode && !ui::style().plain && !ui::style().tui {
+                ui.ui_suspended = true;
+                let _ = event_tx.send(AppEvent::ShowOverlay(help_text()));
+            } else {
+                let _ = event_tx.send(AppEvent::ShowMessage(help_text(), FlashLevel::Info));
+            }
+        }
+
+        Command::Help(Some(topic)) => {
+            let topic = topic.trim_start_matches('/').to_lowercase();
+            let aliases = state.config.lock().aliases.clone();
+            let mut msg = String::new();
+            let mut name = topic.clone();
+            // 别名显示其对应命令的说明
+            if find_command(&topic).is_none()
+                && aliases.contains_key(&topic)
+                && let Ok(expanded) = expand_alias(&format!("/{}", topic), &aliases)
+            {
+                msg = tr!(Msg::HelpIsAlias, topic, expanded);
+                name = expanded
+                    .trim_start_matches('/')
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("")
+                    .to_string();
+            }
+
+            let Some(spec) = find_command(&name) else {
+                let candidates = suggest_commands(&topic, &BTreeMap::new());
+                let mut msg = tr!(Msg::HelpNotFound, topic);
+                if !candidates.is_empty() {
+                    let suggestion = candidates
+                        .iter()
+                        .map(|c| format!("/help {}", c))
+                        .collect::<Vec<_>>()
+                        .join(Msg::Or.text());
+                    msg.push_str(&tr!(Msg::DidYouMean, suggestion));
+                }
+                msg.push_str(Msg::HelpSeeAll.text());
+                let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Error));
+                return;
+            };
+            msg.push_str(&command_help_text(spec));
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
+        }
+
+        Command::Folder(path, flags) => {
+            // 验证路径
+            if path.trim().is_empty() {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::PathEmpty),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            let folder_path = resolve_path(&path);
+            let display_path = folder_path.display().to_string();
+            if !folder_path.exists() {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::PathNotFound, display_path),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            if !folder_path.is_dir() {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NotAFolder, display_path),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            let depth = flags
+                .depth
+                .or(state.config.lock().scanning.max_depth)
+                .unwrap_or(usize::MAX);
+            let mut pl = state.playlist.lock();
+            let follow_symlinks = flags.symlinks || pl.scan_folder_options.follow_symlinks;
+            let include_hidden = flags.hidden || pl.scan_folder_options.include_hidden;
+            let result = pl.scan_folder_depth(
+                &folder_path,
+                depth,
+                follow_symlinks,
+                include_hidden,
+                &mut |seen, found| {
+                    let _ = event_tx.send(AppEvent::ScanProgress(seen, found));
+                },
+            );
+            let _ = event_tx.send(AppEvent::ScanFinished);
+            match result {
+                Ok(summary) => {
+                    let mode = pl.mode;
+                    drop(pl);
+                    state.ui.lock().mode = mode;
+                    state.undo.lock().clear();
+                    restart_watcher(state, event_tx);
+                    let depth_desc = if depth == usize::MAX {
+                        tr!(Msg::DepthUnlimited)
+                    } else {
+                        tr!(Msg::DepthLevels, depth)
+                    };
+                    let mut skipped = String::new();
+                    if summary.unsupported > 0 {
+                        skipped.push_str(&tr!(Msg::SkippedUnsupported, summary.unsupported));
+                    }
+                    if summary.skipped_hidden > 0 {
+                        skipped.push_str(&tr!(Msg::SkippedHidden, summary.skipped_hidden));
+                    }
+                    if summary.added == 0 {
+                        let _ = event_tx.send(AppEvent::ShowMessage(
+                            tr!(Msg::NoAudioFound, display_path, skipped, depth_desc),
+                            FlashLevel::Info,
+                        ));
+                    } else {
+                        let _ = event_tx.send(AppEvent::ShowMessage(
+                            tr!(Msg::ScanFound, summary.added, skipped, display_path),
+                            FlashLevel::Ok,
+                        ));
+                    }
+                    if summary.skipped_links > 0 {
+                        let _ = event_tx.send(AppEvent::ShowMessage(
+                            tr!(Msg::SkippedLinks, summary.skipped_links),
+                            FlashLevel::Info,
+                        ));
+                    }
+                    if let Some(limit) = summary.stopped {
+                        let _ = event_tx.send(AppEvent::ShowMessage(
+                            scan_stopped_message(limit, summary.added),
+                            FlashLevel::Error,
+                        ));
+                    }
+                    let play = flags
+                        .play
+                        .unwrap_or(state.config.lock().playback.play_after_scan);
+                    if play && summary.added > 0 {
+                        // 随机模式下从随机顺序中取第一首，否则从第一首开始
+                        let mut pl = state.playlist.lock();
+                        let first = match pl.mode {
+                            PlaybackMode::Shuffle => pl.next_index().unwrap_or(0),
+                            _ => 0,
+                        };
+                        drop(pl);
+                        handle_command(state, player, Command::PlayIndex(first + 1), event_tx);
+                    }
+                }
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::ScanFailed, e),
+                        FlashLevel::Error,
+                    ));
+                }
+            }
+        }
+
+        Command::Reload => {
+            let mut pl = state.playlist.lock();
+            let Some(folder) = pl.folder.clone() else {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NoFolderYet),
+                    FlashLevel::Info,
+                ));
+                return;
+            };
+            let result = pl.reload(&mut |seen, found| {
+                let _ = event_tx.send(AppEvent::ScanProgress(seen, found));
+            });
+            let _ = event_tx.send(AppEvent::ScanFinished);
+            let Some((added, removed, summary)) = result else {
+                return;
+            };
+            let total = pl.items.len();
+            drop(pl);
+
+            sync_playing_state(state, event_tx);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::Reloaded, folder.display(), added, removed, total),
+                FlashLevel::Ok,
+            ));
+            if let Some(limit) = summary.stopped {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    scan_stopped_message(limit, summary.added),
+                    FlashLevel::Error,
+                ));
+            }
+        }
+
+        Command::List(format) => {
+            let page_size = state.ui.lock().list_page_size;
+            let page = state.playlist.lock().current_page(page_size);
+            show_list_page(state, page, format, event_tx);
+        }
+
+        Command::ListPage(page, format) => show_list_page(state, page, format, event_tx),
+
+        Command::ListPageSize(size) => {
+            state.ui.lock().list_page_size = size;
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::ListPageSizeSet, size),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::PlayIndex(mut i) => {
+            let pl_len = state.playlist.lock().items.len();
+            if pl_len == 0 {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::PlaylistEmpty),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            if i > pl_len {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::IndexOutOfRange, pl_len, pl_len),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            if i > 0 && i <= pl_len {
+                i -= 1; // 转换为0基索引
+            } else {
+                i = 0;
+            }
+
+            play_song(state, player, i, event_tx);
+        }
+
+        Command::PlayRange(from, to) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            // 第一首立即播放，其余的插入到队列最前面，按顺序接着播放；
+            // 检查范围和取出歌曲在同一次加锁中完成
+            let mut pl = state.playlist.lock();
+            if let Err(error) = pl.check_range(from, to) {
+                drop(pl);
+                let _ = event_tx.send(AppEvent::ShowMessage(error, FlashLevel::Error));
+                return;
+            }
+            let rest = pl.items[from..to].to_vec();
+            pl.queue_front(rest);
+            drop(pl);
+            handle_command(state, player, Command::PlayIndex(from), event_tx);
+
+            if to > from {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::RangeQueued, from + 1, to, to - from),
+                    FlashLevel::Ok,
+                ));
+            }
+        }
+
+        Command::PlayRandom => {
             if check_playlist_empty(state, event_tx) {
                 return;
             }
-            if !is_playing(state) {
+
+            let pl = state.playlist.lock();
+            let single = pl.items.len() == 1;
+            let idx = pl.random_index();
+            drop(pl);
+
+            if single {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::OnlyOneSongReplay),
+                    FlashLevel::Info,
+                ));
+            }
+            if let Some(i) = idx {
+                play_song(state, player, i, event_tx);
+            }
+        }
+
+        Command::Sample(0, _) => {
+            let cleared = state.playlist.lock().clear_queue();
+            sync_playing_state(state, event_tx);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::QueueCleared, cleared),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Sample(n, looped) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            let added = state.playlist.lock().sample(n, looped);
+            sync_playing_state(state, event_tx);
+
+            let mut msg = tr!(Msg::SampleAdded, added);
+            if looped {
+                msg.push_str(Msg::SampleLoopHint.text());
+            }
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+        }
+
+        Command::Goto(i) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            let mut pl = state.playlist.lock();
+            let pl_len = pl.items.len();
+            if i > pl_len {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::IndexOutOfRange, pl_len, pl_len),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            // 只移动列表位置和“下一首”，正在播放的歌曲不受影响
+            pl.current = Some(i - 1);
+            let name = pl.items[i - 1]
+                .file_name()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_string();
+            let next = pl.peek_next_name();
+            drop(pl);
+            let _ = event_tx.send(AppEvent::UpdateNext(next));
+            let _ = event_tx.send(AppEvent::RefreshUI);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::GotoDone, i, name),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Next => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+            next_song(state, player, event_tx);
+        }
+
+        Command::Prev => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+            prev_song(state, player, event_tx);
+        }
+
+        Command::Pause => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+            if !is_playing(state) {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NothingPlaying),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+            player.pause();
+            state.ui.lock().play_state = player.state();
+            let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::Paused), FlashLevel::Ok));
+        }
+
+        Command::Resume => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+            if !is_playing(state) {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NothingPlaying),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+            player.resume();
+            state.ui.lock().play_state = player.state();
+            let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::Resumed), FlashLevel::Ok));
+        }
+
+        Command::Continue => {
+            let Some(path) = player.current_path().map(Path::to_path_buf) else {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NothingPlaying),
+                    FlashLevel::Error,
+                ));
+                return;
+            };
+            let Some(ms) = state.history.lock().position(&path) else {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NoSavedPosition),
+                    FlashLevel::Info,
+                ));
+                return;
+            };
+
+            let start = Duration::from_millis(ms);
+            player.play_file_at(&path, start);
+            player.set_volume(state.ui.lock().output_volume());
+            state.ui.lock().play_state = player.state();
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::ResumedFrom, format_time(start)),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Seek(position) => seek(state, player, position, event_tx),
+
+        Command::SeekRelative(secs) => {
+            let current = player.get_current_ms() as u64;
+            let target = current.saturating_add_signed(secs.saturating_mul(1000));
+            seek(state, player, Duration::from_millis(target), event_tx);
+        }
+
+        Command::Volume(v) => set_volume(state, player, v, event_tx),
+
+        Command::VolumeUp(step) => {
+            let v = state.ui.lock().volume.unwrap_or(50);
+            set_volume(state, player, v.saturating_add(step).min(100), event_tx);
+        }
+
+        Command::VolumeDown(step) => {
+            let v = state.ui.lock().volume.unwrap_or(50);
+            set_volume(state, player, v.saturating_sub(step), event_tx);
+        }
+
+        Command::Lyrics => {
+            if !is_playing(state) {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::LyricsNeedPlaying),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            let mut ui = state.ui.lock();
+            ui.toggle_lyrics();
+            let status = if ui.show_lyrics {
+                Msg::LyricsShown.text()
+            } else {
+                Msg::LyricsHidden.text()
+            };
+
+            if ui.show_lyrics {
+                if let Some(lyrics) = &ui.lyrics {
+                    if lyrics.is_empty() {
+                        let _ = event_tx.send(AppEvent::ShowMessage(
+                            tr!(Msg::LyricsEmptyFile, status),
+                            FlashLevel::Info,
+                        ));
+                    } else {
+                        let _ = event_tx.send(AppEvent::ShowMessage(
+                            tr!(Msg::LyricsLoaded, status, lyrics.len()),
+                            FlashLevel::Ok,
+                        ));
+                    }
+                } else {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::LyricsNoFile, status),
+                        FlashLevel::Info,
+                    ));
+                }
+            } else {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::LyricsStatus, status),
+                    FlashLevel::Ok,
+                ));
+            }
+            let _ = event_tx.send(AppEvent::RefreshUI);
+        }
+
+        Command::LyricsExport(path) => {
+            // 歌词属于正在播放的歌曲；/goto 只移动列表中的当前位置，不能用它决定文件名
+            let Some(audio) = player.current_path().map(Path::to_path_buf) else {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NothingPlayingHint),
+                    FlashLevel::Error,
+                ));
+                return;
+            };
+            let Some(lyrics) = state.ui.lock().lyrics.clone() else {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::LyricsNothingToExport),
+                    FlashLevel::Error,
+                ));
+                return;
+            };
+
+            // . 表示歌曲旁边的同名 LRC 文件；文件夹中使用同样的文件名
+            let sidecar = lyrics::lrc_path(&audio);
+            let target = if path == "." {
+                sidecar
+            } else {
+                let target = resolve_path(&path);
+                match sidecar.file_name() {
+                    Some(name) if target.is_dir() => target.join(name),
+                    _ => target,
+                }
+            };
+            if target.exists()
+                && !confirm(
+                    state,
+                    Command::LyricsExport(path),
+                    tr!(Msg::ConfirmOverwrite, target.display()),
+                    event_tx,
+                )
+            {
+                return;
+            }
+            match std::fs::write(&target, lyrics.to_lrc_string()) {
+                Ok(()) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::LyricsExported, lyrics.len(), target.display()),
+                        FlashLevel::Ok,
+                    ));
+                }
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::LyricsExportFailed, target.display(), e),
+                        FlashLevel::Error,
+                    ));
+                }
+            }
+        }
+
+        Command::LyricsMode => {
+            if !is_playing(state) {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::LyricsModeNeedPlaying),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+
+            let mut ui = state.ui.lock();
+            if !ui.lyrics_stream_mode && !ui::style().ansi {
+                drop(ui);
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::LyricsStreamUnsupported),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+            ui.toggle_lyrics_mode();
+            let mode_name = if ui.lyrics_stream_mode {
+                Msg::LyricsStream.text()
+            } else {
+                Msg::LyricsClear.text()
+            };
+
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::LyricsModeSwitched, mode_name),
+                FlashLevel::Ok,
+            ));
+            let _ = event_tx.send(AppEvent::RefreshUI);
+        }
+
+        Command::Vu(on) => {
+            let mut ui = state.ui.lock();
+            ui.show_vu = on.unwrap_or(!ui.show_vu);
+            let msg = if ui.show_vu {
+                Msg::VuOn.text()
+            } else {
+                Msg::VuOff.text()
+            };
+            let playing = ui.playing_ui_active;
+            drop(ui);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.to_string(), FlashLevel::Ok));
+            if playing {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+            }
+        }
+
+        Command::Viz(on) => {
+            let mut ui = state.ui.lock();
+            let enabled = on.unwrap_or(ui.visualizer.is_none());
+            if enabled != ui.visualizer.is_some() {
+                ui.visualizer = enabled.then(Visualizer::default);
+            }
+            player.set_spectrum(enabled);
+            let msg = if enabled {
+                Msg::VizOn.text()
+            } else {
+                Msg::VizOff.text()
+            };
+            let playing = ui.playing_ui_active;
+            drop(ui);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.to_string(), FlashLevel::Ok));
+            if playing {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+            }
+        }
+
+        Command::Debug(on) => {
+            let mut ui = state.ui.lock();
+            ui.debug_overlay = on.unwrap_or(!ui.debug_overlay);
+            let msg = if ui.debug_overlay {
+                Msg::DebugOn.text()
+            } else {
+                Msg::DebugOff.text()
+            };
+            let playing = ui.playing_ui_active;
+            drop(ui);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.to_string(), FlashLevel::Ok));
+            if playing {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+            }
+        }
+
+        Command::Pane(on) => {
+            let mut ui = state.ui.lock();
+            ui.show_pane = on.unwrap_or(!ui.show_pane);
+            let msg = if ui.show_pane {
+                Msg::PaneOn.text()
+            } else {
+                Msg::PaneOff.text()
+            };
+            let playing = ui.playing_ui_active;
+            drop(ui);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.to_string(), FlashLevel::Ok));
+            if playing {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+            }
+        }
+
+        Command::Compact(on) => {
+            let mut ui = state.ui.lock();
+            ui.compact_override = Some(on.unwrap_or(!ui.compact_mode));
+            ui.update_compact();
+            let msg = if ui.compact_mode {
+                Msg::CompactOn.text()
+            } else {
+                Msg::CompactOff.text()
+            };
+            let playing = ui.playing_ui_active;
+            drop(ui);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.to_string(), FlashLevel::Ok));
+            if playing {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+            }
+        }
+
+        Command::Quiet(on) => {
+            if ui::style().tui {
+                let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::QuietTui), FlashLevel::Error));
+                return;
+            }
+            let mut ui = state.ui.lock();
+            ui.quiet_mode = on.unwrap_or(!ui.quiet_mode);
+            let msg = if ui.quiet_mode {
+                Msg::QuietOn.text()
+            } else {
+                Msg::QuietOff.text()
+            };
+            let playing = ui.playing_ui_active;
+            drop(ui);
+
+            // 先切换界面再显示消息：开启时消息输出在普通屏幕上，关闭时输出在重绘的播放界面下方
+            if playing {
+                let _ = event_tx.send(AppEvent::RefreshUI);
+            }
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.to_string(), FlashLevel::Ok));
+        }
+
+        Command::Lang(lang) => {
+            i18n::set_lang(lang);
+            state.config.lock().ui.language = lang;
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::LangSwitched),
+                FlashLevel::Ok,
+            ));
+            let _ = event_tx.send(AppEvent::RefreshUI);
+        }
+
+        Command::Theme(name) => {
+            let mut config = state.config.lock();
+            // 切换方案时不再使用配置中单独覆盖的颜色
+            let Some(theme) = Theme::named(&name, &config.ui) else {
+                let available: Vec<&str> = ThemePreset::ALL
+                    .iter()
+                    .map(|preset| preset.name())
+                    .chain(config.ui.themes.keys().map(String::as_str))
+                    .collect();
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::ThemeUnknown, name, available.join(", ")),
+                    FlashLevel::Error,
+                ));
+                return;
+            };
+            ui::set_theme(Theme {
+                border_style: config.ui.border_style,
+                ..theme
+            });
+            config.ui.theme = name.clone();
+            drop(config);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::ThemeSwitched, name),
+                FlashLevel::Ok,
+            ));
+            let _ = event_tx.send(AppEvent::RefreshUI);
+        }
+
+        Command::FadeIn(duration) => {
+            player.fade_in_duration = duration;
+            let msg = if duration.is_zero() {
+                tr!(Msg::FadeInOff)
+            } else {
+                tr!(Msg::FadeInSet, duration.as_millis())
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+        }
+
+        Command::FadeOut(duration) => {
+            player.fade_out_duration = duration;
+            let msg = if duration.is_zero() {
+                tr!(Msg::FadeOutOff)
+            } else {
+                tr!(Msg::FadeOutSet, duration.as_millis())
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+        }
+
+        Command::Border(style) => {
+            state.config.lock().ui.border_style = style;
+            ui::set_theme(Theme {
+                border_style: style,
+                ..ui::theme()
+            });
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::BorderSwitched, style.name()),
+                FlashLevel::Ok,
+            ));
+            let _ = event_tx.send(AppEvent::RefreshUI);
+        }
+
+        Command::ReplayGain(mode) => {
+            state.config.lock().playback.replaygain_mode = mode;
+            let mut msg = match mode {
+                RgMode::Off => tr!(Msg::RgOff),
+                RgMode::Track => tr!(Msg::RgTrack),
+                RgMode::Album => tr!(Msg::RgAlbum),
+            };
+            // 立即按新模式调整正在播放的歌曲
+            if let Some(path) = player.current_path().map(Path::to_path_buf) {
+                apply_replaygain(state, &path);
+                let ui = state.ui.lock();
+                player.set_volume(ui.output_volume());
+                match ui.replay_gain {
+                    Some(db) => msg.push_str(&tr!(Msg::RgApplied, format_gain(db))),
+                    None if mode != RgMode::Off => msg.push_str(Msg::RgNoTags.text()),
+                    None => {}
+                }
+            }
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+            let _ = event_tx.send(AppEvent::RefreshUI);
+        }
+
+        Command::DeviceList => {
+            // cpal 的设备之后列出 PulseAudio / PipeWire 的 sink，sink 附带描述
+            let mut devices: Vec<(String, String)> = Player::list_devices()
+                .into_iter()
+                .map(|name| (name.clone(), name))
+                .collect();
+            for (name, description) in enumerate_pulse_sinks() {
+                let label = format!("{} ({})", name, description);
+                devices.push((name, label));
+            }
+            if devices.is_empty() {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::DeviceNone),
+                    FlashLevel::Info,
+                ));
+                return;
+            }
+            let current = state.config.lock().playback.audio_device.clone();
+            let mut msg = tr!(Msg::DeviceHeader);
+            for (i, (name, label)) in devices.iter().enumerate() {
+                msg.push_str(&format_item(i, label, current.as_ref() == Some(name)));
+            }
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                msg.trim_end().to_string(),
+                FlashLevel::Info,
+            ));
+        }
+
+        Command::DeviceSelect(name) => {
+            if let Err(e) = player.set_device(&name) {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "没有正在播放的歌曲".to_string(),
+                    tr!(Msg::DeviceSwitchFailed, e),
                     FlashLevel::Error,
                 ));
                 return;
             }
-            player.pause();
-            let _ = event_tx.send(AppEvent::ShowMessage("已暂停".to_string(), FlashLevel::Ok));
+            let mut config = state.config.lock();
+            config.playback.audio_device = Some(name.clone());
+            let msg = match config.save() {
+                Ok(()) => (tr!(Msg::DeviceSwitched, name), FlashLevel::Ok),
+                Err(e) => (tr!(Msg::DeviceSaveFailed, name, e), FlashLevel::Error),
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.0, msg.1));
         }
 
-        Command::Resume => {
+        Command::Watch(on) => {
+            let mut config = state.config.lock();
+            config.scanning.watch = on.unwrap_or(!config.scanning.watch);
+            let enabled = config.scanning.watch;
+            drop(config);
+
+            restart_watcher(state, event_tx);
+            let watching = state.watcher.lock().is_some();
+            let msg = match (enabled, watching, state.playlist.lock().folder.clone()) {
+                (false, _, _) => tr!(Msg::WatchOff),
+                (true, true, Some(folder)) => tr!(Msg::Watching, folder.display()),
+                (true, false, Some(_)) => return, // 启动失败，restart_watcher 已提示
+                (true, _, None) => tr!(Msg::WatchPending),
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+        }
+
+        Command::Now => {
             if check_playlist_empty(state, event_tx) {
                 return;
             }
-            if !is_playing(state) {
+            show_now_playing(state, event_tx);
+        }
+
+        Command::Info => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+            show_track_info(state, player, event_tx);
+        }
+
+        Command::PlayName(query) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            let matched = state.playlist.lock().match_name(&query);
+            match matched {
+                Ok(idx) => handle_command(state, player, Command::PlayIndex(idx + 1), event_tx),
+                Err(candidates) if candidates.is_empty() => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::SearchNone, query),
+                        FlashLevel::Error,
+                    ));
+                }
+                Err(candidates) => {
+                    let mut msg = tr!(Msg::PlayNameAmbiguous, candidates.len(), query);
+                    for (idx, path) in candidates.iter().take(PLAY_NAME_CANDIDATES) {
+                        let name = path
+                            .file_name()
+                            .and_then(|s| s.to_str())
+                            .unwrap_or(Msg::UnknownFileName.text());
+                        msg.push_str(&format!("  {}. {}\n", idx + 1, name));
+                    }
+                    if candidates.len() > PLAY_NAME_CANDIDATES {
+                        msg.push_str("  …\n");
+                    }
+                    msg.push_str(Msg::SearchHint.text());
+                    let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
+                }
+            }
+        }
+
+        Command::Search(query) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            let pl = state.playlist.lock();
+            let results = pl.search(&query);
+            drop(pl);
+
+            if results.is_empty() {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "没有正在播放的歌曲".to_string(),
-                    FlashLevel::Error,
+                    tr!(Msg::SearchNone, query),
+                    FlashLevel::Info,
                 ));
+            } else {
+                let mut msg = tr!(Msg::SearchResults, query);
+                for (idx, path) in results {
+                    let name = path
+                        .file_name()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or(Msg::UnknownFileName.text());
+                    msg.push_str(&format!("  {}. {}\n", idx + 1, name));
+                }
+                msg.push_str(Msg::SearchHint.text());
+                let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
+            }
+        }
+
+        Command::Mode(mode) => {
+            if check_playlist_empty(state, event_tx) {
                 return;
             }
-            player.resume();
+
+            let mut pl = state.playlist.lock();
+            let mode_name = match mode {
+                PlaybackMode::Sequential => Msg::ModeSequential.text(),
+                PlaybackMode::RepeatOne => Msg::ModeRepeatOne.text(),
+                PlaybackMode::Shuffle => Msg::ModeShuffle.text(),
+                PlaybackMode::Audiobook => Msg::ModeAudiobook.text(),
+            };
+
+            // 检查是否已经是该模式
+            if pl.mode == mode {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::AlreadyInMode, mode_name),
+                    FlashLevel::Info,
+                ));
+                return;
+            }
+
+            pl.mode = mode;
+            drop(pl);
+            state.ui.lock().mode = mode;
+
+            // 进入有声书模式时使用当前文件上次的速度，离开时恢复原速
+            let speed = match player.current_path().map(Path::to_path_buf) {
+                Some(path) => speed_for(state, &path),
+                None => 1.0,
+            };
+            player.set_speed(speed);
+            state.ui.lock().speed = speed;
+            let applied = apply_mode_settings(state, player);
+
+            let mut msg = tr!(Msg::SwitchedMode, mode_name);
+            if mode == PlaybackMode::Audiobook {
+                msg.push_str(&format!(" ({})", format_speed(speed)));
+            }
+            if !applied.is_empty() {
+                msg.push_str(&tr!(Msg::ModeSettingsApplied, applied.join(", ")));
+                if state.ui.lock().playing_ui_active {
+                    let _ = event_tx.send(AppEvent::RefreshUI);
+                }
+            }
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+        }
+
+        Command::ShowMode => {
+            let mode_name = match state.playlist.lock().mode {
+                PlaybackMode::Sequential => Msg::ModeNameSequential.text(),
+                PlaybackMode::RepeatOne => Msg::ModeNameRepeatOne.text(),
+                PlaybackMode::Shuffle => Msg::ModeNameShuffle.text(),
+                PlaybackMode::Audiobook => Msg::ModeNameAudiobook.text(),
+            };
             let _ = event_tx.send(AppEvent::ShowMessage(
-                "继续播放".to_string(),
-                FlashLevel::Ok,
+                tr!(Msg::CurrentMode, mode_name, Msg::ExpectMode.text()),
+                FlashLevel::Info,
             ));
         }
 
-        Command::Volume(v) => {
-            if check_playlist_empty(state, event_tx) {
+        Command::AudiobookMode => {
+            handle_command(
+                state,
+                player,
+                Command::Mode(PlaybackMode::Audiobook),
+                event_tx,
+            );
+        }
+
+        Command::Speed(speed) => {
+            if state.playlist.lock().mode != PlaybackMode::Audiobook {
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::SpeedNeedsAudiobook),
+                    FlashLevel::Error,
+                ));
                 return;
             }
-            if !is_playing(state) {
+
+            // 速度按文件记录，没有正在播放的文件时无处保存
+            let Some(path) = player.current_path().map(Path::to_path_buf) else {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "当前没有播放歌曲，无法调节音量".to_string(),
+                    tr!(Msg::NoCurrentFile),
                     FlashLevel::Error,
                 ));
                 return;
+            };
+
+            player.set_speed(speed);
+            state.ui.lock().speed = speed;
+            let mut audiobook = state.audiobook.lock();
+            audiobook.set_speed(&path, speed);
+            let _ = audiobook.save();
+            drop(audiobook);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::SpeedSet, format_speed(speed)),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Shuffle => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            let mut pl = state.playlist.lock();
+            let original = pl.items.clone();
+            pl.shuffle_order();
+            drop(pl);
+            state.undo.lock().push(UndoAction::Shuffle { original });
+            sync_playing_state(state, event_tx);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::Shuffled), FlashLevel::Ok));
+        }
+
+        Command::Sort(key) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
             }
-            let vol = (v as f32 / 100.0).clamp(0.0, 1.0);
-            player.set_volume(vol);
-            state.ui.lock().volume = Some(v);
+
+            let mut pl = state.playlist.lock();
+            let original = pl.items.clone();
+            pl.sort_by(key);
+            drop(pl);
+            state.undo.lock().push(UndoAction::Sort { original, key });
+            sync_playing_state(state, event_tx);
+
+            let key_name = match key {
+                SortKey::Name => Msg::SortName.text(),
+                SortKey::TrackNumberInferred => Msg::SortTrack.text(),
+            };
             let _ = event_tx.send(AppEvent::ShowMessage(
-                format!("音量设置为: {}%", v),
+                tr!(Msg::Sorted, key_name),
                 FlashLevel::Ok,
             ));
         }
 
-        Command::Lyrics => {
-            if !is_playing(state) {
+        Command::Dedup(strict) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            let mut pl = state.playlist.lock();
+            let original = pl.items.clone();
+            let removed = pl.dedup(strict);
+            drop(pl);
+
+            if removed == 0 {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "当前没有播放歌曲，无法操作歌词显示".to_string(),
-                    FlashLevel::Error,
+                    tr!(Msg::NoDuplicates),
+                    FlashLevel::Info,
                 ));
                 return;
             }
 
-            let mut ui = state.ui.lock();
-            ui.toggle_lyrics();
-            let status = if ui.show_lyrics {
-                "已显示"
+            state
+                .undo
+                .lock()
+                .push(UndoAction::Dedup { original, removed });
+            sync_playing_state(state, event_tx);
+
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::DuplicatesRemoved, removed),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Confirm(yes) => {
+            let mut confirmation = state.confirm.lock();
+            let pending = if yes {
+                confirmation.approve()
             } else {
-                "已隐藏"
+                confirmation.cancel()
             };
-
-            if ui.show_lyrics {
-                if let Some(lyrics) = &ui.lyrics {
-                    if lyrics.lines.is_empty() {
-                        let _ = event_tx.send(AppEvent::ShowMessage(
-                            format!("歌词{}，但歌词文件为空", status),
-                            FlashLevel::Info,
-                        ));
-                    } else {
-                        let _ = event_tx.send(AppEvent::ShowMessage(
-                            format!("歌词{}，已加载 {} 行歌词", status, lyrics.lines.len()),
-                            FlashLevel::Ok,
-                        ));
-                    }
-                } else {
+            drop(confirmation);
+            match pending {
+                Some(cmd) if yes => handle_command(state, player, cmd, event_tx),
+                Some(_) => {
                     let _ = event_tx.send(AppEvent::ShowMessage(
-                        format!("歌词{}，但未找到歌词文件", status),
+                        tr!(Msg::ConfirmCancelled),
                         FlashLevel::Info,
                     ));
                 }
-            } else {
+                None => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::NothingToConfirm),
+                        FlashLevel::Error,
+                    ));
+                }
+            }
+        }
+
+        Command::Clear => {
+            let count = state.playlist.lock().items.len();
+            if count == 0 {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    format!("歌词{}", status),
-                    FlashLevel::Ok,
+                    tr!(Msg::PlaylistAlreadyEmpty),
+                    FlashLevel::Info,
                 ));
+                return;
             }
-            let _ = event_tx.send(AppEvent::RefreshUI);
+            if !confirm(
+                state,
+                Command::Clear,
+                tr!(Msg::ConfirmClear, count),
+                event_tx,
+            ) {
+                return;
+            }
+
+            remember_position(state, player);
+            player.stop();
+            let mut pl = state.playlist.lock();
+            let original = pl.items.clone();
+            let removed = pl.clear();
+            drop(pl);
+            state.undo.lock().push(UndoAction::Clear { original });
+            state.ui.lock().reset_playing();
+
+            let _ = event_tx.send(AppEvent::ShowStartScreen);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::PlaylistCleared, removed),
+                FlashLevel::Ok,
+            ));
         }
 
-        Command::LyricsMode => {
-            if !is_playing(state) {
+        Command::RemoveRange(from, to) => {
+            if check_playlist_empty(state, event_tx) {
+                return;
+            }
+
+            // 在同一次加锁中检查范围并删除，避免文件夹监视在两者之间修改列表；
+            // 从后往前删除，前面歌曲的下标不受影响
+            let mut pl = state.playlist.lock();
+            if let Err(error) = pl.check_range(from, to) {
+                drop(pl);
+                let _ = event_tx.send(AppEvent::ShowMessage(error, FlashLevel::Error));
+                return;
+            }
+            let original = pl.items.clone();
+            let current_removed = pl.current.is_some_and(|cur| (from - 1..to).contains(&cur));
+            let removed = (from - 1..to)
+                .rev()
+                .filter(|&idx| pl.remove(idx).is_some())
+                .count();
+            let remaining = pl.items.len();
+            drop(pl);
+            state
+                .undo
+                .lock()
+                .push(UndoAction::Remove { original, removed });
+
+            let msg = if removed == 1 {
+                tr!(Msg::SongRemoved, from)
+            } else {
+                tr!(Msg::RangeRemoved, from, to, removed)
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Ok));
+
+            // 正在播放的歌曲被删除时接着播放删除范围之后的一首，列表删空时回到初始界面
+            let playing = state.ui.lock().now_index.is_some();
+            if !(current_removed && playing) {
+                sync_playing_state(state, event_tx);
+            } else if remaining == 0 {
+                remember_position(state, player);
+                player.stop();
+                state.ui.lock().reset_playing();
+                let _ = event_tx.send(AppEvent::ShowStartScreen);
+            } else {
+                let next = (from - 1) % remaining;
+                handle_command(state, player, Command::PlayIndex(next + 1), event_tx);
+            }
+        }
+
+        Command::Undo => {
+            let Some(action) = state.undo.lock().pop() else {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "当前没有播放歌曲，无法切换歌词显示模式".to_string(),
-                    FlashLevel::Error,
+                    tr!(Msg::NothingToUndo),
+                    FlashLevel::Info,
                 ));
                 return;
-            }
+            };
+            let description = action.description();
+            action.restore(&mut state.playlist.lock());
+            sync_playing_state(state, event_tx);
 
-            let mut ui = state.ui.lock();
-            ui.toggle_lyrics_mode();
-            let mode_name = if ui.lyrics_stream_mode {
-                "流式输出"
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::UndoDone, description),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Add(path) => {
+            let target = resolve_path(&path);
+            let options = state.playlist.lock().scan_folder_options.clone();
+            let entries = if target.is_dir() {
+                let (files, summary) = collect_audio_files(&target, usize::MAX, &options);
+                if let Some(limit) = summary.stopped {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        scan_stopped_message(limit, summary.added),
+                        FlashLevel::Error,
+                    ));
+                }
+                files
+            } else if target.is_file() && options.is_audio(&target) {
+                vec![target.clone()]
             } else {
-                "清屏刷新"
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::NotPlayable, target.display()),
+                    FlashLevel::Error,
+                ));
+                return;
             };
 
+            let mut pl = state.playlist.lock();
+            let (added, skipped) = pl.merge(entries);
+            let total = pl.items.len();
+            drop(pl);
+
+            sync_playing_state(state, event_tx);
             let _ = event_tx.send(AppEvent::ShowMessage(
-                format!("歌词显示模式已切换为: {}", mode_name),
+                tr!(Msg::AddDone, added, skipped, total),
+                FlashLevel::Ok,
+            ));
+        }
+
+        Command::Merge(path) => {
+            let file_path = resolve_path(&path);
+            let options = state.playlist.lock().scan_folder_options.clone();
+            let entries = match load_playlist_file(&file_path, &options) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::PlaylistReadFailed, file_path.display(), e),
+                        FlashLevel::Error,
+                    ));
+                    return;
+                }
+            };
+
+            let mut pl = state.playlist.lock();
+            let (added, skipped) = pl.merge(entries);
+            let total = pl.items.len();
+            drop(pl);
+
+            sync_playing_state(state, event_tx);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::MergeDone, added, skipped, total),
                 FlashLevel::Ok,
             ));
-            let _ = event_tx.send(AppEvent::RefreshUI);
         }
 
-        Command::Now => {
+        Command::CopyPlaylist(path, flags) => {
             if check_playlist_empty(state, event_tx) {
                 return;
             }
-            show_now_playing(state, event_tx);
+
+            // 复制到 U 盘等较慢的设备可能需要很久，在单独的线程中进行，播放和其他命令不受影响
+            let dest = resolve_path(&path);
+            let files = copy::audio_files(&state.playlist.lock().items);
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::CopyProgress, 0, files.len()),
+                FlashLevel::Info,
+            ));
+            let ui_tx = state.ui_tx.clone();
+            let handle = spawn_named("copy", move || copy_playlist(&files, &dest, flags, &ui_tx));
+            let mut background = state.background.lock();
+            background.retain(|h| !h.is_finished());
+            background.push(handle);
         }
 
-        Command::Search(query) => {
+        Command::Snapshot => {
             if check_playlist_empty(state, event_tx) {
                 return;
             }
 
-            let pl = state.playlist.lock();
-            let results = pl.search(&query);
-            drop(pl);
+            let snapshot = Snapshot::from_files(&state.playlist.lock().items);
+            match snapshot.save() {
+                Ok(path) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::SnapshotSaved, snapshot.files.len(), path.display()),
+                        FlashLevel::Ok,
+                    ));
+                }
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::SnapshotSaveFailed, e),
+                        FlashLevel::Error,
+                    ));
+                }
+            }
+        }
 
-            if results.is_empty() {
+        Command::Diff => {
+            let Some(folder) = state.playlist.lock().folder.clone() else {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    format!("没有找到包含 '{}' 的歌曲", query),
-                    FlashLevel::Info,
+                    tr!(Msg::NoFolderScanned),
+                    FlashLevel::Error,
                 ));
-            } else {
-                let mut msg = format!("搜索 '{}' 的结果：\n", query);
-                for (idx, path) in results {
+                return;
+            };
+            let saved = match Snapshot::load() {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    let _ = event_tx.send(AppEvent::ShowMessage(
+                        tr!(Msg::SnapshotReadFailed, e),
+                        FlashLevel::Error,
+                    ));
+                    return;
+                }
+            };
+
+            let (depth, options) = {
+                let pl = state.playlist.lock();
+                (pl.scan_depth, pl.rescan_options())
+            };
+            let (files, summary) = collect_audio_files(&folder, depth, &options);
+            if let Some(limit) = summary.stopped {
+                // 扫描不完整时比较结果会把未扫描到的歌曲误报为删除
+                let _ = event_tx.send(AppEvent::ShowMessage(
+                    tr!(Msg::DiffScanStopped, limit),
+                    FlashLevel::Error,
+                ));
+                return;
+            }
+            let diff = saved.diff(&Snapshot::from_files(&files));
+            if diff.total() == 0 {
+                let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::DiffNone), FlashLevel::Info));
+                return;
+            }
+
+            let mut msg = tr!(Msg::DiffHeader, diff.total());
+            for (label, paths) in [
+                (Msg::DiffAdded.text(), &diff.added),
+                (Msg::DiffRemoved.text(), &diff.removed),
+                (Msg::DiffModified.text(), &diff.modified),
+            ] {
+                msg.push_str(&tr!(Msg::DiffCount, label, paths.len()));
+                for path in paths.iter().take(DIFF_LIST_LIMIT) {
                     let name = path
                         .file_name()
                         .and_then(|s| s.to_str())
-                        .unwrap_or("未知文件名");
-                    msg.push_str(&format!("  {}. {}\n", idx + 1, name));
+                        .unwrap_or(Msg::UnknownFileName.text());
+                    msg.push_str(&format!("    {}\n", name));
+                }
+                if paths.len() > DIFF_LIST_LIMIT {
+                    msg.push_str(&tr!(Msg::DiffMore, paths.len()));
+                }
+            }
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
+        }
+
+        Command::AliasList => {
+            let config = state.config.lock();
+            if config.aliases.is_empty() {
+                let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::NoAliases), FlashLevel::Info));
+            } else {
+                let mut msg = tr!(Msg::AliasHeader);
+                for (name, target) in &config.aliases {
+                    msg.push_str(&format!("  /{} -> {}\n", name, target));
                 }
-                msg.push_str("\n使用 /play <N> 播放指定歌曲");
                 let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
             }
         }
 
-        Command::Mode(mode) => {
-            if check_playlist_empty(state, event_tx) {
+        Command::AliasSet(name, target) => {
+            let mut config = state.config.lock();
+            if let Err(e) = validate_alias(&name, &target, &config.aliases) {
+                let _ = event_tx.send(AppEvent::ShowMessage(e, FlashLevel::Error));
                 return;
             }
-
-            let mut pl = state.playlist.lock();
-            let mode_name = match mode {
-                PlaybackMode::Sequential => "顺序播放模式",
-                PlaybackMode::RepeatOne => "单曲循环模式",
-                PlaybackMode::Shuffle => "随机播放模式",
+            config.aliases.insert(name.clone(), target.clone());
+            let msg = match config.save() {
+                Ok(()) => (tr!(Msg::AliasSet, name, target), FlashLevel::Ok),
+                Err(e) => (tr!(Msg::AliasSaveFailed, name, e), FlashLevel::Error),
             };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.0, msg.1));
+        }
 
-            // 检查是否已经是该模式
-            if pl.mode == mode {
+        Command::AliasRemove(name) => {
+            let mut config = state.config.lock();
+            if config.aliases.remove(&name).is_none() {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    format!("已经是{}", mode_name),
-                    FlashLevel::Info,
+                    tr!(Msg::AliasNotFound, name),
+                    FlashLevel::Error,
                 ));
                 return;
             }
-
-            pl.mode = mode;
-            state.ui.lock().mode = mode;
-            drop(pl);
-
-            let _ = event_tx.send(AppEvent::ShowMessage(
-                format!("已切换到{}", mode_name),
-                FlashLevel::Ok,
-            ));
+            let msg = match config.save() {
+                Ok(()) => (tr!(Msg::AliasRemoved, name), FlashLevel::Ok),
+                Err(e) => (tr!(Msg::AliasRemoveSaveFailed, name, e), FlashLevel::Error),
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg.0, msg.1));
         }
 
-        Command::Quit => {
-            // Quit 已在 audio_thread 中处理
+        Command::ConfigShow => {
+            let path = Config::path()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| tr!(Msg::Unknown));
+            let msg = match toml::to_string_pretty(&*state.config.lock()) {
+                Ok(content) => tr!(Msg::ConfigCurrent, path, content.trim_end()),
+                Err(e) => tr!(Msg::ConfigShowFailed, e),
+            };
+            let _ = event_tx.send(AppEvent::ShowMessage(msg, FlashLevel::Info));
         }
 
-        Command::Unknown(s) => {
-            let _ = event_tx.send(AppEvent::ShowMessage(
-                format!("未知命令: {}\n输入 /help 查看帮助。", s),
-                FlashLevel::Error,
-            ));
+        Command::Quit | Command::Source(_) | Command::Sleep(_) | Command::Noop => {
+            // Quit 已在 audio_thread 中处理，Source 和 Sleep 由输入线程执行，Noop 无需处理
         }
     }
 }
@@ -591,7 +2622,7 @@ fn check_playlist_empty(state: &AppState, event_tx: &Sender<AppEvent>) -> bool {
     let pl = state.playlist.lock();
     if pl.items.is_empty() {
         let _ = event_tx.send(AppEvent::ShowMessage(
-            "播放列表为空，请先使用 /folder 添加歌曲".to_string(),
+            tr!(Msg::PlaylistEmpty),
             FlashLevel::Error,
         ));
         true
@@ -604,26 +2635,390 @@ fn is_playing(state: &AppState) -> bool {
     state.playlist.lock().current.is_some()
 }
 
+// 播放列表变化后同步界面上的“下一首”，正在播放的歌曲不受影响
+fn sync_playing_state(state: &AppState, event_tx: &Sender<AppEvent>) {
+    let mut pl = state.playlist.lock();
+    let current = pl.current;
+    let next = pl.peek_next_name();
+    drop(pl);
+
+    let ui = state.ui.lock();
+    if let (Some(idx), Some(_)) = (current, ui.now_index) {
+        let now_name = ui.now_name.clone();
+        drop(ui);
+        let _ = event_tx.send(AppEvent::UpdatePlayingState(idx, now_name, next));
+        // 列表面板中的顺序随之变化
+        let _ = event_tx.send(AppEvent::RefreshUI);
+    }
+}
+
+// 破坏性命令执行前确认，返回是否继续执行。交互输入时记下命令并提示输入 y 确认，返回 false，
+// 确认后重新执行该命令时返回 true；脚本和管道输入中 --yes 时直接执行，否则报错
+fn confirm(state: &AppState, cmd: Command, what: String, event_tx: &Sender<AppEvent>) -> bool {
+    let batch = !io::stdin().is_terminal() || state.scripts_running.load(Ordering::SeqCst) > 0;
+    let (message, level) = match state.confirm.lock().decide(cmd, batch, state.assume_yes) {
+        Decision::Run => return true,
+        Decision::Ask => (tr!(Msg::ConfirmPrompt, what), FlashLevel::Info),
+        Decision::Refuse => (tr!(Msg::ConfirmBatch, what), FlashLevel::Error),
+    };
+    let _ = event_tx.send(AppEvent::ShowMessage(message, level));
+    false
+}
+
+// 按监视开关为当前文件夹重新创建监视器；旧的监视器先停止，其后台线程随之退出
+fn restart_watcher(state: &AppState, event_tx: &Sender<AppEvent>) {
+    let mut watcher = state.watcher.lock();
+    *watcher = None;
+    if !state.config.lock().scanning.watch {
+        return;
+    }
+    let Some(folder) = state.playlist.lock().folder.clone() else {
+        return;
+    };
+    let tx = state.ui_tx.clone();
+    match FolderWatcher::start(&folder, move |paths| {
+        let _ = tx.send(AppEvent::FolderChanged(paths));
+    }) {
+        Ok(w) => *watcher = Some(w),
+        Err(e) => {
+            let _ = event_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::WatchFailed, folder.display(), e),
+                FlashLevel::Error,
+            ));
+        }
+    }
+}
+
+// 换歌前加载章节，并按播放模式设置速度：有声书模式使用该文件上次的速度，其他模式为原速
+fn apply_audiobook_state(state: &AppState, player: &mut Player, path: &Path) {
+    let speed = speed_for(state, path);
+    player.set_speed(speed);
+    let mut ui = state.ui.lock();
+    ui.speed = speed;
+    ui.chapters = load_chapters(path);
+    ui.current_chapter = None;
+}
+
+// 创建带名称的线程，日志中按名称区分
+fn spawn_named<F>(name: &str, f: F) -> thread::JoinHandle<()>
+where
+    F: FnOnce() + Send + 'static,
+{
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(f)
+        .expect("spawn thread")
+}
+
+// /copyto 的复制线程：复制过程中显示进度，结束后显示结果
+fn copy_playlist(files: &[PathBuf], dest: &Path, flags: CopyFlags, ui_tx: &Sender<AppEvent>) {
+    // 最多每 200 毫秒显示一次进度
+    let mut last_progress = Instant::now();
+    let result = copy::copy_files(files, dest, flags, |done, total| {
+        if last_progress.elapsed() >= Duration::from_millis(200) {
+            last_progress = Instant::now();
+            let _ = ui_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::CopyProgress, done, total),
+                FlashLevel::Info,
+            ));
+        }
+    });
+    let summary = match result {
+        Ok(summary) => summary,
+        Err(e) => {
+            let _ = ui_tx.send(AppEvent::ShowMessage(
+                tr!(Msg::CopyFailed, dest.display(), e),
+                FlashLevel::Error,
+            ));
+            return;
+        }
+    };
+    let mut msg = if flags.symlink {
+        tr!(Msg::LinkDone, summary.copied, dest.display())
+    } else {
+        tr!(Msg::CopyDone, summary.copied, dest.display())
+    };
+    if flags.lrc {
+        msg.push_str(&tr!(Msg::CopyLyrics, summary.lyrics));
+    }
+    if let Some(first) = summary.skipped.first() {
+        let name = first.file_name().unwrap_or_default().to_string_lossy();
+        msg.push_str(&tr!(Msg::CopySkipped, summary.skipped.len(), name));
+    }
+    if summary.failed > 0 {
+        msg.push_str(&tr!(Msg::CopyErrors, summary.failed));
+    }
+    let level = if summary.skipped.is_empty() && summary.failed == 0 {
+        FlashLevel::Ok
+    } else {
+        FlashLevel::Info
+    };
+    let _ = ui_tx.send(AppEvent::ShowMessage(msg, level));
+}
+
+// 歌曲结束（或交叉淡化开始）时播放下一首，crossfade 为两首歌重叠的时长。
+// 没有下一首时返回 false
+fn advance_track(
+    state: &AppState,
+    player: &mut Player,
+    event_tx: &Sender<AppEvent>,
+    crossfade: Option<Duration>,
+) -> bool {
+    forget_position(state, player);
+    let mut pl = state.playlist.lock();
+    let Some((next_idx, path)) = pl.step_finished() else {
+        log::info!("end of playlist");
+        return false;
+    };
+    drop(pl);
+    log::debug!("advancing to #{} (crossfade {:?})", next_idx + 1, crossfade);
+
+    apply_audiobook_state(state, player, &path);
+    apply_mode_settings(state, player);
+    apply_replaygain(state, &path);
+    match crossfade {
+        Some(overlap) => {
+            let _ = player.crossfade_to(&path, overlap);
+        }
+        None => player.play_file(&path),
+    }
+    player.set_volume(state.ui.lock().output_volume());
+
+    let name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("")
+        .to_string();
+    let next_name = state.playlist.lock().peek_next_name();
+    let lyrics = state.lyrics.lock().get(&path);
+
+    // 发送UI更新事件
+    let _ = event_tx.send(AppEvent::UpdatePlayingState(next_idx, name, next_name));
+    let _ = event_tx.send(AppEvent::UpdateLyrics(lyrics));
+    let _ = event_tx.send(AppEvent::RefreshUI);
+    true
+}
+
+// 换歌前按 ReplayGain 模式读取增益，之后设置音量时乘以该增益
+fn apply_replaygain(state: &AppState, path: &Path) {
+    let config = state.config.lock();
+    let (mode, preamp) = (
+        config.playback.replaygain_mode,
+        config.playback.replaygain_preamp,
+    );
+    drop(config);
+    state.ui.lock().replay_gain = gain_db(mode, &cue::audio_file(path)).map(|db| db + preamp);
+}
+
+// 应用当前播放模式在 [modes.*] 中设置的音量和淡入淡出，已应用过同一模式时不做改动（保留手动调整）。
+// 上一个模式覆盖过而当前模式没有设置的项恢复为 [playback] 中的值。返回改动了的设置，用于提示
+fn apply_mode_settings(state: &AppState, player: &mut Player) -> Vec<String> {
+    let mode = state.playlist.lock().mode;
+    let previous = state.ui.lock().mode_settings.replace(mode);
+    if previous == Some(mode) {
+        return Vec::new();
+    }
+    let config = state.config.lock();
+    let settings = config.modes.get(mode).clone();
+    let old = previous
+        .map(|m| config.modes.get(m).clone())
+        .unwrap_or_default();
+    let playback = config.playback.clone();
+    drop(config);
+
+    // 当前模式设置了的项使用它的值，否则上一个模式设置过时恢复默认值
+    let mut applied = Vec::new();
+    if let Some(v) = settings.volume.or(old.volume.map(|_| playback.volume)) {
+        let mut ui = state.ui.lock();
+        ui.volume = Some(v);
+        player.set_volume(ui.output_volume());
+        applied.push(tr!(Msg::ModeVolume, v));
+    }
+    if let Some(ms) = settings
+        .fade_in_ms
+        .or(old.fade_in_ms.map(|_| playback.fade_in_ms))
+    {
+        player.fade_in_duration = Duration::from_millis(ms);
+        applied.push(tr!(Msg::ModeFadeIn, ms));
+    }
+    if let Some(ms) = settings
+        .fade_out_ms
+        .or(old.fade_out_ms.map(|_| playback.fade_out_ms))
+    {
+        player.fade_out_duration = Duration::from_millis(ms);
+        applied.push(tr!(Msg::ModeFadeOut, ms));
+    }
+    applied
+}
+
+fn speed_for(state: &AppState, path: &Path) -> f32 {
+    if state.playlist.lock().mode != PlaybackMode::Audiobook {
+        return 1.0;
+    }
+    let default = state.config.lock().playback.audiobook_speed;
+    state.audiobook.lock().speed(path).unwrap_or(default)
+}
+
+// 有声书模式下 /next、/prev 在章节间跳转，返回是否已跳转。
+// 没有章节、已在最后一章（向后）或在第一章开头（向前）时返回 false，由调用者切换歌曲
+fn jump_chapter(
+    state: &AppState,
+    player: &mut Player,
+    forward: bool,
+    event_tx: &Sender<AppEvent>,
+) -> bool {
+    if state.playlist.lock().mode != PlaybackMode::Audiobook {
+        return false;
+    }
+    let Some(path) = player.current_path().map(Path::to_path_buf) else {
+        return false;
+    };
+    let ui = state.ui.lock();
+    if ui.chapters.is_empty() {
+        return false;
+    }
+    let position = Duration::from_millis(player.get_current_ms() as u64);
+    let current = chapter_index(&ui.chapters, position);
+    let target = if forward {
+        current.map_or(0, |i| i + 1)
+    } else {
+        // 本章已播放超过 3 秒时回到本章开头，否则跳到上一章
+        match current {
+            Some(i) if position >= ui.chapters[i].start + Duration::from_secs(3) => i,
+            Some(i) if i > 0 => i - 1,
+            _ => return false,
+        }
+    };
+    let Some(chapter) = ui.chapters.get(target).cloned() else {
+        return false;
+    };
+    let total = ui.chapters.len();
+    drop(ui);
+
+    player.play_file_at(&path, chapter.start);
+    player.set_volume(state.ui.lock().output_volume());
+    let mut ui = state.ui.lock();
+    ui.current_chapter = Some(target);
+    ui.play_state = player.state();
+    drop(ui);
+    let _ = event_tx.send(AppEvent::ShowMessage(
+        tr!(Msg::ChapterJump, target + 1, total, chapter.title),
+        FlashLevel::Ok,
+    ));
+    true
+}
+
+// 记录正在播放的歌曲的位置，下次播放时可以从这里继续
+fn remember_position(state: &AppState, player: &Player) {
+    let Some(path) = player.current_path() else {
+        return;
+    };
+    if player.finished() {
+        return;
+    }
+    let mut history = state.history.lock();
+    history.set_position(
+        path,
+        player.get_current_ms() as u64,
+        player.total_duration(),
+    );
+    let _ = history.save();
+}
+
+// 歌曲播完或被跳过时清除保存的位置
+fn forget_position(state: &AppState, player: &Player) {
+    let Some(path) = player.current_path() else {
+        return;
+    };
+    let mut history = state.history.lock();
+    if history.position(path).is_some() {
+        history.clear_position(path);
+        let _ = history.save();
+    }
+}
+
+// 跳转到当前歌曲的指定位置。超过歌曲末尾时歌曲立即结束，
+// 由播放线程按播放模式切换到下一首
+fn seek(state: &AppState, player: &mut Player, position: Duration, event_tx: &Sender<AppEvent>) {
+    if player.current_path().is_none() {
+        let _ = event_tx.send(AppEvent::ShowMessage(
+            tr!(Msg::NothingPlaying),
+            FlashLevel::Error,
+        ));
+        return;
+    }
+    let position = match player.total_duration() {
+        Some(total) => position.min(total),
+        None => position,
+    };
+    if let Err(e) = player.seek(position) {
+        let _ = event_tx.send(AppEvent::ShowMessage(e.to_string(), FlashLevel::Error));
+        return;
+    }
+
+    // 歌词高亮和流式歌词的显示范围需要立即按新位置重新计算
+    let mut ui = state.ui.lock();
+    ui.current_ms = position.as_millis();
+    ui.current_lyric_line = None;
+    drop(ui);
+    let _ = event_tx.send(AppEvent::ShowMessage(
+        tr!(Msg::SeekTo, format_time(position)),
+        FlashLevel::Ok,
+    ));
+    let _ = event_tx.send(AppEvent::RefreshUI);
+}
+
+// 音量随时可以调节：没有播放时只记录下来，下次播放时生效
+fn set_volume(state: &AppState, player: &mut Player, v: u8, event_tx: &Sender<AppEvent>) {
+    let mut ui = state.ui.lock();
+    ui.volume = Some(v);
+    player.set_volume(ui.output_volume());
+    let playing_ui = ui.playing_ui_active;
+    drop(ui);
+    // 先更新播放界面中的音量条（流式模式下只重写这一行），再显示提示
+    if playing_ui {
+        let _ = event_tx.send(AppEvent::RefreshUI);
+    }
+    let _ = event_tx.send(AppEvent::ShowMessage(
+        tr!(Msg::VolumeSet, volume_bar(v, ui_width()), v),
+        FlashLevel::Ok,
+    ));
+}
+
 fn play_song(state: &AppState, player: &mut Player, i: usize, event_tx: &Sender<AppEvent>) {
     let path_opt = state.playlist.lock().get(i).cloned();
     if let Some(path) = path_opt {
-        if !path.exists() {
+        if !cue::audio_file(&path).exists() {
             let name = path
                 .file_name()
                 .and_then(|s| s.to_str())
-                .unwrap_or("未知文件");
+                .unwrap_or(Msg::UnknownFileName.text());
             let _ = event_tx.send(AppEvent::ShowMessage(
-                format!("歌曲文件不存在: {}", name),
+                tr!(Msg::SongMissing, name),
                 FlashLevel::Error,
             ));
             return;
         }
 
-        state.playlist.lock().current = Some(i);
-        player.play_file(&path);
-
-        let vol = state.ui.lock().volume.unwrap_or(50) as f32 / 100.0;
-        player.set_volume(vol);
+        // 手动切换歌曲时记下正在播放的歌曲的位置
+        remember_position(state, player);
+        let resume_ms = state.history.lock().position(&path);
+        // 有声书模式总是从上次位置继续
+        let auto_resume = state.config.lock().playback.auto_resume_position
+            || state.playlist.lock().mode == PlaybackMode::Audiobook;
+        let start = match resume_ms {
+            Some(ms) if auto_resume => Duration::from_millis(ms),
+            _ => Duration::ZERO,
+        };
+
+        // 读取路径之后列表可能已被修改，按路径重新确定下标
+        let i = state.playlist.lock().set_current(i, &path).unwrap_or(i);
+        apply_audiobook_state(state, player, &path);
+        apply_mode_settings(state, player);
+        apply_replaygain(state, &path);
+        player.play_file_at(&path, start);
+
+        player.set_volume(state.ui.lock().output_volume());
 
         let name = path
             .file_name()
@@ -631,39 +3026,51 @@ fn play_song(state: &AppState, player: &mut Player, i: usize, event_tx: &Sender<
             .unwrap_or("")
             .to_string();
         let next = state.playlist.lock().peek_next_name();
-        let lyrics = Lyrics::load_from_path(&path);
+        let lyrics = state.lyrics.lock().get(&path);
 
         // 发送更新事件
         let _ = event_tx.send(AppEvent::UpdatePlayingState(i, name.clone(), next));
         let _ = event_tx.send(AppEvent::UpdateLyrics(lyrics.clone()));
 
-        let mut flash_msg = format!("开始播放: {}", name);
+        let mut flash_msg = tr!(Msg::NowPlaying, name);
         if lyrics.is_some() {
-            flash_msg.push_str(" | 已加载歌词");
+            flash_msg.push_str(Msg::LyricsLoadedTag.text());
+        }
+        if let Some(ms) = resume_ms {
+            let position = format_time(Duration::from_millis(ms));
+            if auto_resume {
+                flash_msg.push_str(&tr!(Msg::ResumingFrom, position));
+            } else {
+                flash_msg.push_str(&tr!(Msg::ResumePrompt, position));
+            }
         }
         let _ = event_tx.send(AppEvent::ShowMessage(flash_msg, FlashLevel::Ok));
     }
 }
 
 fn next_song(state: &AppState, player: &mut Player, event_tx: &Sender<AppEvent>) {
+    if jump_chapter(state, player, true, event_tx) {
+        return;
+    }
     let mut pl = state.playlist.lock();
 
     if pl.items.len() == 1 {
         let _ = event_tx.send(AppEvent::ShowMessage(
-            "只有一首歌曲，无法切换到下一首".to_string(),
+            tr!(Msg::OnlyOneSongNext),
             FlashLevel::Info,
         ));
         return;
     }
 
-    if let Some(next_idx) = pl.next_index() {
-        let path = pl.get(next_idx).cloned().unwrap();
-        pl.current = Some(next_idx);
+    if let Some((next_idx, path)) = pl.step_next() {
         drop(pl);
 
+        forget_position(state, player);
+        apply_audiobook_state(state, player, &path);
+        apply_mode_settings(state, player);
+        apply_replaygain(state, &path);
         player.play_file(&path);
-        let vol = state.ui.lock().volume.unwrap_or(50) as f32 / 100.0;
-        player.set_volume(vol);
+        player.set_volume(state.ui.lock().output_volume());
 
         let name = path
             .file_name()
@@ -671,26 +3078,24 @@ fn next_song(state: &AppState, player: &mut Player, event_tx: &Sender<AppEvent>)
             .unwrap_or("")
             .to_string();
         let next = state.playlist.lock().peek_next_name();
-        let lyrics = Lyrics::load_from_path(&path);
+        let lyrics = state.lyrics.lock().get(&path);
 
         let _ = event_tx.send(AppEvent::UpdatePlayingState(next_idx, name.clone(), next));
         let _ = event_tx.send(AppEvent::UpdateLyrics(lyrics));
         let _ = event_tx.send(AppEvent::ShowMessage(
-            format!("已切换到下一首: {}", name),
+            tr!(Msg::SwitchedNext, name),
             FlashLevel::Ok,
         ));
     } else {
-        let mode = state.playlist.lock().mode;
+        let mode = pl.mode;
+        drop(pl);
         match mode {
             PlaybackMode::Sequential => {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    "已经是最后一首，顺序播放模式下不循环".to_string(),
-                    FlashLevel::Info,
-                ));
+                let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::LastSong), FlashLevel::Info));
             }
             _ => {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "无法获取下一首歌曲".to_string(),
+                    tr!(Msg::NoNextSong),
                     FlashLevel::Error,
                 ));
             }
@@ -699,24 +3104,28 @@ fn next_song(state: &AppState, player: &mut Player, event_tx: &Sender<AppEvent>)
 }
 
 fn prev_song(state: &AppState, player: &mut Player, event_tx: &Sender<AppEvent>) {
-    let pl = state.playlist.lock();
+    if jump_chapter(state, player, false, event_tx) {
+        return;
+    }
+    let mut pl = state.playlist.lock();
 
     if pl.items.len() == 1 {
         let _ = event_tx.send(AppEvent::ShowMessage(
-            "只有一首歌曲，无法切换到上一首".to_string(),
+            tr!(Msg::OnlyOneSongPrev),
             FlashLevel::Info,
         ));
         return;
     }
 
-    if let Some(prev_idx) = pl.prev_index() {
-        let path = pl.get(prev_idx).cloned().unwrap();
+    if let Some((prev_idx, path)) = pl.step_prev() {
         drop(pl);
-        state.playlist.lock().current = Some(prev_idx);
+        forget_position(state, player);
+        apply_audiobook_state(state, player, &path);
+        apply_mode_settings(state, player);
+        apply_replaygain(state, &path);
         player.play_file(&path);
 
-        let vol = state.ui.lock().volume.unwrap_or(50) as f32 / 100.0;
-        player.set_volume(vol);
+        player.set_volume(state.ui.lock().output_volume());
 
         let name = path
             .file_name()
@@ -724,26 +3133,24 @@ fn prev_song(state: &AppState, player: &mut Player, event_tx: &Sender<AppEvent>)
             .unwrap_or("")
             .to_string();
         let next = state.playlist.lock().peek_next_name();
-        let lyrics = Lyrics::load_from_path(&path);
+        let lyrics = state.lyrics.lock().get(&path);
 
         let _ = event_tx.send(AppEvent::UpdatePlayingState(prev_idx, name.clone(), next));
         let _ = event_tx.send(AppEvent::UpdateLyrics(lyrics));
         let _ = event_tx.send(AppEvent::ShowMessage(
-            format!("已切换到上一首: {}", name),
+            tr!(Msg::SwitchedPrev, name),
             FlashLevel::Ok,
         ));
     } else {
-        let mode = state.playlist.lock().mode;
+        let mode = pl.mode;
+        drop(pl);
         match mode {
-            PlaybackMode::Sequential => {
-                let _ = event_tx.send(AppEvent::ShowMessage(
-                    "已经是第一首，顺序播放模式下不循环".to_string(),
-                    FlashLevel::Info,
-                ));
+            PlaybackMode::Sequential | PlaybackMode::RepeatOne | PlaybackMode::Audiobook => {
+                let _ = event_tx.send(AppEvent::ShowMessage(tr!(Msg::FirstSong), FlashLevel::Info));
             }
             _ => {
                 let _ = event_tx.send(AppEvent::ShowMessage(
-                    "无法获取上一首歌曲".to_string(),
+                    tr!(Msg::NoPrevSong),
                     FlashLevel::Error,
                 ));
             }
@@ -758,78 +3165,76 @@ fn show_now_playing(state: &AppState, event_tx: &Sender<AppEvent>) {
     if let Some(current_idx) = pl.current {
         let mut info = String::new();
 
-        info.push_str(&"═".repeat(60));
-        info.push_str("\n");
-        info.push_str(&format!("{:^60}\n", "🎵 当前播放信息"));
-        info.push_str(&"═".repeat(60));
+        info.push_str(&border_line());
+        info.push('\n');
+        info.push_str(&center_line(Msg::NowTitle.text()));
+        info.push('\n');
+        info.push_str(&border_line());
         info.push_str("\n\n");
 
-        info.push_str(&"─".repeat(20));
-        info.push_str(" 基本信息 ");
-        info.push_str(&"─".repeat(19));
-        info.push_str("\n");
+        info.push_str(&divider(Msg::NowBasics.text()));
+        info.push('\n');
 
-        info.push_str(&format!("  歌曲: {}\n", ui.now_name));
-        info.push_str(&format!(
-            "  序号: {} / {}\n",
-            current_idx + 1,
-            pl.items.len()
-        ));
-        info.push_str(&format!(
-            "  模式: {}\n",
+        info.push_str(&tr!(Msg::NowSong, ui.now_name));
+        info.push_str(&tr!(Msg::NowState, ui.play_state.label()));
+        info.push_str(&tr!(Msg::NowIndex, current_idx + 1, pl.items.len()));
+        info.push_str(&tr!(
+            Msg::NowMode,
             match ui.mode {
-                PlaybackMode::Sequential => "顺序播放",
-                PlaybackMode::RepeatOne => "单曲循环",
-                PlaybackMode::Shuffle => "随机播放",
+                PlaybackMode::Sequential => Msg::ModeNameSequential.text(),
+                PlaybackMode::RepeatOne => Msg::ModeNameRepeatOne.text(),
+                PlaybackMode::Shuffle => Msg::ModeNameShuffle.text(),
+                PlaybackMode::Audiobook => Msg::ModeNameAudiobook.text(),
             }
         ));
-        info.push_str(&format!("  音量: {}%\n", ui.volume.unwrap_or(50)));
+        info.push_str(&tr!(Msg::NowVolume, ui.volume_text()));
 
         let current_ms = ui.current_ms;
         let minutes = current_ms / 60_000;
         let seconds = (current_ms % 60_000) / 1000;
-        info.push_str(&format!("  播放时间: {:02}:{:02}\n\n", minutes, seconds));
+        info.push_str(&tr!(
+            Msg::NowPosition,
+            format!("{:02}:{:02}", minutes, seconds)
+        ));
 
-        info.push_str(&"─".repeat(20));
-        info.push_str(" 歌词信息 ");
-        info.push_str(&"─".repeat(19));
-        info.push_str("\n");
+        info.push_str(&divider(Msg::NowLyricsSection.text()));
+        info.push('\n');
 
         if ui.show_lyrics {
             if let Some(lyrics) = &ui.lyrics {
-                if !lyrics.lines.is_empty() {
-                    info.push_str(&format!("  歌词: 已加载 ({} 行)\n\n", lyrics.lines.len()));
+                if !lyrics.is_empty() {
+                    info.push_str(&tr!(Msg::NowLyricsLoaded, lyrics.len()));
 
-                    info.push_str(&"─".repeat(20));
-                    info.push_str(" 当前歌词 ");
-                    info.push_str(&"─".repeat(19));
-                    info.push_str("\n");
+                    info.push_str(&divider(Msg::NowCurrentLyrics.text()));
+                    info.push('\n');
 
+                    // 前奏中显示开头几行，不标记当前行
                     let current_idx = lyrics.current_line_index(current_ms);
+                    let active = lyrics.active_line(current_ms);
                     let start = current_idx.saturating_sub(2);
                     let end = (current_idx + 3).min(lyrics.lines.len());
 
                     for i in start..end {
                         let (_, ref text) = lyrics.lines[i];
-                        if i == current_idx {
+                        if active == Some(i) {
                             info.push_str(&format!("  ▶ {}\n", text));
                         } else {
                             info.push_str(&format!("    {}\n", text));
                         }
                     }
                 } else {
-                    info.push_str("  歌词: 文件为空\n");
+                    info.push_str(Msg::NowLyricsEmpty.text());
                 }
             } else {
-                info.push_str("  歌词: 未找到歌词文件\n");
+                info.push_str(Msg::NowLyricsMissing.text());
             }
         } else {
-            info.push_str("  歌词: 已关闭\n");
+            info.push_str(Msg::NowLyricsOff.text());
         }
 
-        info.push_str("\n");
-        info.push_str(&"═".repeat(60));
-        info.push_str("\n");
+        info.push('\n');
+        info.push_str(&border_line());
+        info.push('\n');
 
         drop(ui);
         drop(pl);
@@ -837,55 +3242,333 @@ fn show_now_playing(state: &AppState, event_tx: &Sender<AppEvent>) {
     } else {
         // 简单提示，不显示复杂框架
         let _ = event_tx.send(AppEvent::ShowMessage(
-            "当前没有播放歌曲，使用 /play 开始播放".to_string(),
+            tr!(Msg::NothingPlayingHint),
+            FlashLevel::Info,
+        ));
+    }
+}
+
+// 当前歌曲的技术信息：文件、音频属性和全部标签。播放中显示正在播放的文件
+// （列表可能已被排序或修改），停止后显示列表中的当前歌曲
+fn show_track_info(state: &AppState, player: &Player, event_tx: &Sender<AppEvent>) {
+    let path = player.current_path().map(Path::to_path_buf).or_else(|| {
+        let pl = state.playlist.lock();
+        pl.current.and_then(|i| pl.get(i)).cloned()
+    });
+    let Some(path) = path else {
+        let _ = event_tx.send(AppEvent::ShowMessage(
+            tr!(Msg::NothingPlayingHint),
             FlashLevel::Info,
         ));
+        return;
+    };
+
+    let info = TrackInfo::read(&path);
+    let unknown = || Msg::InfoUnknown.text().to_string();
+    let mut text = String::new();
+    text.push_str(&border_line());
+    text.push('\n');
+    text.push_str(&center_line(Msg::InfoTitle.text()));
+    text.push('\n');
+    text.push_str(&border_line());
+    text.push_str("\n\n");
+
+    text.push_str(&divider(Msg::InfoFile.text()));
+    text.push('\n');
+    text.push_str(&tr!(Msg::InfoPath, info.path.display()));
+    text.push_str(&tr!(
+        Msg::InfoSize,
+        info.size.map(format_size).unwrap_or_else(unknown)
+    ));
+    text.push_str(&tr!(Msg::InfoFormat, info.format));
+
+    text.push_str(&divider(Msg::InfoAudio.text()));
+    text.push('\n');
+    text.push_str(&tr!(
+        Msg::InfoSampleRate,
+        info.sample_rate
+            .map(|hz| format!("{} Hz", hz))
+            .unwrap_or_else(unknown)
+    ));
+    text.push_str(&tr!(
+        Msg::InfoChannels,
+        info.channels.map(|n| n.to_string()).unwrap_or_else(unknown)
+    ));
+    text.push_str(&tr!(
+        Msg::InfoBitrate,
+        info.bitrate
+            .map(|kbps| format!("{} kbps", kbps))
+            .unwrap_or_else(unknown)
+    ));
+    text.push_str(&tr!(
+        Msg::InfoDuration,
+        info.duration.map(format_time).unwrap_or_else(unknown)
+    ));
+
+    text.push_str(&divider(Msg::InfoTags.text()));
+    text.push('\n');
+    if info.tags.is_empty() {
+        text.push_str(Msg::InfoNoTags.text());
+    }
+    for (name, value) in &info.tags {
+        text.push_str(&format!("  {}: {}\n", name, value));
     }
+
+    text.push('\n');
+    text.push_str(&border_line());
+    text.push('\n');
+    let _ = event_tx.send(AppEvent::ShowMessage(text, FlashLevel::Info));
 }
 
-fn refresh_ui_now(state: &AppState) {
+fn refresh_ui_now(state: &AppState, screen: &mut dyn Renderer) {
     let mut ui_lock = state.ui.lock();
     let pl_view = state.playlist.lock().clone_view();
-    if let Ok(mut screen) = Screen::new() {
-        let _ = screen.draw(&mut *ui_lock, &pl_view);
-    }
+    let _ = screen.draw(&mut ui_lock, &pl_view);
 }
 
 fn help_text() -> String {
     let mut s = String::new();
-    s.push_str(&"═".repeat(60));
-    s.push_str("\n");
-    s.push_str(&format!("{:^60}\n", "🎵 BeatCLI — Console Music Player"));
-    s.push_str(&"═".repeat(60));
+    s.push_str(&border_line());
+    s.push('\n');
+    s.push_str(&center_line("🎵 BeatCLI — Console Music Player"));
+    s.push('\n');
+    s.push_str(&border_line());
     s.push_str("\n\n");
 
-    s.push_str(&"─".repeat(20));
-    s.push_str(" 常用命令 ");
-    s.push_str(&"─".repeat(20));
-    s.push_str("\n");
-
-    s.push_str("/help                显示帮助\n");
-    s.push_str("/folder <path>       选择音乐文件夹\n");
-    s.push_str("/list                列出播放列表\n");
-    s.push_str("/search <keyword>    搜索歌曲\n");
-    s.push_str("/play <N>            播放第 N 首(从1开始)，默认播放第一首\n");
-    s.push_str("/pause               暂停\n");
-    s.push_str("/resume              继续\n");
-    s.push_str("/next                下一首\n");
-    s.push_str("/prev                上一首\n");
-    s.push_str("/mode <Sequential|RepeatOne|Shuffle> 切换播放模式\n");
-    s.push_str("/volume <0..100>     设置音量\n");
-    s.push_str("/lyrics              切换歌词显示\n");
-    s.push_str("/lmode               切换歌词显示模式(流式/清屏)\n");
-    s.push_str("/now                 显示当前播放信息\n");
-    s.push_str("/quit                退出\n");
-
-    s.push_str(&"═".repeat(60));
+    s.push_str(&divider(Msg::HelpCommands.text()));
+    s.push('\n');
+
+    for spec in COMMANDS {
+        s.push_str(&format!("{:<20} {}\n", spec.usage, spec.summary()));
+    }
+    s.push('\n');
+    s.push_str(Msg::HelpFooter.text());
+
+    s.push_str(&border_line());
     s.push_str("\n\n");
     s
 }
 
+// /help <命令> 的内容：用法、简写、详细说明和示例
+fn command_help_text(spec: &CommandSpec) -> String {
+    let mut s = tr!(Msg::HelpUsage, spec.usage);
+    if !spec.short.is_empty() {
+        let short: Vec<String> = spec.short.iter().map(|n| format!("/{}", n)).collect();
+        s.push_str(&tr!(Msg::HelpShort, short.join(Msg::ListSep.text())));
+    }
+    s.push_str(&format!("\n{}\n", spec.summary()));
+    if !spec.details().is_empty() {
+        s.push_str(&format!("{}\n", spec.details()));
+    }
+    if !spec.examples.is_empty() {
+        s.push_str(Msg::HelpExamples.text());
+        for example in spec.examples {
+            s.push_str(&format!("  {}\n", example));
+        }
+    }
+    s.trim_end().to_string()
+}
+
+fn scan_stopped_message(limit: ScanLimit, found: usize) -> String {
+    tr!(Msg::ScanStopped, limit, found)
+}
+
+// 显示播放列表的第 page 页（从 1 开始），当前歌曲前显示 >>，不是最后一页时提示下一页的命令
+fn show_list_page(state: &AppState, page: usize, format: ListFormat, event_tx: &Sender<AppEvent>) {
+    let page_size = state.ui.lock().list_page_size;
+    let pl = state.playlist.lock();
+    if pl.items.is_empty() {
+        let _ = event_tx.send(AppEvent::ShowMessage(
+            tr!(Msg::EmptyPlaylistHint),
+            FlashLevel::Info,
+        ));
+        return;
+    }
+    let pages = pl.page_count(page_size);
+    if page > pages {
+        let _ = event_tx.send(AppEvent::ShowMessage(
+            tr!(Msg::ListPageOutOfRange, pages),
+            FlashLevel::Error,
+        ));
+        return;
+    }
+
+    let mut msg = tr!(Msg::PlaylistHeader, page, pages, pl.items.len());
+    let digits = pl.items.len().to_string().len();
+    let start = (page - 1) * page_size;
+    let entries: Vec<(usize, PathBuf)> = pl
+        .items
+        .iter()
+        .cloned()
+        .enumerate()
+        .skip(start)
+        .take(page_size)
+        .collect();
+    let current = pl.current;
+    drop(pl);
+
+    let name_of = |path: &Path| {
+        path.file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or(Msg::UnknownFileName.text())
+            .to_string()
+    };
+    let marker = |i: usize| if current == Some(i) { ">>" } else { "  " };
+    if format == ListFormat::Compact {
+        for (i, path) in &entries {
+            msg.push_str(&format!(
+                "{} {:>digits$}. {}\n",
+                marker(*i),
+                i + 1,
+                name_of(path)
+            ));
+        }
+    } else {
+        // 读取本页各首的时长和艺术家，读过的从缓存取
+        let mut cache = state.metadata.lock();
+        let rows: Vec<(usize, String, String, String)> = entries
+            .iter()
+            .map(|(i, path)| {
+                let meta = cache.get(path);
+                let duration = meta.duration.map_or("--:--".to_string(), format_time);
+                let artist = meta.artist.clone().unwrap_or_default();
+                (*i, name_of(path), artist, duration)
+            })
+            .collect();
+        drop(cache);
+        msg.push_str(&list_table(&rows, digits, format, current));
+    }
+    if page < pages {
+        let next = match format {
+            ListFormat::Compact => (page + 1).to_string(),
+            _ => format!("{} {}", page + 1, format.name()),
+        };
+        msg.push_str(&tr!(Msg::ListMore, next));
+    }
+    let _ = event_tx.send(AppEvent::ShowMessage(
+        msg.trim_end().to_string(),
+        FlashLevel::Info,
+    ));
+}
+
+// /list normal 和 wide 的表格：带列标题，时长右对齐，文件名按剩余宽度截断，整行不超过界面宽度。
+// rows 为 (下标, 文件名, 艺术家, 时长)
+fn list_table(
+    rows: &[(usize, String, String, String)],
+    digits: usize,
+    format: ListFormat,
+    current: Option<usize>,
+) -> String {
+    const GAP: &str = "  ";
+    let wide = format == ListFormat::Wide;
+    let prefix_width = 3 + digits + 2; // ">> " + 序号 + ". "
+    let duration_width = rows
+        .iter()
+        .map(|r| r.3.width())
+        .chain([Msg::ListColumnDuration.text().width()])
+        .max()
+        .unwrap_or(0);
+    let mut rest = ui_width().saturating_sub(prefix_width + GAP.len() + duration_width);
+    let artist_width = if wide {
+        let width = rows
+            .iter()
+            .map(|r| r.2.width())
+            .chain([Msg::ListColumnArtist.text().width()])
+            .max()
+            .unwrap_or(0)
+            .min(rest / 3);
+        rest = rest.saturating_sub(width + GAP.len());
+        width
+    } else {
+        0
+    };
+    let name_width = rest;
+
+    let line = |prefix: String, name: &str, artist: &str, duration: &str| {
+        let mut line = prefix;
+        line.push_str(&fit_width(name, name_width));
+        line.push_str(GAP);
+        if wide {
+            line.push_str(&fit_width(artist, artist_width));
+            line.push_str(GAP);
+        }
+        line.push_str(&align_right(duration, duration_width));
+        line.push('\n');
+        line
+    };
+    let mut table = line(
+        format!("   {:>digits$}  ", "#"),
+        Msg::ListColumnName.text(),
+        Msg::ListColumnArtist.text(),
+        Msg::ListColumnDuration.text(),
+    );
+    for (i, name, artist, duration) in rows {
+        let marker = if current == Some(*i) { ">>" } else { "  " };
+        table.push_str(&line(
+            format!("{} {:>digits$}. ", marker, i + 1),
+            name,
+            artist,
+            duration,
+        ));
+    }
+    table
+}
+
+// 按显示宽度右对齐
+fn align_right(text: &str, width: usize) -> String {
+    format!("{}{}", " ".repeat(width.saturating_sub(text.width())), text)
+}
+
 fn format_item(idx: usize, name: &str, is_current: bool) -> String {
     let marker = if is_current { ">" } else { " " };
     format!("  {}. {}{}\n", idx + 1, marker, name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn each_command_waits_for_its_own_ack() {
+        let (cmd_tx, cmd_rx) = unbounded();
+        let (ack_tx, ack_rx) = unbounded();
+        let audio = thread::spawn(move || {
+            let mut handled = Vec::new();
+            while let Ok(command) = cmd_rx.recv() {
+                // 第一条命令很慢（如扫描大文件夹）并且成功，第二条立即失败
+                let ok = match command {
+                    Command::Folder(..) => {
+                        thread::sleep(Duration::from_millis(300));
+                        true
+                    }
+                    _ => false,
+                };
+                handled.push(format!("{:?}", command));
+                let _ = ack_tx.send(ok);
+            }
+            handled
+        });
+
+        let slow = Command::Folder("/music".into(), FolderFlags::default());
+        let started = Instant::now();
+        assert!(send_and_wait(&cmd_tx, &ack_rx, slow));
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        assert!(!send_and_wait(&cmd_tx, &ack_rx, Command::Pause));
+        assert!(ack_rx.is_empty());
+
+        drop(cmd_tx);
+        let handled = audio.join().unwrap();
+        assert_eq!(handled.len(), 2);
+        assert!(handled[1].starts_with("Pause"));
+    }
+
+    #[test]
+    fn send_and_wait_fails_when_the_audio_thread_is_gone() {
+        let (cmd_tx, cmd_rx) = unbounded::<Command>();
+        let (ack_tx, ack_rx) = unbounded::<bool>();
+        drop(ack_tx);
+        assert!(!send_and_wait(&cmd_tx, &ack_rx, Command::Pause));
+        drop(cmd_rx);
+        assert!(!send_and_wait(&cmd_tx, &ack_rx, Command::Pause));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::cue;
+use crate::decode;
+use crate::replaygain::read_tags;
+use rodio::Source;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// 音轨的技术信息，/info 显示
+pub struct TrackInfo {
+    pub path: PathBuf,               // 实际的音频文件（cue 音轨为整轨文件）
+    pub size: Option<u64>,           // 文件大小（字节）
+    pub format: String,              // 文件格式，例如 FLAC、MP3、Ogg Opus
+    pub sample_rate: Option<u32>,    // 采样率（Hz），无法解码时为 None
+    pub channels: Option<u16>,       // 声道数
+    pub duration: Option<Duration>,  // 时长，cue 音轨为该音轨的时长
+    pub bitrate: Option<u32>,        // 平均码率（kbps），按文件大小和整个文件的时长估算
+    pub tags: Vec<(String, String)>, // 文本标签，没有标签或格式不支持时为空
+}
+
+impl TrackInfo {
+    /// 读取列表条目的技术信息，文件无法打开或解码时对应项为 None
+    pub fn read(entry: &Path) -> Self {
+        let path = cue::audio_file(entry);
+        let size = std::fs::metadata(&path).ok().map(|m| m.len());
+        let decoded = decode::open(&path, Duration::ZERO).ok();
+        let (sample_rate, channels, file_duration) = match &decoded {
+            Some((source, total)) => (Some(source.sample_rate()), Some(source.channels()), *total),
+            None => (None, None, None),
+        };
+        let bitrate = size.zip(file_duration).and_then(|(size, d)| {
+            let secs = d.as_secs_f64();
+            (secs > 0.0).then(|| (size as f64 * 8.0 / secs / 1000.0).round() as u32)
+        });
+        Self {
+            format: detect_format(&path),
+            tags: read_tags(&path).unwrap_or_default(),
+            path,
+            size,
+            sample_rate,
+            channels,
+            duration: entry_duration(entry, file_duration),
+            bitrate,
+        }
+    }
+}
+
+// 列表条目的时长：cue 音轨为该音轨的时长，否则为整个文件的时长
+fn entry_duration(entry: &Path, file_duration: Option<Duration>) -> Option<Duration> {
+    match cue::resolve(entry) {
+        Some(track) => track
+            .end
+            .or(file_duration)
+            .map(|end| end.saturating_sub(track.start)),
+        None => file_duration,
+    }
+}
+
+/// /list 显示的曲目信息
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    pub duration: Option<Duration>,
+    pub artist: Option<String>,
+    modified: Option<SystemTime>, // 读取时文件的修改时间，文件变化后重新读取
+}
+
+impl Metadata {
+    fn read(entry: &Path, modified: Option<SystemTime>) -> Self {
+        let path = cue::audio_file(entry);
+        let file_duration = decode::open(&path, Duration::ZERO)
+            .ok()
+            .and_then(|(_, total)| total);
+        let artist = read_tags(&path)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|(key, value)| key.eq_ignore_ascii_case("ARTIST") && !value.trim().is_empty())
+            .map(|(_, value)| value.trim().to_string());
+        Self {
+            duration: entry_duration(entry, file_duration),
+            artist,
+            modified,
+        }
+    }
+}
+
+/// 按需读取的曲目信息，避免每次 /list 都重新解码文件、读取标签
+#[derive(Default)]
+pub struct MetadataCache {
+    entries: HashMap<PathBuf, Metadata>,
+}
+
+impl MetadataCache {
+    /// 取出列表条目的信息，没有缓存或文件已修改时重新读取
+    pub fn get(&mut self, entry: &Path) -> &Metadata {
+        let modified = std::fs::metadata(cue::audio_file(entry))
+            .and_then(|m| m.modified())
+            .ok();
+        let stale = self
+            .entries
+            .get(entry)
+            .is_none_or(|cached| cached.modified != modified);
+        if stale {
+            self.entries
+                .insert(entry.to_path_buf(), Metadata::read(entry, modified));
+        }
+        &self.entries[entry]
+    }
+}
+
+// 按文件开头的标识判断格式，无法识别时使用扩展名
+fn detect_format(path: &Path) -> String {
+    let mut head = [0u8; 40];
+    let n = File::open(path)
+        .and_then(|mut f| f.read(&mut head))
+        .unwrap_or(0);
+    let head = &head[..n];
+    let format = if head.starts_with(b"fLaC") {
+        "FLAC"
+    } else if head.starts_with(b"OggS") {
+        if head.windows(8).any(|w| w == b"OpusHead") {
+            "Ogg Opus"
+        } else if head.windows(7).any(|w| w == b"\x01vorbis") {
+            "Ogg Vorbis"
+        } else {
+            "Ogg"
+        }
+    } else if head.starts_with(b"ID3") || (n >= 2 && head[0] == 0xff && head[1] & 0xe0 == 0xe0) {
+        "MP3"
+    } else if head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WAVE") {
+        "WAV"
+    } else if head.get(4..8) == Some(b"ftyp") {
+        "MP4 / AAC"
+    } else {
+        return path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_uppercase())
+            .unwrap_or_default();
+    };
+    format.to_string()
+}
+
+/// 文件大小的显示文本，例如 4.2 MB、512 KB
+pub fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let b = bytes as f64;
+    if b >= KB * KB * KB {
+        format!("{:.1} GB", b / (KB * KB * KB))
+    } else if b >= KB * KB {
+        format!("{:.1} MB", b / (KB * KB))
+    } else if b >= KB {
+        format!("{:.0} KB", b / KB)
+    } else {
+        format!("{} B", bytes)
+    }
+}
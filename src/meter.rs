@@ -0,0 +1,384 @@
+use parking_lot::Mutex;
+use rodio::Source;
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+// 每隔多少帧取一帧参与计算，降低对音频线程的开销
+const DOWNSAMPLE: u32 = 8;
+// 每秒发布电平的次数
+const UPDATES_PER_SEC: u32 = 20;
+// 频谱分析使用的样本数（2 的幂）
+const SPECTRUM_WINDOW: usize = 1024;
+// 频谱音源攒够这么多帧才写入共享缓冲区，减少加锁次数
+const SPECTRUM_CHUNK: usize = 256;
+// 频谱显示的频率范围（Hz）和幅度范围（dB）
+const SPECTRUM_MIN_HZ: f32 = 40.0;
+const SPECTRUM_MAX_HZ: f32 = 16000.0;
+const SPECTRUM_FLOOR_DB: f32 = -70.0;
+
+/// 左右声道的当前电平（RMS，0.0-1.0），由音频源写入、界面读取
+#[derive(Clone, Default)]
+pub struct LevelMeter {
+    levels: Arc<[AtomicU32; 2]>,
+}
+
+impl LevelMeter {
+    pub fn levels(&self) -> (f32, f32) {
+        (
+            f32::from_bits(self.levels[0].load(Ordering::Relaxed)),
+            f32::from_bits(self.levels[1].load(Ordering::Relaxed)),
+        )
+    }
+
+    pub fn reset(&self) {
+        self.store(0.0, 0.0);
+    }
+
+    fn store(&self, left: f32, right: f32) {
+        self.levels[0].store(left.to_bits(), Ordering::Relaxed);
+        self.levels[1].store(right.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// 包装解码器的 Source，在样本经过时计算每个声道的 RMS
+pub struct MeterSource<S> {
+    inner: S,
+    meter: LevelMeter,
+    channels: u16,
+    channel: u16,     // 下一个样本所属的声道
+    frame: u32,       // 当前窗口内已经过的帧数
+    window: u32,      // 每个窗口的帧数
+    sums: [f32; 2],   // 窗口内样本平方和
+    counts: [u32; 2], // 窗口内参与计算的样本数
+}
+
+impl<S: Source<Item = i16>> MeterSource<S> {
+    pub fn new(inner: S, meter: LevelMeter) -> Self {
+        let channels = inner.channels().max(1);
+        let window = (inner.sample_rate() / UPDATES_PER_SEC).max(DOWNSAMPLE);
+        Self {
+            inner,
+            meter,
+            channels,
+            channel: 0,
+            frame: 0,
+            window,
+            sums: [0.0; 2],
+            counts: [0; 2],
+        }
+    }
+
+    fn publish(&mut self) {
+        let rms = |i: usize| {
+            if self.counts[i] == 0 {
+                0.0
+            } else {
+                (self.sums[i] / self.counts[i] as f32).sqrt()
+            }
+        };
+        let left = rms(0);
+        // 单声道时两个电平相同
+        let right = if self.channels == 1 { left } else { rms(1) };
+        self.meter.store(left, right);
+        self.sums = [0.0; 2];
+        self.counts = [0; 2];
+    }
+}
+
+impl<S: Source<Item = i16>> Iterator for MeterSource<S> {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        let sample = self.inner.next()?;
+
+        // 多于两个声道时，其余声道都计入右声道
+        if self.frame.is_multiple_of(DOWNSAMPLE) {
+            let idx = usize::from(self.channel.min(1));
+            let v = sample as f32 / i16::MAX as f32;
+            self.sums[idx] += v * v;
+            self.counts[idx] += 1;
+        }
+
+        self.channel += 1;
+        if self.channel >= self.channels {
+            self.channel = 0;
+            self.frame += 1;
+            if self.frame >= self.window {
+                self.frame = 0;
+                self.publish();
+            }
+        }
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = i16>> Source for MeterSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+/// 最近播放的样本（多声道混合为单声道），由音频源写入，用于计算频谱。
+/// 关闭时音频源只转发样本，播放中也可以随时开关
+#[derive(Clone, Default)]
+pub struct SampleBuffer {
+    inner: Arc<Mutex<(VecDeque<f32>, u32)>>, // 样本, 采样率
+    enabled: Arc<AtomicBool>,
+}
+
+impl SampleBuffer {
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.reset();
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn push(&self, frames: &[f32], sample_rate: u32) {
+        let mut inner = self.inner.lock();
+        let (samples, rate) = &mut *inner;
+        *rate = sample_rate;
+        samples.extend(frames);
+        let excess = samples.len().saturating_sub(SPECTRUM_WINDOW);
+        samples.drain(..excess);
+    }
+
+    pub fn reset(&self) {
+        self.inner.lock().0.clear();
+    }
+
+    /// 把最近的样本分成 bars 个对数间隔的频段，返回每个频段的强度（0.0-1.0）
+    pub fn spectrum(&self, bars: usize) -> Vec<f32> {
+        let (samples, rate) = {
+            let inner = self.inner.lock();
+            (inner.0.iter().copied().collect::<Vec<f32>>(), inner.1)
+        };
+        if samples.len() < SPECTRUM_WINDOW || rate == 0 || bars == 0 {
+            return vec![0.0; bars];
+        }
+
+        // 加汉宁窗后做 FFT，幅度按窗函数的总和归一化，满幅正弦波约为 0 dB
+        let tables = spectrum_tables();
+        let mut re: Vec<f32> = samples
+            .iter()
+            .zip(&tables.window)
+            .map(|(v, w)| v * w)
+            .collect();
+        let mut im = vec![0.0; SPECTRUM_WINDOW];
+        fft(&mut re, &mut im, &tables.twiddles);
+        let scale = 4.0 / SPECTRUM_WINDOW as f32;
+
+        let bin_hz = rate as f32 / SPECTRUM_WINDOW as f32;
+        let max_hz = SPECTRUM_MAX_HZ.min(rate as f32 / 2.0);
+        let edge =
+            |i: usize| SPECTRUM_MIN_HZ * (max_hz / SPECTRUM_MIN_HZ).powf(i as f32 / bars as f32);
+        (0..bars)
+            .map(|b| {
+                // 每个频段至少包含一个频点，低频段较窄时取最近的频点
+                let lo = (edge(b) / bin_hz).floor() as usize;
+                let hi = ((edge(b + 1) / bin_hz).ceil() as usize).max(lo + 1);
+                let peak = (lo..hi.min(SPECTRUM_WINDOW / 2))
+                    .map(|k| (re[k] * re[k] + im[k] * im[k]).sqrt() * scale)
+                    .fold(0.0f32, f32::max);
+                let db = 20.0 * peak.max(1e-6).log10();
+                ((db - SPECTRUM_FLOOR_DB) / -SPECTRUM_FLOOR_DB).clamp(0.0, 1.0)
+            })
+            .collect()
+    }
+}
+
+// 频谱分析用的汉宁窗和旋转因子，只计算一次
+struct SpectrumTables {
+    window: Vec<f32>,
+    twiddles: Vec<(f32, f32)>,
+}
+
+fn spectrum_tables() -> &'static SpectrumTables {
+    static TABLES: OnceLock<SpectrumTables> = OnceLock::new();
+    TABLES.get_or_init(|| SpectrumTables {
+        window: (0..SPECTRUM_WINDOW)
+            .map(|i| 0.5 * (1.0 - (2.0 * PI * i as f32 / (SPECTRUM_WINDOW - 1) as f32).cos()))
+            .collect(),
+        twiddles: twiddles(SPECTRUM_WINDOW),
+    })
+}
+
+// 长度为 n 的 FFT 的旋转因子 e^(-2πik/n)，k < n/2
+fn twiddles(n: usize) -> Vec<(f32, f32)> {
+    (0..n / 2)
+        .map(|k| {
+            let angle = -2.0 * PI * k as f32 / n as f32;
+            (angle.cos(), angle.sin())
+        })
+        .collect()
+}
+
+// 原地基 2 FFT，长度必须是 2 的幂，twiddles 由 twiddles(n) 生成
+fn fft(re: &mut [f32], im: &mut [f32], twiddles: &[(f32, f32)]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_re, w_im) = twiddles[k * stride];
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// 包装 Source，把经过的样本混合为单声道写入 SampleBuffer；buffer 关闭时只转发样本
+pub struct SpectrumSource<S> {
+    inner: S,
+    buffer: SampleBuffer,
+    channels: u16,
+    channel: u16,      // 下一个样本所属的声道
+    sum: f32,          // 当前帧各声道样本之和
+    pending: Vec<f32>, // 尚未写入共享缓冲区的帧
+}
+
+impl<S: Source<Item = i16>> SpectrumSource<S> {
+    pub fn new(inner: S, buffer: SampleBuffer) -> Self {
+        let channels = inner.channels().max(1);
+        Self {
+            inner,
+            buffer,
+            channels,
+            channel: 0,
+            sum: 0.0,
+            pending: Vec::with_capacity(SPECTRUM_CHUNK),
+        }
+    }
+}
+
+impl<S: Source<Item = i16>> Iterator for SpectrumSource<S> {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        let sample = self.inner.next()?;
+        // 只在帧的开头检查开关，保证写入的每一帧都包含全部声道
+        if self.channel == 0 && !self.buffer.enabled() {
+            self.pending.clear();
+            return Some(sample);
+        }
+
+        self.sum += sample as f32 / i16::MAX as f32;
+        self.channel += 1;
+        if self.channel >= self.channels {
+            self.pending.push(self.sum / self.channels as f32);
+            self.sum = 0.0;
+            self.channel = 0;
+            if self.pending.len() >= SPECTRUM_CHUNK {
+                self.buffer.push(&self.pending, self.inner.sample_rate());
+                self.pending.clear();
+            }
+        }
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = i16>> Source for SpectrumSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fft_matches_a_direct_transform() {
+        let n = 16;
+        let input: Vec<f32> = (0..n).map(|i| ((i * 7) % 5) as f32 - 2.0).collect();
+        let mut re = input.clone();
+        let mut im = vec![0.0; n];
+        fft(&mut re, &mut im, &twiddles(n));
+        for k in 0..n {
+            let (mut dft_re, mut dft_im) = (0.0f32, 0.0f32);
+            for (i, v) in input.iter().enumerate() {
+                let angle = -2.0 * PI * (k * i) as f32 / n as f32;
+                dft_re += v * angle.cos();
+                dft_im += v * angle.sin();
+            }
+            assert!((re[k] - dft_re).abs() < 1e-3, "re[{k}]");
+            assert!((im[k] - dft_im).abs() < 1e-3, "im[{k}]");
+        }
+    }
+
+    #[test]
+    fn sine_peaks_in_its_own_band() {
+        let rate = 44_100;
+        let bars = 24;
+        let max_hz = SPECTRUM_MAX_HZ.min(rate as f32 / 2.0);
+        let edge =
+            |i: usize| SPECTRUM_MIN_HZ * (max_hz / SPECTRUM_MIN_HZ).powf(i as f32 / bars as f32);
+        for hz in [200.0, 1_000.0, 5_000.0] {
+            let buffer = SampleBuffer::default();
+            let sine: Vec<f32> = (0..SPECTRUM_WINDOW)
+                .map(|i| (2.0 * PI * hz * i as f32 / rate as f32).sin())
+                .collect();
+            buffer.push(&sine, rate);
+            let bands = buffer.spectrum(bars);
+            // 低频段很窄，相邻频段可能共用同一个频点，强度相同
+            let band = (0..bars).find(|&b| hz < edge(b + 1)).unwrap();
+            let loudest = bands.iter().copied().fold(0.0f32, f32::max);
+            assert_eq!(bands[band], loudest, "{hz} Hz: {bands:?}");
+            assert!(loudest > 0.9);
+            let far = (0..bars).filter(|b| b.abs_diff(band) > 3);
+            assert!(far.map(|b| bands[b]).all(|v| v < 0.5), "{hz} Hz: {bands:?}");
+        }
+    }
+}
@@ -1,19 +1,98 @@
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink};
+use crate::cue;
+use crate::decode;
+use crate::i18n::{Msg, tr};
+use crate::meter::{LevelMeter, MeterSource, SampleBuffer, SpectrumSource};
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::decoder::DecoderError;
+use rodio::{OutputStream, OutputStreamHandle, Sink, Source};
 use std::{
-    fs::File,
-    io::BufReader,
-    path::Path,
+    fmt, io,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+    },
+    thread,
     time::{Duration, Instant},
 };
 
+// 淡入淡出时每步调整音量的间隔
+const RAMP_STEP: Duration = Duration::from_millis(20);
+
+/// 打开或解码音频文件失败的原因
+#[derive(Debug)]
+pub enum PlayError {
+    Open(io::Error),
+    Decode(DecoderError),
+    #[cfg(feature = "symphonia")]
+    Symphonia(symphonia::core::errors::Error),
+    #[cfg(feature = "symphonia")]
+    UnsupportedCodec(&'static str),
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::Open(e) => write!(f, "{}", tr!(Msg::OpenFailed, e)),
+            PlayError::Decode(e) => write!(f, "{}", tr!(Msg::DecodeFailed, e)),
+            #[cfg(feature = "symphonia")]
+            PlayError::Symphonia(e) => write!(f, "{}", tr!(Msg::DecodeFailed, e)),
+            #[cfg(feature = "symphonia")]
+            PlayError::UnsupportedCodec(codec) => {
+                write!(f, "{}", tr!(Msg::UnsupportedCodec, codec))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlayError {}
+
+/// 播放状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlayState {
+    Playing,
+    Paused,
+    #[default]
+    Stopped,
+}
+
+impl PlayState {
+    /// 状态的显示文本，例如 ▶ 播放中
+    pub fn label(self) -> &'static str {
+        match self {
+            PlayState::Playing => Msg::PlayStatePlaying.text(),
+            PlayState::Paused => Msg::PlayStatePaused.text(),
+            PlayState::Stopped => Msg::PlayStateStopped.text(),
+        }
+    }
+}
+
 /// 播放器
 pub struct Player {
     _stream: OutputStream,
     handle: OutputStreamHandle,
-    sink: Option<Sink>,
-    started_at: Option<Instant>,
+    sink: Option<Arc<Sink>>,
+    secondary_sink: Option<Arc<Sink>>, // 交叉淡化时正在淡出的上一首歌曲
+    started_at: Option<Instant>,       // 当前计时段的开始时间（换歌或调速时重新开始）
     paused_at: Option<Instant>,
     elapsed_pause: Duration,
+    offset: Duration, // 计时段开始时的播放位置
+    speed: f32,
+    decoded: Arc<AtomicU64>, // 当前歌曲已送往输出的样本数
+    samples_per_sec: u32,    // 当前歌曲每秒的样本数（采样率 × 声道数）
+    decode_start: Duration,  // 开始解码的位置，跳转后为跳转到的位置
+    meter: LevelMeter,
+    spectrum: SampleBuffer,          // 开启频谱显示后记录最近的样本
+    path: Option<PathBuf>,           // 当前歌曲的文件路径
+    total: Option<Duration>,         // 当前歌曲的总时长
+    device: Option<String>,          // 选择的输出设备名称，None 为系统默认设备
+    pub fade_in_duration: Duration,  // 新歌曲开始时的淡入时长，0 为不淡入
+    pub fade_out_duration: Duration, // 歌曲结尾的淡出时长，0 为不淡出
+    volume: Arc<AtomicU32>,          // 目标音量（f32 的位），渐变线程按它计算每一步的音量
+    ramp: Arc<AtomicU64>,            // 渐变的代数，开始新渐变或取消时加一，旧的渐变线程随之退出
+    ramping: Arc<AtomicBool>,        // 是否正在渐变，渐变期间设置音量只更新目标音量
+    fading_out: bool,
 }
 
 impl Player {
@@ -23,35 +102,325 @@ impl Player {
             _stream,
             handle,
             sink: None,
+            secondary_sink: None,
             started_at: None,
             paused_at: None,
             elapsed_pause: Duration::ZERO,
+            offset: Duration::ZERO,
+            speed: 1.0,
+            decoded: Arc::new(AtomicU64::new(0)),
+            samples_per_sec: 0,
+            decode_start: Duration::ZERO,
+            meter: LevelMeter::default(),
+            spectrum: SampleBuffer::default(),
+            path: None,
+            total: None,
+            device: None,
+            fade_in_duration: Duration::ZERO,
+            fade_out_duration: Duration::ZERO,
+            volume: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            ramp: Arc::new(AtomicU64::new(0)),
+            ramping: Arc::new(AtomicBool::new(false)),
+            fading_out: false,
         })
     }
 
-    pub fn play_file(&mut self, path: &Path) {
+    /// 所有音频后端中可用的输出设备名称
+    pub fn list_devices() -> Vec<String> {
+        let mut names = Vec::new();
+        for device in output_devices() {
+            if let Ok(name) = device.name()
+                && !names.contains(&name)
+            {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// 切换到指定名称的输出设备，正在播放的歌曲从当前位置继续。
+    /// Linux 上也可以使用 PulseAudio / PipeWire 的 sink 名称
+    pub fn set_device(&mut self, name: &str) -> anyhow::Result<()> {
+        let device = match find_device(name) {
+            Some(device) => {
+                set_pulse_sink(None);
+                device
+            }
+            None => {
+                pulse_device(name).ok_or_else(|| anyhow::anyhow!(tr!(Msg::DeviceNotFound, name)))?
+            }
+        };
+        let (stream, handle) = OutputStream::try_from_device(&device)?;
+        self.replace_stream(stream, handle)?;
+        self.device = Some(name.to_string());
+        log::info!("output device: {}", name);
+        Ok(())
+    }
+
+    /// 改用系统默认的输出设备
+    pub fn use_default_device(&mut self) -> anyhow::Result<()> {
+        set_pulse_sink(None);
+        let (stream, handle) = OutputStream::try_default()?;
+        self.replace_stream(stream, handle)?;
+        self.device = None;
+        log::info!("output device: system default");
+        Ok(())
+    }
+
+    /// 选择的输出设备是否已经消失（如蓝牙耳机断开），使用默认设备时总是 false
+    pub fn device_lost(&self) -> bool {
+        self.device.as_deref().is_some_and(|name| {
+            find_device(name).is_none() && !enumerate_pulse_sinks().iter().any(|(n, _)| n == name)
+        })
+    }
+
+    // 旧的 sink 绑定在旧设备上，记下位置后在新设备上重新播放
+    fn replace_stream(
+        &mut self,
+        stream: OutputStream,
+        handle: OutputStreamHandle,
+    ) -> Result<(), PlayError> {
+        let position = Duration::from_millis(self.get_current_ms() as u64);
+        let volume = self.sink.as_ref().map(|_| self.target_volume());
         if let Some(s) = &self.sink {
             s.stop();
         }
-        let file = match File::open(path) {
-            Ok(f) => f,
-            Err(_) => return,
+        self.sink = None;
+        self._stream = stream;
+        self.handle = handle;
+        match volume {
+            Some(volume) => self.restart_at(position, volume),
+            None => Ok(()),
+        }
+    }
+
+    /// 跳转到当前歌曲的指定位置，保持音量和暂停状态。
+    /// 超过歌曲末尾时当前歌曲随即结束
+    pub fn seek(&mut self, position: Duration) -> Result<(), PlayError> {
+        self.restart_at(position, self.target_volume())
+    }
+
+    // rodio 的 Sink 不支持跳转，从指定位置重新解码当前文件
+    fn restart_at(&mut self, position: Duration, volume: f32) -> Result<(), PlayError> {
+        let Some(path) = self.path.clone() else {
+            return Ok(());
         };
-        let source = match Decoder::new(BufReader::new(file)) {
-            Ok(s) => s,
-            Err(_) => return,
+        let paused = self.paused_at.is_some();
+        self.open(&path, position, Duration::ZERO)?;
+        self.set_volume(volume);
+        if paused {
+            self.pause();
+        }
+        Ok(())
+    }
+
+    /// 播放文件，失败时静默忽略
+    pub fn play_file(&mut self, path: &Path) {
+        let _ = self.try_play_file(path);
+    }
+
+    /// 播放文件，成功时返回歌曲总时长（格式不支持时为 None）
+    pub fn try_play_file(&mut self, path: &Path) -> Result<Option<Duration>, PlayError> {
+        self.try_play_file_at(path, Duration::ZERO)
+    }
+
+    /// 从指定位置开始播放文件，失败时静默忽略
+    pub fn play_file_at(&mut self, path: &Path, start: Duration) {
+        let _ = self.try_play_file_at(path, start);
+    }
+
+    /// 从指定位置开始播放文件，成功时返回歌曲总时长。
+    /// cue 音轨条目播放整轨文件中的对应片段，位置和时长都相对于音轨开头
+    pub fn try_play_file_at(
+        &mut self,
+        path: &Path,
+        start: Duration,
+    ) -> Result<Option<Duration>, PlayError> {
+        self.open(path, start, self.fade_in_duration)
+    }
+
+    /// 交叉淡化到下一首：当前歌曲在 overlap 内淡出，新歌曲同时淡入。
+    /// 淡入时长不超过新歌曲的一半，成功时返回新歌曲的总时长
+    pub fn crossfade_to(
+        &mut self,
+        path: &Path,
+        overlap: Duration,
+    ) -> Result<Option<Duration>, PlayError> {
+        let outgoing = self.sink.take();
+        let result = self.open(path, Duration::ZERO, overlap);
+        if let Some(sink) = outgoing {
+            if result.is_ok() {
+                fade_away(sink.clone(), overlap);
+                self.secondary_sink = Some(sink);
+            } else {
+                sink.stop();
+            }
+        }
+        result
+    }
+
+    /// 距离结尾不足交叉淡化时长时返回剩余的播放时间（按播放速度换算），
+    /// 由播放线程调用，返回 Some 时应开始交叉淡化。重叠时长不超过当前歌曲的一半
+    pub fn crossfade_due(&self, crossfade: Duration) -> Option<Duration> {
+        if crossfade.is_zero() || self.paused_at.is_some() || self.finished() {
+            return None;
+        }
+        let total = self.total?;
+        let remaining = total.saturating_sub(Duration::from_millis(self.get_current_ms() as u64));
+        (!remaining.is_zero() && remaining < crossfade.min(total / 2))
+            .then(|| remaining.div_f32(self.speed))
+    }
+
+    // 打开文件开始播放，按 fade_in 淡入（跳转、切换设备时为 0，不淡入），
+    // 淡入时长不超过歌曲的一半
+    fn open(
+        &mut self,
+        path: &Path,
+        start: Duration,
+        fade_in: Duration,
+    ) -> Result<Option<Duration>, PlayError> {
+        self.cancel_ramp();
+        self.stop_secondary();
+        if let Some(s) = &self.sink {
+            s.stop();
+        }
+        let (file, clip_start, clip_end) = match cue::resolve(path) {
+            Some(track) => (track.file, track.start, track.end),
+            None => (path.to_path_buf(), Duration::ZERO, None),
         };
+        let (source, file_total) = decode::open(&file, clip_start + start)
+            .inspect_err(|e| log::warn!("cannot play {}: {}", file.display(), e))?;
+        let total = clip_end
+            .or(file_total)
+            .map(|end| end.saturating_sub(clip_start));
         let sink = Sink::try_new(&self.handle).expect("create sink");
-        sink.append(source);
+        let samples_per_sec = source.sample_rate() * u32::from(source.channels());
+        // 交叉淡化时上一首仍在计数，新歌曲使用新的计数器
+        let decoded = Arc::new(AtomicU64::new(0));
+        // 播放到下一音轨的开始位置时结束
+        match clip_end {
+            Some(end) => sink.append(SpectrumSource::new(
+                MeterSource::new(
+                    PositionSource::new(
+                        source.take_duration(end.saturating_sub(clip_start + start)),
+                        decoded.clone(),
+                    ),
+                    self.meter.clone(),
+                ),
+                self.spectrum.clone(),
+            )),
+            None => sink.append(SpectrumSource::new(
+                MeterSource::new(
+                    PositionSource::new(source, decoded.clone()),
+                    self.meter.clone(),
+                ),
+                self.spectrum.clone(),
+            )),
+        }
 
+        sink.set_speed(self.speed);
+        let fade_in = total.map_or(fade_in, |total| fade_in.min(total / 2));
+        sink.set_volume(if fade_in.is_zero() {
+            self.target_volume()
+        } else {
+            0.0
+        });
         sink.play();
-        self.sink = Some(sink);
+        self.sink = Some(Arc::new(sink));
         self.started_at = Some(Instant::now());
+        self.offset = start;
+        self.decoded = decoded;
+        self.samples_per_sec = samples_per_sec;
+        self.decode_start = start;
         self.paused_at = None;
         self.elapsed_pause = Duration::ZERO;
+        self.path = Some(path.to_path_buf());
+        self.total = total;
+        if !fade_in.is_zero() {
+            self.start_ramp(0.0, 1.0, fade_in);
+        }
+        log::info!(
+            "playing {} from {:?} (length {:?}, fade in {:?})",
+            path.display(),
+            start,
+            total,
+            fade_in
+        );
+        Ok(total)
+    }
+
+    /// 距离结尾不足淡出时长时开始淡出，由播放线程定期调用
+    pub fn update_fade_out(&mut self) {
+        if self.fade_out_duration.is_zero() || self.fading_out || self.paused_at.is_some() {
+            return;
+        }
+        let Some(total) = self.total else {
+            return;
+        };
+        let remaining = total.saturating_sub(Duration::from_millis(self.get_current_ms() as u64));
+        if !remaining.is_zero() && remaining < self.fade_out_duration {
+            self.fading_out = true;
+            // 正在淡入时从当前音量开始淡出
+            let target = self.target_volume();
+            let from = match &self.sink {
+                Some(s) if target > 0.0 => (s.volume() / target).min(1.0),
+                _ => 1.0,
+            };
+            self.start_ramp(from, 0.0, remaining.div_f32(self.speed));
+        }
+    }
+
+    // 后台线程每 20ms 调整一次音量，从目标音量的 from 倍渐变到 to 倍
+    fn start_ramp(&mut self, from: f32, to: f32, duration: Duration) {
+        let Some(sink) = self.sink.clone() else {
+            return;
+        };
+        let generation = self.ramp.fetch_add(1, Ordering::SeqCst) + 1;
+        let (ramp, ramping, volume) =
+            (self.ramp.clone(), self.ramping.clone(), self.volume.clone());
+        let steps = (duration.as_millis() / RAMP_STEP.as_millis()).max(1) as u32;
+        ramping.store(true, Ordering::SeqCst);
+        thread::spawn(move || {
+            for step in 1..=steps {
+                thread::sleep(RAMP_STEP);
+                if ramp.load(Ordering::SeqCst) != generation {
+                    return;
+                }
+                let factor = from + (to - from) * step as f32 / steps as f32;
+                sink.set_volume(f32::from_bits(volume.load(Ordering::SeqCst)) * factor);
+            }
+            if ramp.load(Ordering::SeqCst) == generation {
+                ramping.store(false, Ordering::SeqCst);
+            }
+        });
+    }
+
+    // 停止正在进行的渐变，音量恢复为目标音量
+    fn cancel_ramp(&mut self) {
+        self.ramp.fetch_add(1, Ordering::SeqCst);
+        self.fading_out = false;
+        if self.ramping.swap(false, Ordering::SeqCst)
+            && let Some(s) = &self.sink
+        {
+            s.set_volume(self.target_volume());
+        }
+    }
+
+    // 交叉淡化中的上一首立即停止
+    fn stop_secondary(&mut self) {
+        if let Some(s) = self.secondary_sink.take() {
+            s.stop();
+        }
+    }
+
+    fn target_volume(&self) -> f32 {
+        f32::from_bits(self.volume.load(Ordering::SeqCst))
     }
 
     pub fn pause(&mut self) {
+        // 暂停时停止淡入淡出，继续播放后由播放线程重新判断是否需要淡出
+        self.cancel_ramp();
+        self.stop_secondary();
         if let Some(s) = &self.sink {
             s.pause();
         }
@@ -70,38 +439,276 @@ impl Player {
         }
     }
 
+    /// 设置音量，之后播放的歌曲也使用这个音量；淡入淡出期间只更新渐变的目标音量
     pub fn set_volume(&self, v: f32) {
-        if let Some(s) = &self.sink {
+        self.volume.store(v.to_bits(), Ordering::SeqCst);
+        if !self.ramping.load(Ordering::SeqCst)
+            && let Some(s) = &self.sink
+        {
             s.set_volume(v);
         }
     }
 
+    /// 当前的左右声道电平，暂停或未播放时为 0
+    pub fn levels(&self) -> (f32, f32) {
+        if self.sink.is_none() || self.paused_at.is_some() || self.finished() {
+            return (0.0, 0.0);
+        }
+        self.meter.levels()
+    }
+
+    /// 开始或停止记录播放的样本用于频谱显示，正在播放的歌曲立即生效
+    pub fn set_spectrum(&self, enabled: bool) {
+        self.spectrum.set_enabled(enabled);
+    }
+
+    /// 当前的频谱（bars 个频段，0.0-1.0），暂停、未播放或未开启频谱时为 0
+    pub fn spectrum(&self, bars: usize) -> Vec<f32> {
+        if self.spectrum.enabled() && self.state() == PlayState::Playing {
+            self.spectrum.spectrum(bars)
+        } else {
+            vec![0.0; bars]
+        }
+    }
+
+    /// 当前歌曲的文件路径，停止后为 None
+    pub fn current_path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn total_duration(&self) -> Option<Duration> {
+        self.total
+    }
+
+    /// 当前的播放状态，歌曲播放完后为 Stopped
+    pub fn state(&self) -> PlayState {
+        if self.sink.is_none() || self.finished() {
+            PlayState::Stopped
+        } else if self.paused_at.is_some() {
+            PlayState::Paused
+        } else {
+            PlayState::Playing
+        }
+    }
+
     pub fn finished(&self) -> bool {
         self.sink.as_ref().map(|s| s.empty()).unwrap_or(false)
     }
 
-    pub fn get_current_ms(&self) -> u128 {
-        if let Some(start) = self.started_at {
-            let mut elapsed = start.elapsed();
-            if let Some(paused) = self.paused_at {
-                elapsed = paused.duration_since(start) - self.elapsed_pause;
-            } else {
-                elapsed -= self.elapsed_pause;
+    /// 设置播放速度（1.0 为原速），之后播放的歌曲也使用这个速度
+    pub fn set_speed(&mut self, speed: f32) {
+        // 以当前位置开始新的计时段，之前的部分按原来的速度计算
+        if self.started_at.is_some() {
+            let now = Instant::now();
+            self.offset = self.clock_position();
+            self.started_at = Some(now);
+            self.elapsed_pause = Duration::ZERO;
+            if self.paused_at.is_some() {
+                self.paused_at = Some(now);
             }
-            elapsed.as_millis()
-        } else {
-            0
         }
+        self.speed = speed;
+        if let Some(s) = &self.sink {
+            s.set_speed(speed);
+        }
+    }
+
+    /// 当前播放位置（毫秒），优先使用已解码的样本数，无法得知时按经过的时间估算
+    pub fn get_current_ms(&self) -> u128 {
+        self.decode_position()
+            .unwrap_or_else(|| self.clock_position())
+            .as_millis()
+    }
+
+    /// 按已送往输出的样本数计算的播放位置，不受卡顿和调速误差的影响；
+    /// 没有正在播放的歌曲或采样率未知时为 None
+    pub fn decode_position(&self) -> Option<Duration> {
+        if self.sink.is_none() || self.samples_per_sec == 0 {
+            return None;
+        }
+        let samples = self.decoded.load(Ordering::Relaxed);
+        Some(
+            self.decode_start
+                + Duration::from_secs_f64(samples as f64 / f64::from(self.samples_per_sec)),
+        )
+    }
+
+    /// 当前计时段累计的暂停时长，包括正在进行的暂停
+    pub fn paused_duration(&self) -> Duration {
+        self.elapsed_pause + self.paused_at.map_or(Duration::ZERO, |t| t.elapsed())
+    }
+
+    /// 按经过的时间估算的播放位置，扣除暂停的时间并按播放速度换算
+    pub fn clock_position(&self) -> Duration {
+        let Some(start) = self.started_at else {
+            return Duration::ZERO;
+        };
+        let end = self.paused_at.unwrap_or_else(Instant::now);
+        self.offset + played_time(start, end, self.elapsed_pause).mul_f32(self.speed)
     }
 
     /// 停止播放并清理资源
     pub fn stop(&mut self) {
+        self.cancel_ramp();
+        self.stop_secondary();
         if let Some(sink) = &self.sink {
             sink.stop();
         }
         self.sink = None;
+        self.path = None;
+        self.total = None;
+        self.meter.reset();
+        self.spectrum.reset();
         self.started_at = None;
         self.paused_at = None;
         self.elapsed_pause = Duration::ZERO;
+        self.offset = Duration::ZERO;
+        self.samples_per_sec = 0;
+    }
+}
+
+// 统计经过的样本数，用来计算播放位置。暂停时 rodio 不再取样本，计数随之停止
+struct PositionSource<S> {
+    inner: S,
+    samples: Arc<AtomicU64>,
+}
+
+impl<S> PositionSource<S> {
+    fn new(inner: S, samples: Arc<AtomicU64>) -> Self {
+        Self { inner, samples }
+    }
+}
+
+impl<S: Source<Item = i16>> Iterator for PositionSource<S> {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        let sample = self.inner.next()?;
+        self.samples.fetch_add(1, Ordering::Relaxed);
+        Some(sample)
+    }
+}
+
+impl<S: Source<Item = i16>> Source for PositionSource<S> {
+    fn current_frame_len(&self) -> Option<usize> {
+        self.inner.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+}
+
+// 计时段中实际播放的时长。暂停时长的累计值可能因时钟误差略大于实际经过的时间，相减时不能下溢
+fn played_time(start: Instant, end: Instant, paused: Duration) -> Duration {
+    end.saturating_duration_since(start).saturating_sub(paused)
+}
+
+// 交叉淡化时上一首歌曲的淡出：从当前音量降到 0 后停止，sink 被停止时提前退出
+fn fade_away(sink: Arc<Sink>, duration: Duration) {
+    let from = sink.volume();
+    let steps = (duration.as_millis() / RAMP_STEP.as_millis()).max(1) as u32;
+    thread::spawn(move || {
+        for step in 1..=steps {
+            thread::sleep(RAMP_STEP);
+            if sink.empty() {
+                return;
+            }
+            sink.set_volume(from * (1.0 - step as f32 / steps as f32));
+        }
+        sink.stop();
+    });
+}
+
+// 依次列出所有音频后端的输出设备
+fn output_devices() -> impl Iterator<Item = rodio::Device> {
+    rodio::cpal::available_hosts()
+        .into_iter()
+        .filter_map(|id| rodio::cpal::host_from_id(id).ok())
+        .filter_map(|host| host.output_devices().ok())
+        .flatten()
+}
+
+fn find_device(name: &str) -> Option<rodio::Device> {
+    output_devices().find(|d| d.name().is_ok_and(|n| n == name))
+}
+
+/// PulseAudio / PipeWire 的 sink 列表（名称, 描述），没有 pactl 时为空
+pub fn enumerate_pulse_sinks() -> Vec<(String, String)> {
+    if !cfg!(target_os = "linux") {
+        return Vec::new();
+    }
+    // pactl 的输出随语言环境翻译，固定为 C 以便解析
+    let Ok(output) = process::Command::new("pactl")
+        .args(["list", "sinks"])
+        .env("LC_ALL", "C")
+        .output()
+    else {
+        return Vec::new();
+    };
+    let mut sinks = Vec::new();
+    let mut name: Option<String> = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let line = line.trim();
+        if let Some(n) = line.strip_prefix("Name:") {
+            name = Some(n.trim().to_string());
+        } else if let Some(desc) = line.strip_prefix("Description:")
+            && let Some(n) = name.take()
+        {
+            sinks.push((n, desc.trim().to_string()));
+        }
+    }
+    sinks
+}
+
+// ALSA 的 pulse 插件（以及 PipeWire 的兼容层）按 PULSE_SINK 选择输出的 sink
+fn pulse_device(name: &str) -> Option<rodio::Device> {
+    if !enumerate_pulse_sinks().iter().any(|(n, _)| n == name) {
+        return None;
+    }
+    set_pulse_sink(Some(name));
+    find_device("pulse")
+        .or_else(|| find_device("pipewire"))
+        .or_else(|| find_device("default"))
+}
+
+fn set_pulse_sink(sink: Option<&str>) {
+    // SAFETY: 只在播放线程切换设备时调用，此时输入线程在等待命令完成，
+    // 其他线程不会同时读写环境变量
+    unsafe {
+        match sink {
+            Some(sink) => std::env::set_var("PULSE_SINK", sink),
+            None => std::env::remove_var("PULSE_SINK"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn played_time_never_underflows() {
+        let start = Instant::now();
+        let end = start + Duration::from_secs(1);
+        assert_eq!(
+            played_time(start, end, Duration::from_millis(400)),
+            Duration::from_millis(600)
+        );
+        // 累计的暂停时长略大于经过的时间
+        assert_eq!(
+            played_time(start, end, Duration::from_millis(1005)),
+            Duration::ZERO
+        );
+        // 结束时刻早于开始时刻
+        assert_eq!(played_time(end, start, Duration::ZERO), Duration::ZERO);
     }
 }
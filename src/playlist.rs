@@ -1,53 +1,314 @@
+use crate::config::ScanningConfig;
+use crate::cue;
+use crate::decode;
+use crate::i18n::{Msg, tr};
+use globset::GlobSet;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{Rng, thread_rng};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PlaybackMode {
     #[default]
     Sequential,
     RepeatOne,
     Shuffle,
+    Audiobook, // 按顺序播放，可调速，/next、/prev 在 cue 章节间跳转
+}
+
+/// 播放列表排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Name,                // 按文件名
+    TrackNumberInferred, // 按文件名中推断出的音轨号
+}
+
+/// /list 的显示格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListFormat {
+    #[default]
+    Compact, // 只显示文件名
+    Normal, // 文件名和时长
+    Wide,   // 文件名、艺术家和时长
 }
 
-#[derive(Default, Clone)]
+impl ListFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "compact" => Some(Self::Compact),
+            "normal" => Some(Self::Normal),
+            "wide" => Some(Self::Wide),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Compact => "compact",
+            Self::Normal => "normal",
+            Self::Wide => "wide",
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Playlist {
     pub items: Vec<PathBuf>,
     pub current: Option<usize>,
     pub mode: PlaybackMode,
+    pub default_mode: PlaybackMode, // 扫描新文件夹后恢复的播放模式
+    pub auto_sort: bool,            // 扫描后若所有文件都带音轨号，则按音轨号排序
+    pub folder: Option<PathBuf>,    // 最近一次扫描的文件夹
+    pub scan_depth: usize,          // 最近一次扫描使用的递归深度
+    pub scan_symlinks: bool,        // 最近一次扫描是否跟随符号链接
+    pub scan_hidden: bool,          // 最近一次扫描是否包含隐藏文件
+    pub scan_folder_options: ScanOptions,
+    pub queue: VecDeque<PathBuf>, // 优先于播放模式依次播放的歌曲（如 /sample 抽取的曲目）
+    sample_loop: Option<usize>,   // /sampleloop 的抽取数量，队列播完后重新抽取
+    shuffle_bag: Vec<usize>,      // 随机模式下尚未播放的下标，末尾为下一首
 }
 
-#[derive(Clone, Default)]
+impl Default for Playlist {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            current: None,
+            mode: PlaybackMode::default(),
+            default_mode: PlaybackMode::default(),
+            auto_sort: true,
+            folder: None,
+            scan_depth: usize::MAX,
+            scan_symlinks: false,
+            scan_hidden: false,
+            scan_folder_options: ScanningConfig::default().scan_options(),
+            queue: VecDeque::new(),
+            sample_loop: None,
+            shuffle_bag: Vec::new(),
+        }
+    }
+}
+
+/// 文件夹扫描选项（来自配置文件的 [scanning]）
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    pub extensions: Vec<String>, // 小写、不带点的扩展名
+    pub ignore: GlobSet,         // 匹配相对扫描文件夹的路径
+    pub follow_symlinks: bool,
+    pub include_hidden: bool, // 包含隐藏文件和文件夹
+    pub max_entries: usize,   // 最多遍历的条目数，超出后停止扫描
+    pub timeout: Duration,    // 最长扫描时间，超出后停止扫描
+    pub split_cue: bool,      // 按 cue 文件把整轨文件拆分为音轨
+}
+
+impl ScanOptions {
+    pub fn is_audio(&self, path: &Path) -> bool {
+        extension_lowercase(path).is_some_and(|ext| {
+            self.extensions.contains(&ext) || decode::extra_extensions().contains(&ext.as_str())
+        })
+    }
+}
+
+/// 文件夹扫描结果
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScanSummary {
+    pub added: usize,               // 加入播放列表的歌曲数
+    pub unsupported: usize,         // 识别为音频但无法解码而跳过的文件数
+    pub skipped_links: usize,       // 未跟随的指向目录的符号链接数
+    pub skipped_hidden: usize,      // 跳过的隐藏文件和文件夹数
+    pub stopped: Option<ScanLimit>, // 因超出限制而提前停止时的原因
+}
+
+/// 提前停止扫描的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanLimit {
+    Entries(usize),
+    Timeout(Duration),
+}
+
+impl fmt::Display for ScanLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanLimit::Entries(n) => write!(f, "{}", tr!(Msg::ScanLimitEntries, n)),
+            ScanLimit::Timeout(d) => write!(f, "{}", tr!(Msg::ScanLimitTimeout, d.as_secs())),
+        }
+    }
+}
+
+// 播放界面列表面板每页显示的歌曲数
+const PANE_ENTRIES: usize = 7;
+
+#[derive(Clone, Default, PartialEq)]
 pub struct PlaylistView {
     pub len: usize,
     pub current: Option<usize>,
-    pub mode: PlaybackMode,
-    pub now_name: String,
-    pub next_name: String,
+    pub window: Vec<(usize, String)>, // 当前歌曲所在一页的歌曲 (下标, 文件名)，显示在列表面板中
+    pub page: usize,                  // 列表面板显示的页（从 1 开始）
+    pub pages: usize,                 // 列表面板的总页数
 }
 
 impl Playlist {
-    pub fn scan_folder(&mut self, folder: &str) -> anyhow::Result<usize> {
+    /// 扫描文件夹，max_depth 为递归的最大层数（1 表示只扫描当前目录，usize::MAX 表示不限），
+    /// follow_symlinks 为 true 时进入符号链接指向的目录，include_hidden 为 true 时包含隐藏文件。
+    /// 扫描期间定期调用 on_progress(已遍历的条目数, 已找到的音频数)
+    pub fn scan_folder_depth(
+        &mut self,
+        folder: &Path,
+        max_depth: usize,
+        follow_symlinks: bool,
+        include_hidden: bool,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> anyhow::Result<ScanSummary> {
+        self.reset();
+        self.folder = Some(folder.to_path_buf());
+        self.scan_depth = max_depth;
+        self.scan_symlinks = follow_symlinks;
+        self.scan_hidden = include_hidden;
+        let (items, summary) =
+            scan_audio_files(folder, max_depth, &self.rescan_options(), on_progress);
+        self.items = items;
+
+        if self.auto_sort
+            && matches!(
+                self.mode,
+                PlaybackMode::Sequential | PlaybackMode::Audiobook
+            )
+            && !self.items.is_empty()
+            && self.items.iter().all(|p| track_number_of(p).is_some())
+        {
+            self.sort_by(SortKey::TrackNumberInferred);
+        }
+
+        Ok(summary)
+    }
+
+    /// 重新扫描最近一次的文件夹：新文件追加到末尾，已删除的文件从列表中移除，
+    /// 播放模式和当前歌曲保持不变。返回 (新增数量, 移除数量, 扫描结果)，尚未扫描过文件夹时返回 None
+    pub fn reload(
+        &mut self,
+        on_progress: &mut dyn FnMut(usize, usize),
+    ) -> Option<(usize, usize, ScanSummary)> {
+        let folder = self.folder.clone()?;
+        let (found, summary) = scan_audio_files(
+            &folder,
+            self.scan_depth,
+            &self.rescan_options(),
+            on_progress,
+        );
+
+        // 扫描提前停止时无法判断哪些文件已被删除，只追加新文件；
+        // 通过 /add、/merge 加入的文件夹外的歌曲不受影响
+        let mut removed = 0;
+        if summary.stopped.is_none() {
+            let found_set: HashSet<&PathBuf> = found.iter().collect();
+            let current_path = self.current.and_then(|i| self.items.get(i)).cloned();
+            let before = self.items.len();
+            self.items
+                .retain(|p| !p.starts_with(&folder) || found_set.contains(p));
+            removed = before - self.items.len();
+            self.current = current_path.and_then(|cur| self.items.iter().position(|p| *p == cur));
+        }
+        let (added, _) = self.merge(found);
+        Some((added, removed, summary))
+    }
+
+    /// 按文件夹监视报告的变化路径更新列表：已不存在的文件（包括被删除文件夹中的文件）移除，
+    /// 新出现的歌曲或文件夹中的歌曲追加到末尾，当前歌曲保持不变。返回 (新增数量, 移除数量)
+    pub fn apply_folder_changes(&mut self, paths: &[PathBuf]) -> (usize, usize) {
+        let Some(folder) = self.folder.clone() else {
+            return (0, 0);
+        };
+        let options = self.rescan_options();
+        let current_path = self.current.and_then(|i| self.items.get(i)).cloned();
+
+        let gone: Vec<&PathBuf> = paths.iter().filter(|p| !p.exists()).collect();
+        let before = self.items.len();
+        self.items
+            .retain(|item| !gone.iter().any(|p| item.starts_with(p)));
+        let removed = before - self.items.len();
+
+        // 与扫描时使用相同的深度、隐藏文件和忽略规则
+        let mut found = Vec::new();
+        for path in paths.iter().filter(|p| p.exists()) {
+            let Ok(relative) = path.strip_prefix(&folder) else {
+                continue;
+            };
+            let depth = relative.components().count();
+            if depth == 0 || depth > self.scan_depth || !is_included(relative, &options) {
+                continue;
+            }
+            if path.is_dir() {
+                let (files, _) = collect_audio_files(path, self.scan_depth - depth, &options);
+                found.extend(files.into_iter().filter(|f| {
+                    f.strip_prefix(&folder)
+                        .is_ok_and(|r| is_included(r, &options))
+                }));
+            } else if path.is_file() && options.is_audio(path) {
+                found.push(path.clone());
+            } else if path.is_file() && options.split_cue && cue::is_cue(path) {
+                // 新出现的 cue 把列表中的整轨文件替换为音轨
+                let items = std::mem::take(&mut self.items);
+                self.items = cue::expand(items, std::slice::from_ref(path));
+            }
+        }
+        let (added, _) = self.merge(found);
+
+        self.current = current_path.and_then(|cur| self.items.iter().position(|p| *p == cur));
+        self.shuffle_bag.clear();
+        (added, removed)
+    }
+
+    // 清空列表和播放位置，播放模式恢复为默认值
+    fn reset(&mut self) {
         self.items.clear();
         self.current = None;
-        self.mode = PlaybackMode::Sequential;
-        for entry in WalkDir::new(folder).into_iter().filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.is_file() && is_audio(path) {
-                self.items.push(path.to_path_buf());
-            }
+        self.shuffle_bag.clear();
+        self.clear_queue();
+        self.mode = self.default_mode;
+    }
+
+    /// 用检查点中的列表、当前位置和播放模式替换当前列表
+    pub fn restore(&mut self, items: Vec<PathBuf>, current: Option<usize>, mode: PlaybackMode) {
+        self.clear();
+        self.current = current.filter(|&i| i < items.len());
+        self.items = items;
+        self.mode = mode;
+    }
+
+    /// 清空播放列表和队列，保留播放模式和扫描设置，返回删除的歌曲数
+    pub fn clear(&mut self) -> usize {
+        let removed = self.items.len();
+        self.items.clear();
+        self.current = None;
+        self.shuffle_bag.clear();
+        self.clear_queue();
+        removed
+    }
+
+    /// 最近一次扫描实际使用的选项，重新扫描同一文件夹（如 /diff）时使用
+    pub fn rescan_options(&self) -> ScanOptions {
+        ScanOptions {
+            follow_symlinks: self.scan_symlinks,
+            include_hidden: self.scan_hidden,
+            // 有声书模式下 cue 用作章节，整轨文件不拆分
+            split_cue: self.scan_folder_options.split_cue && self.mode != PlaybackMode::Audiobook,
+            ..self.scan_folder_options.clone()
         }
-        Ok(self.items.len())
     }
 
-    pub fn list(&self) -> Vec<(usize, std::path::PathBuf, bool)> {
-        // 返回 (索引, 文件路径, 是否当前播放)
-        self.items
-            .iter()
-            .enumerate()
-            .map(|(i, p)| (i, p.clone(), Some(i) == self.current))
-            .collect()
+    /// 每页 page_size 首时的总页数
+    pub fn page_count(&self, page_size: usize) -> usize {
+        self.items.len().div_ceil(page_size)
+    }
+
+    /// 当前歌曲所在的页（从 1 开始），没有当前歌曲时为第 1 页
+    pub fn current_page(&self, page_size: usize) -> usize {
+        self.current.map_or(0, |i| i / page_size) + 1
     }
 
     pub fn search(&self, q: &str) -> Vec<(usize, std::path::PathBuf)> {
@@ -66,58 +327,346 @@ impl Playlist {
             .collect()
     }
 
+    /// 按名称选歌（不区分大小写）：文件名或去掉扩展名后与 q 相同的优先，其次是以 q 开头的，
+    /// 最后是包含 q 的。最优的一档只有一首时返回其下标，否则返回这一档的全部候选（没有匹配时为空）
+    pub fn match_name(&self, q: &str) -> Result<usize, Vec<(usize, PathBuf)>> {
+        let ql = q.to_lowercase();
+        let rank = |p: &Path| {
+            let name = p.file_name()?.to_str()?.to_lowercase();
+            let stem = p
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+            if name == ql || stem == ql {
+                Some(0)
+            } else if name.starts_with(&ql) {
+                Some(1)
+            } else if name.contains(&ql) {
+                Some(2)
+            } else {
+                None
+            }
+        };
+        let ranked: Vec<(u8, usize)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| rank(p).map(|r| (r, i)))
+            .collect();
+        let Some(best) = ranked.iter().map(|(r, _)| *r).min() else {
+            return Err(Vec::new());
+        };
+        let top: Vec<usize> = ranked
+            .into_iter()
+            .filter(|(r, _)| *r == best)
+            .map(|(_, i)| i)
+            .collect();
+        match top.as_slice() {
+            [only] => Ok(*only),
+            _ => Err(top
+                .into_iter()
+                .map(|i| (i, self.items[i].clone()))
+                .collect()),
+        }
+    }
+
+    /// 按指定方式排序，current 随原歌曲一起移动
+    pub fn sort_by(&mut self, key: SortKey) {
+        let current_path = self.current.and_then(|i| self.items.get(i)).cloned();
+        match key {
+            SortKey::Name => self.items.sort_by_key(|p| {
+                p.file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("")
+                    .to_lowercase()
+            }),
+            // 同一目录内按音轨号排序，无音轨号的排在该目录末尾
+            SortKey::TrackNumberInferred => self.items.sort_by_key(|p| {
+                (
+                    p.parent().map(Path::to_path_buf),
+                    track_number_of(p).unwrap_or(u32::MAX),
+                    p.clone(),
+                )
+            }),
+        }
+        self.current = current_path.and_then(|cur| self.items.iter().position(|p| *p == cur));
+        // 下标已经变化，洗牌袋需要重建
+        self.shuffle_bag.clear();
+    }
+
+    /// 删除第 idx 首（从 0 开始），返回删除的条目。
+    /// 删除当前歌曲时 current 指向它的上一首，顺序播放时接着播放它后面的歌曲
+    pub fn remove(&mut self, idx: usize) -> Option<PathBuf> {
+        if idx >= self.items.len() {
+            return None;
+        }
+        let removed = self.items.remove(idx);
+        self.current = match self.current {
+            Some(cur) if cur > idx => Some(cur - 1),
+            Some(cur) if cur == idx => cur.checked_sub(1),
+            other => other,
+        };
+        self.shuffle_bag.clear();
+        Some(removed)
+    }
+
+    /// 把歌曲按顺序插入到播放队列最前面，在队列中原有的歌曲之前播放
+    pub fn queue_front(&mut self, entries: Vec<PathBuf>) {
+        for entry in entries.into_iter().rev() {
+            self.queue.push_front(entry);
+        }
+    }
+
+    /// 用给定的列表替换播放列表（/undo），当前歌曲仍在列表中时保持指向它
+    pub fn restore_items(&mut self, items: Vec<PathBuf>) {
+        let current_path = self.current.and_then(|i| self.items.get(i)).cloned();
+        self.items = items;
+        self.current = current_path.and_then(|cur| self.items.iter().position(|p| *p == cur));
+        self.shuffle_bag.clear();
+    }
+
+    /// 删除重复项，返回删除的数量。
+    /// strict 时只删除规范路径相同的文件，否则同时删除去掉音轨号后同名的文件；
+    /// 当前歌曲被删除时，current 指向保留下来的那一份
+    pub fn dedup(&mut self, strict: bool) -> usize {
+        let mut seen_paths: HashMap<PathBuf, usize> = HashMap::new();
+        let mut seen_names: HashMap<String, usize> = HashMap::new();
+        let mut kept: Vec<PathBuf> = Vec::with_capacity(self.items.len());
+        let mut new_current = None;
+
+        for (i, path) in self.items.iter().enumerate() {
+            let name = normalized_name(path);
+            let existing = seen_paths.get(&canonical_key(path)).copied().or_else(|| {
+                if strict || name.is_empty() {
+                    None
+                } else {
+                    seen_names.get(&name).copied()
+                }
+            });
+
+            let idx = match existing {
+                Some(idx) => idx,
+                None => {
+                    let idx = kept.len();
+                    seen_paths.insert(canonical_key(path), idx);
+                    seen_names.entry(name).or_insert(idx);
+                    kept.push(path.clone());
+                    idx
+                }
+            };
+            if self.current == Some(i) {
+                new_current = Some(idx);
+            }
+        }
+
+        let removed = self.items.len() - kept.len();
+        self.items = kept;
+        self.current = new_current;
+        self.shuffle_bag.clear();
+        removed
+    }
+
+    /// 追加歌曲，跳过规范路径已在列表中的文件，返回 (新增数量, 跳过的重复数量)
+    pub fn merge(&mut self, paths: Vec<PathBuf>) -> (usize, usize) {
+        let mut seen: HashSet<PathBuf> = self.items.iter().map(|p| canonical_key(p)).collect();
+        let mut added = 0;
+        let mut skipped = 0;
+        for path in paths {
+            if seen.insert(canonical_key(&path)) {
+                self.items.push(path);
+                added += 1;
+            } else {
+                skipped += 1;
+            }
+        }
+        self.shuffle_bag.clear();
+        (added, skipped)
+    }
+
+    /// 随机打乱整个列表顺序，current 随原歌曲一起移动
+    pub fn shuffle_order(&mut self) {
+        let mut order: Vec<usize> = (0..self.items.len()).collect();
+        order.shuffle(&mut thread_rng());
+
+        self.items = order.iter().map(|&i| self.items[i].clone()).collect();
+        self.current = self
+            .current
+            .and_then(|cur| order.iter().position(|&i| i == cur));
+        self.shuffle_bag.clear();
+    }
+
+    /// 随机选择一首歌曲（排除当前歌曲，其余歌曲概率相等）
+    pub fn random_index(&self) -> Option<usize> {
+        let len = self.items.len();
+        match (len, self.current) {
+            (0, _) => None,
+            (1, _) => Some(0),
+            (_, Some(cur)) if cur < len => {
+                let i = thread_rng().gen_range(0..len - 1);
+                Some(if i >= cur { i + 1 } else { i })
+            }
+            _ => Some(thread_rng().gen_range(0..len)),
+        }
+    }
+
     pub fn get(&self, idx: usize) -> Option<&PathBuf> {
         self.items.get(idx)
     }
 
-    fn next_index_step(&self) -> Option<usize> {
+    /// 检查从 1 开始、含两端的范围：起始不大于结束且不超出列表，无效时返回错误提示
+    pub fn check_range(&self, from: usize, to: usize) -> Result<(), String> {
+        let len = self.items.len();
+        if from > to {
+            Err(tr!(Msg::InvalidRange, from, to))
+        } else if to > len {
+            Err(tr!(Msg::IndexOutOfRange, len, len))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// 把 path 设为当前歌曲，返回它的下标。idx 是之前加锁时读到的位置，
+    /// 期间列表被文件夹监视等修改时按路径重新查找；歌曲已被移除时 current 为 None
+    pub fn set_current(&mut self, idx: usize, path: &Path) -> Option<usize> {
+        self.current = if self.items.get(idx).is_some_and(|p| p == path) {
+            Some(idx)
+        } else {
+            self.items.iter().position(|p| p == path)
+        };
+        self.current
+    }
+
+    /// 随机抽取 n 首不重复的歌曲加入队列（超过列表长度时取全部），返回实际加入的数量。
+    /// looped 为 true 时，队列播完后自动重新抽取同样数量的歌曲
+    pub fn sample(&mut self, n: usize, looped: bool) -> usize {
+        let n = n.min(self.items.len());
+        let picked = rand::seq::index::sample(&mut thread_rng(), self.items.len(), n);
+        self.queue
+            .extend(picked.iter().map(|i| self.items[i].clone()));
+        self.sample_loop = looped.then_some(n);
+        n
+    }
+
+    /// 清空队列并停止循环抽样，返回清除的歌曲数
+    pub fn clear_queue(&mut self) -> usize {
+        let cleared = self.queue.len();
+        self.queue.clear();
+        self.sample_loop = None;
+        cleared
+    }
+
+    // 队列中下一首的下标（不取出）：跳过已不在列表中的歌曲，循环抽样时在队列空后重新抽取
+    fn queue_peek(&mut self) -> Option<usize> {
+        if self.queue.is_empty()
+            && let Some(n) = self.sample_loop
+        {
+            self.sample(n, true);
+        }
+        while let Some(path) = self.queue.front() {
+            if let Some(i) = self.items.iter().position(|p| p == path) {
+                return Some(i);
+            }
+            self.queue.pop_front();
+        }
+        None
+    }
+
+    // 与 queue_peek 相同，但会把歌曲从队列中取出
+    fn queue_take(&mut self) -> Option<usize> {
+        let next = self.queue_peek()?;
+        self.queue.pop_front();
+        Some(next)
+    }
+
+    // 随机模式下的下一首：取洗牌袋末尾（不弹出），袋空时重新洗牌
+    fn shuffle_peek(&mut self) -> Option<usize> {
+        let len = self.items.len();
+        let current = self.current;
+        self.shuffle_bag.retain(|&i| i < len && Some(i) != current);
+        if self.shuffle_bag.is_empty() {
+            self.shuffle_bag = (0..len).filter(|&i| Some(i) != current).collect();
+            self.shuffle_bag.shuffle(&mut thread_rng());
+        }
+        self.shuffle_bag.last().copied().or(current)
+    }
+
+    // 与 shuffle_peek 相同，但会把选中的下标从洗牌袋中取出
+    fn shuffle_take(&mut self) -> Option<usize> {
+        let next = self.shuffle_peek()?;
+        if self.shuffle_bag.last() == Some(&next) {
+            self.shuffle_bag.pop();
+        }
+        Some(next)
+    }
+
+    /// 上一首的下标；顺序、单曲循环和有声书模式下在第一首时不回到最后一首，返回 None
+    pub fn prev_index(&self) -> Option<usize> {
         if self.items.is_empty() {
             return None;
         }
         match self.mode {
-            PlaybackMode::Sequential => {
-                let i = self.current.unwrap_or(0);
-                Some((i + 1) % self.items.len())
-            }
-            PlaybackMode::RepeatOne => self.current,
-            PlaybackMode::Shuffle => {
-                let mut rng = thread_rng();
-                let mut choices: Vec<usize> = (0..self.items.len()).collect();
-                if let Some(cur) = self.current {
-                    choices.retain(|&x| x != cur);
-                }
-                choices.choose(&mut rng).copied().or(self.current)
+            PlaybackMode::Sequential | PlaybackMode::RepeatOne | PlaybackMode::Audiobook => {
+                self.current.unwrap_or(0).checked_sub(1)
             }
+            PlaybackMode::Shuffle => self.random_index(),
         }
     }
 
-    pub fn prev_index(&self) -> Option<usize> {
+    pub fn next_index(&mut self) -> Option<usize> {
         if self.items.is_empty() {
             return None;
         }
+        if let Some(next) = self.queue_take() {
+            return Some(next);
+        }
         match self.mode {
-            PlaybackMode::Sequential | PlaybackMode::RepeatOne => {
+            PlaybackMode::Sequential | PlaybackMode::Audiobook => {
                 let i = self.current.unwrap_or(0);
-                Some(if i == 0 { self.items.len() - 1 } else { i - 1 })
+                Some((i + 1) % self.items.len())
             }
-            PlaybackMode::Shuffle => self.next_index_step(),
+            PlaybackMode::RepeatOne => self.current.filter(|&i| i < self.items.len()),
+            PlaybackMode::Shuffle => self.shuffle_take(),
         }
     }
-    pub fn current_index(&self) -> Option<usize> {
-        self.current
+
+    /// /next 切换到的歌曲：在同一次加锁中取出下标和路径并设为当前歌曲，
+    /// 避免放锁后文件夹监视、/remove 等修改列表使下标失效
+    pub fn step_next(&mut self) -> Option<(usize, PathBuf)> {
+        let next = self.next_index();
+        self.select(next)
     }
 
-    pub fn next_index(&mut self) -> Option<usize> {
-        self.next_index_step()
+    /// /prev 切换到的歌曲，与 step_next 相同
+    pub fn step_prev(&mut self) -> Option<(usize, PathBuf)> {
+        let prev = self.prev_index();
+        self.select(prev)
     }
 
-    /// 播放结束后，根据模式推进 current，并返回要播放的下标
+    /// 播放结束后接着播放的歌曲，与 step_next 相同
+    pub fn step_finished(&mut self) -> Option<(usize, PathBuf)> {
+        let next = self.advance_on_finished();
+        self.select(next)
+    }
+
+    fn select(&mut self, idx: Option<usize>) -> Option<(usize, PathBuf)> {
+        let idx = idx?;
+        let path = self.items.get(idx)?.clone();
+        self.current = Some(idx);
+        Some((idx, path))
+    }
+
+    /// 播放结束后，优先取队列中的歌曲，否则根据模式推进 current，返回要播放的下标
     pub fn advance_on_finished(&mut self) -> Option<usize> {
         if self.items.is_empty() {
             return None;
         }
+        if let Some(next) = self.queue_take() {
+            self.current = Some(next);
+            return Some(next);
+        }
         match self.mode {
-            PlaybackMode::Sequential => {
+            PlaybackMode::Sequential | PlaybackMode::Audiobook => {
                 let next = match self.current {
                     Some(i) => (i + 1) % self.items.len(),
                     None => 0,
@@ -125,25 +674,31 @@ impl Playlist {
                 self.current = Some(next);
                 Some(next)
             }
-            PlaybackMode::RepeatOne => self.current,
+            PlaybackMode::RepeatOne => self.current.filter(|&i| i < self.items.len()),
             PlaybackMode::Shuffle => {
-                let mut rng = thread_rng();
-                let mut choices: Vec<usize> = (0..self.items.len()).collect();
-                if let Some(cur) = self.current {
-                    choices.retain(|&x| x != cur);
-                }
-                let next = choices.choose(&mut rng).copied().or(self.current)?;
+                let next = self.shuffle_take()?;
                 self.current = Some(next);
                 Some(next)
             }
         }
     }
 
-    pub fn peek_next_name(&self) -> String {
+    /// 下一首的文件名，与之后实际播放的歌曲一致
+    pub fn peek_next_name(&mut self) -> String {
         if self.items.is_empty() {
             return String::new();
         }
-        let next = self.next_index_step();
+        let next = match self.queue_peek() {
+            Some(queued) => Some(queued),
+            None => match self.mode {
+                PlaybackMode::Sequential | PlaybackMode::Audiobook => {
+                    let i = self.current.unwrap_or(0);
+                    Some((i + 1) % self.items.len())
+                }
+                PlaybackMode::RepeatOne => self.current,
+                PlaybackMode::Shuffle => self.shuffle_peek(),
+            },
+        };
         match next.and_then(|i| self.items.get(i)) {
             Some(p) => p
                 .file_name()
@@ -155,32 +710,614 @@ impl Playlist {
     }
 
     pub fn clone_view(&self) -> PlaylistView {
-        let now_name = match self.current.and_then(|i| self.items.get(i)) {
-            Some(p) => p
-                .file_name()
-                .and_then(|s| s.to_str())
-                .unwrap_or("")
-                .to_string(),
-            None => String::new(),
-        };
-        let next_name = self.peek_next_name();
+        let len = self.items.len();
+        let page = self.current_page(PANE_ENTRIES);
+        let start = (page - 1) * PANE_ENTRIES;
+        let end = (start + PANE_ENTRIES).min(len);
+        let window = (start..end)
+            .map(|i| {
+                let name = self.items[i]
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("")
+                    .to_string();
+                (i, name)
+            })
+            .collect();
         PlaylistView {
-            len: self.items.len(),
+            len,
             current: self.current,
-            mode: self.mode,
-            now_name,
-            next_name,
+            window,
+            page,
+            pages: self.page_count(PANE_ENTRIES),
+        }
+    }
+}
+
+// 扫描进度的报告间隔
+const SCAN_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 递归收集文件夹中的音频文件。遍历的条目数或耗时超出限制时停止，保留已找到的文件
+pub fn collect_audio_files(
+    folder: &Path,
+    max_depth: usize,
+    options: &ScanOptions,
+) -> (Vec<PathBuf>, ScanSummary) {
+    scan_audio_files(folder, max_depth, options, &mut |_, _| {})
+}
+
+// 与 collect_audio_files 相同，每隔 SCAN_PROGRESS_INTERVAL 调用一次 on_progress(已遍历的条目数, 已找到的音频数)
+fn scan_audio_files(
+    folder: &Path,
+    max_depth: usize,
+    options: &ScanOptions,
+    on_progress: &mut dyn FnMut(usize, usize),
+) -> (Vec<PathBuf>, ScanSummary) {
+    let mut files = Vec::new();
+    let mut cues = Vec::new();
+    let mut summary = ScanSummary::default();
+    let started = Instant::now();
+    let mut reported = started;
+    let mut skipped_hidden = 0;
+    let walker = WalkDir::new(folder)
+        .max_depth(max_depth)
+        .follow_links(options.follow_symlinks)
+        // 不跟随符号链接时，扫描的根目录本身仍可以是符号链接
+        .follow_root_links(true)
+        .into_iter()
+        .filter_entry(|e| {
+            if e.depth() == 0 {
+                return true;
+            }
+            // 隐藏的文件夹整个跳过，其中的文件不再遍历
+            if !options.include_hidden && is_hidden(e) {
+                skipped_hidden += 1;
+                return false;
+            }
+            let relative = e.path().strip_prefix(folder).unwrap_or(e.path());
+            !options.ignore.is_match(relative)
+        });
+
+    for (visited, entry) in walker.filter_map(|e| e.ok()).enumerate() {
+        if visited >= options.max_entries {
+            summary.stopped = Some(ScanLimit::Entries(visited));
+            break;
+        }
+        if started.elapsed() > options.timeout {
+            summary.stopped = Some(ScanLimit::Timeout(options.timeout));
+            break;
+        }
+        if reported.elapsed() >= SCAN_PROGRESS_INTERVAL {
+            reported = Instant::now();
+            on_progress(visited, files.len());
+        }
+
+        let path = entry.path();
+        if entry.depth() > 0 && entry.path_is_symlink() && !options.follow_symlinks && path.is_dir()
+        {
+            summary.skipped_links += 1;
+            continue;
+        }
+        if !path.is_file() {
+            continue;
+        }
+        if options.is_audio(path) {
+            files.push(path.to_path_buf());
+        } else if options.split_cue && cue::is_cue(path) {
+            cues.push(path.to_path_buf());
+        } else if is_unsupported_audio(path) {
+            summary.unsupported += 1;
+        }
+    }
+    let files = cue::expand(files, &cues);
+    summary.added = files.len();
+    summary.skipped_hidden = skipped_hidden;
+    (files, summary)
+}
+
+// 相对扫描文件夹的路径是否会被扫描收录：不在隐藏目录中（除非包含隐藏文件），且不匹配忽略规则
+fn is_included(relative: &Path, options: &ScanOptions) -> bool {
+    let hidden = relative
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+    if hidden && !options.include_hidden {
+        return false;
+    }
+    !relative
+        .ancestors()
+        .filter(|a| !a.as_os_str().is_empty())
+        .any(|a| options.ignore.is_match(a))
+}
+
+/// 是否为隐藏文件或文件夹：名称以 . 开头，或在 Windows 上带有隐藏属性
+fn is_hidden(entry: &walkdir::DirEntry) -> bool {
+    if entry.file_name().to_string_lossy().starts_with('.') {
+        return true;
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if let Ok(meta) = entry.metadata()
+            && meta.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// 从文件名中提取音轨号，支持 `01 - Song`、`Track 01 Song`、`3. Song` 等格式
+pub fn extract_track_number(filename: &str) -> Option<u32> {
+    let stem = Path::new(filename)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(filename);
+    split_track_number(stem).map(|(n, _)| n)
+}
+
+// 拆分文件名主干中的音轨号前缀，返回 (音轨号, 剩余部分)
+fn split_track_number(stem: &str) -> Option<(u32, &str)> {
+    let stem = stem.trim();
+    let rest = match stem.get(..5) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("track") => {
+            stem[5..].trim_start_matches([' ', '_', '-', '.', '#'])
+        }
+        _ => stem,
+    };
+
+    let digits_len = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    // 超过 3 位的数字更可能是年份等信息，而不是音轨号
+    if digits_len == 0 || digits_len > 3 {
+        return None;
+    }
+
+    // 数字之后必须是分隔符或结尾，避免把 "2Pac" 之类的名字当成音轨号
+    match rest[digits_len..].chars().next() {
+        None | Some(' ' | '.' | '-' | '_' | ')') => {
+            let number = rest[..digits_len].parse().ok()?;
+            let name = rest[digits_len..].trim_start_matches([' ', '.', '-', '_', ')']);
+            Some((number, name))
+        }
+        _ => None,
+    }
+}
+
+// 用于判断重复的规范路径，无法解析时退回原路径
+pub fn canonical_key(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+// 用于判断同名重复的歌曲名：去掉音轨号前缀后转为小写
+fn normalized_name(path: &Path) -> String {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let name = split_track_number(stem).map_or(stem, |(_, rest)| rest);
+    name.trim().to_lowercase()
+}
+
+fn track_number_of(path: &Path) -> Option<u32> {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .and_then(extract_track_number)
+}
+
+// JSON 会话文件（如检查点 .checkpoint.json）中只取播放列表，其他字段忽略
+#[derive(Deserialize)]
+struct SessionFile {
+    items: Vec<String>,
+}
+
+/// 读取播放列表文件（M3U/M3U8/XSPF/JSON 会话），相对路径相对于播放列表所在目录解析，
+/// 只保留存在的音频文件
+pub fn load_playlist_file(path: &Path, options: &ScanOptions) -> anyhow::Result<Vec<PathBuf>> {
+    let content = std::fs::read_to_string(path)?;
+    let entries: Vec<String> = match extension_lowercase(path).as_deref() {
+        Some("m3u" | "m3u8") => content
+            .lines()
+            .map(|l| l.trim().trim_start_matches('\u{feff}'))
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(file_url_to_path)
+            .collect(),
+        Some("xspf") => content
+            .split("<location>")
+            .skip(1)
+            .filter_map(|s| s.split_once("</location>"))
+            .map(|(location, _)| file_url_to_path(&xml_unescape(location.trim())))
+            .collect(),
+        Some("json") => serde_json::from_str::<SessionFile>(&content)?.items,
+        _ => anyhow::bail!(tr!(Msg::UnsupportedPlaylist)),
+    };
+
+    let base = path.parent().unwrap_or(Path::new(""));
+    Ok(entries
+        .into_iter()
+        .map(|entry| base.join(entry))
+        .filter(|p| p.is_file() && options.is_audio(p))
+        .collect())
+}
+
+// file:// URL 转为本地路径（解码 %XX），普通路径原样返回
+fn file_url_to_path(entry: &str) -> String {
+    let Some(rest) = entry.strip_prefix("file://") else {
+        return entry.to_string();
+    };
+    // Windows 的 file:///C:/Music 需要去掉盘符前的斜杠
+    let rest = match rest.as_bytes() {
+        [b'/', _, b':', ..] => &rest[1..],
+        _ => rest,
+    };
+
+    let bytes = rest.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
         }
     }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
 }
 
-pub fn is_audio(path: &Path) -> bool {
-    match path
-        .extension()
+fn extension_lowercase(path: &Path) -> Option<String> {
+    path.extension()
         .and_then(|s| s.to_str())
         .map(|s| s.to_lowercase())
-    {
-        Some(ext) if matches!(ext.as_str(), "mp3" | "flac" | "wav" | "ogg" | "m4a" | "aac") => true,
-        _ => false,
+}
+
+/// 常见的音频格式，但当前解码器无法播放
+fn is_unsupported_audio(path: &Path) -> bool {
+    extension_lowercase(path).is_some_and(|ext| {
+        matches!(
+            ext.as_str(),
+            "wma" | "opus" | "aiff" | "aif" | "ape" | "webm" | "mka" | "dsf"
+        ) && !decode::extra_extensions().contains(&ext.as_str())
+    })
+}
+
+/// 解析用户输入的路径：展开 `~`、`$VAR` 与 `%VAR%` 环境变量，去掉结尾的路径分隔符，
+/// 并转换为绝对路径（引号由命令解析时的 tokenize 处理）
+pub fn resolve_path(raw: &str) -> PathBuf {
+    let raw = expand_percent_vars(raw.trim());
+
+    // 环境变量不存在时只展开 `~`，保留原样的 $VAR 交给后续的存在性检查报错
+    let expanded = shellexpand::full(&raw)
+        .map(|s| s.into_owned())
+        .unwrap_or_else(|_| shellexpand::tilde(&raw).into_owned());
+
+    // 保留根目录（`/`、`C:\`）本身的分隔符
+    let trimmed = expanded.trim_end_matches(['/', '\\']);
+    let trimmed = if trimmed.is_empty() || trimmed.ends_with(':') {
+        expanded.as_str()
+    } else {
+        trimmed
+    };
+
+    let path = PathBuf::from(trimmed);
+    std::path::absolute(&path).unwrap_or(path)
+}
+
+// 展开 Windows 风格的 %VAR%，变量不存在时保留原文
+fn expand_percent_vars(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        let after = &rest[start + 1..];
+        let Some(len) = after.find('%') else {
+            break;
+        };
+        let name = &after[..len];
+        result.push_str(&rest[..start]);
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => {
+                result.push_str(&value);
+                rest = &after[len + 1..];
+            }
+            _ => {
+                result.push('%');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_numbers_from_file_names() {
+        assert_eq!(extract_track_number("01 - Song.mp3"), Some(1));
+        assert_eq!(extract_track_number("Track 12.flac"), Some(12));
+        assert_eq!(extract_track_number("3. Another.wav"), Some(3));
+        assert_eq!(extract_track_number("07_intro.ogg"), Some(7));
+        assert_eq!(extract_track_number("NakedFilename.ogg"), None);
+        assert_eq!(extract_track_number("2Pac - Changes.mp3"), None);
+        assert_eq!(extract_track_number("1999 - Prince.mp3"), None);
+    }
+
+    #[test]
+    fn sort_by_inferred_track_number() {
+        let mut pl = Playlist {
+            items: ["10 - j.mp3", "2 - b.mp3", "1 - a.mp3"]
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            current: Some(0),
+            ..Playlist::default()
+        };
+        pl.sort_by(SortKey::TrackNumberInferred);
+        assert_eq!(
+            pl.items,
+            ["1 - a.mp3", "2 - b.mp3", "10 - j.mp3"].map(PathBuf::from)
+        );
+        // 当前歌曲跟随排序移动
+        assert_eq!(pl.current, Some(2));
+    }
+
+    fn playlist(items: &[&str], current: Option<usize>) -> Playlist {
+        Playlist {
+            items: items.iter().map(PathBuf::from).collect(),
+            current,
+            ..Playlist::default()
+        }
+    }
+
+    #[test]
+    fn dedup_removes_same_paths_and_same_names() {
+        let items = [
+            "/music/a/01 - Song.mp3",
+            "/music/a/02 - Other.mp3",
+            "/music/a/01 - Song.mp3",
+            "/music/b/song.flac",
+            "/music/b/Third.mp3",
+        ];
+
+        let mut pl = playlist(&items, Some(3));
+        assert_eq!(pl.dedup(false), 2);
+        assert_eq!(
+            pl.items,
+            [
+                "/music/a/01 - Song.mp3",
+                "/music/a/02 - Other.mp3",
+                "/music/b/Third.mp3"
+            ]
+            .map(PathBuf::from)
+        );
+        // 当前歌曲是被删除的同名歌曲，改为指向保留下来的那一份
+        assert_eq!(pl.current, Some(0));
+
+        let mut pl = playlist(&items, Some(4));
+        assert_eq!(pl.dedup(true), 1);
+        assert_eq!(pl.items.len(), 4);
+        assert_eq!(pl.current, Some(3));
+    }
+
+    fn file_name(pl: &Playlist, i: usize) -> String {
+        pl.items[i]
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn peeked_next_song_is_the_one_played_next() {
+        let names: Vec<String> = (1..=6).map(|i| format!("{:02}.mp3", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        for mode in [
+            PlaybackMode::Sequential,
+            PlaybackMode::RepeatOne,
+            PlaybackMode::Shuffle,
+        ] {
+            let mut pl = playlist(&names, Some(0));
+            pl.mode = mode;
+            pl.queue.push_back(PathBuf::from("04.mp3"));
+            // 多轮播放覆盖队列、洗牌袋用完后重新洗牌等情况
+            for _ in 0..20 {
+                let peeked = pl.peek_next_name();
+                let next = pl.advance_on_finished().unwrap();
+                assert_eq!(peeked, file_name(&pl, next), "{:?}", mode);
+            }
+        }
+    }
+
+    // 测试用的临时文件夹，每次重新创建
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("beatcli-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_loops_do_not_hang_the_scan() {
+        let root = temp_dir("symlink-loop");
+        let album = root.join("album");
+        std::fs::create_dir(&album).unwrap();
+        std::fs::write(album.join("01 - a.mp3"), b"").unwrap();
+        std::os::unix::fs::symlink(&root, album.join("loop")).unwrap();
+
+        let mut options = ScanningConfig::default().scan_options();
+        let (files, summary) = collect_audio_files(&root, usize::MAX, &options);
+        assert_eq!(files, [album.join("01 - a.mp3")]);
+        assert_eq!(summary.skipped_links, 1);
+
+        // 跟随链接时 walkdir 识别出循环并跳过
+        options.follow_symlinks = true;
+        let (files, _) = collect_audio_files(&root, usize::MAX, &options);
+        assert_eq!(files, [album.join("01 - a.mp3")]);
+
+        // 扫描的根目录本身是符号链接时仍然进入
+        options.follow_symlinks = false;
+        let link = root.with_extension("link");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&root, &link).unwrap();
+        let (files, _) = collect_audio_files(&link, usize::MAX, &options);
+        assert_eq!(files, [link.join("album").join("01 - a.mp3")]);
+
+        std::fs::remove_file(&link).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn set_current_finds_the_song_again_after_the_list_changed() {
+        let mut pl = playlist(&["/m/a.mp3", "/m/b.mp3", "/m/c.mp3"], None);
+        assert_eq!(pl.set_current(1, Path::new("/m/b.mp3")), Some(1));
+        // 下标之前的歌曲被移除，按路径重新查找
+        pl.remove(0);
+        assert_eq!(pl.set_current(1, Path::new("/m/b.mp3")), Some(0));
+        assert_eq!(pl.current, Some(0));
+        // 下标越界时同样按路径查找
+        assert_eq!(pl.set_current(5, Path::new("/m/c.mp3")), Some(1));
+        // 歌曲已被移除
+        pl.remove(1);
+        assert_eq!(pl.set_current(1, Path::new("/m/c.mp3")), None);
+        assert_eq!(pl.current, None);
+    }
+
+    #[test]
+    fn concurrent_next_and_remove_do_not_panic() {
+        use parking_lot::Mutex;
+        use std::sync::Arc;
+        use std::thread;
+
+        let items: Vec<String> = (0..50).map(|i| format!("/m/{i:02}.mp3")).collect();
+        let names: Vec<&str> = items.iter().map(String::as_str).collect();
+        let shared = Arc::new(Mutex::new(playlist(&names, Some(0))));
+
+        let mut handles = Vec::new();
+        for mode in [PlaybackMode::Sequential, PlaybackMode::Shuffle] {
+            let shared = Arc::clone(&shared);
+            handles.push(thread::spawn(move || {
+                for round in 0..2_000 {
+                    // 与 next_song、prev_song 和 advance_track 相同的调用
+                    let mut pl = shared.lock();
+                    pl.mode = mode;
+                    let picked = match round % 3 {
+                        0 => pl.step_next(),
+                        1 => pl.step_prev(),
+                        _ => pl.step_finished(),
+                    };
+                    if let Some((idx, path)) = picked {
+                        assert_eq!(pl.items[idx], path);
+                        assert_eq!(pl.current, Some(idx));
+                    }
+                    pl.peek_next_name();
+                    if let Some(prev) = pl.prev_index() {
+                        assert!(prev < pl.items.len());
+                    }
+                }
+            }));
+        }
+        {
+            let shared = Arc::clone(&shared);
+            handles.push(thread::spawn(move || {
+                for round in 0..2_000 {
+                    let mut pl = shared.lock();
+                    let len = pl.items.len();
+                    if len == 0 || round % 7 == 0 {
+                        pl.items.push(PathBuf::from(format!("/m/new{round}.mp3")));
+                        pl.queue_front(vec![PathBuf::from("/m/00.mp3")]);
+                    } else {
+                        pl.remove(round % len);
+                    }
+                }
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let pl = shared.lock();
+        assert!(pl.current.is_none_or(|i| i < pl.items.len()));
+    }
+
+    #[test]
+    fn prev_index_wraps_only_in_shuffle() {
+        let items = ["/m/a.mp3", "/m/b.mp3", "/m/c.mp3"];
+        for mode in [
+            PlaybackMode::Sequential,
+            PlaybackMode::RepeatOne,
+            PlaybackMode::Audiobook,
+        ] {
+            let mut pl = playlist(&items, Some(0));
+            pl.mode = mode;
+            assert_eq!(pl.prev_index(), None, "{mode:?}");
+            pl.current = Some(2);
+            assert_eq!(pl.prev_index(), Some(1), "{mode:?}");
+            pl.current = None;
+            assert_eq!(pl.prev_index(), None, "{mode:?}");
+        }
+
+        let mut pl = playlist(&items, Some(0));
+        pl.mode = PlaybackMode::Shuffle;
+        for _ in 0..20 {
+            assert!(matches!(pl.prev_index(), Some(1 | 2)));
+        }
+        pl.current = Some(2);
+        for _ in 0..20 {
+            assert!(matches!(pl.prev_index(), Some(0 | 1)));
+        }
+
+        for mode in [PlaybackMode::Sequential, PlaybackMode::Shuffle] {
+            let mut pl = playlist(&[], None);
+            pl.mode = mode;
+            assert_eq!(pl.prev_index(), None);
+        }
+    }
+
+    #[test]
+    fn ranges_must_be_ordered_and_inside_the_list() {
+        let pl = playlist(&["/m/a.mp3", "/m/b.mp3", "/m/c.mp3"], None);
+        assert!(pl.check_range(1, 3).is_ok());
+        assert!(pl.check_range(2, 2).is_ok());
+        assert!(pl.check_range(3, 2).is_err());
+        assert!(pl.check_range(2, 4).is_err());
+        assert!(playlist(&[], None).check_range(1, 1).is_err());
+    }
+
+    #[test]
+    fn json_sessions_are_read_like_playlists() {
+        let dir = temp_dir("session");
+        std::fs::write(dir.join("a.mp3"), b"").unwrap();
+        std::fs::write(dir.join("b.mp3"), b"").unwrap();
+        let a = dir.join("a.mp3").to_string_lossy().into_owned();
+        let session = serde_json::json!({
+            "now_index": 0,
+            "items": [a, "b.mp3", "missing.mp3"],
+            "current": 0,
+        });
+        let path = dir.join("session.json");
+        std::fs::write(&path, session.to_string()).unwrap();
+
+        let options = ScanningConfig::default().scan_options();
+        let files = load_playlist_file(&path, &options).unwrap();
+        assert_eq!(files, [dir.join("a.mp3"), dir.join("b.mp3")]);
+
+        // 没有播放列表的 JSON 不是会话文件
+        std::fs::write(&path, "{}").unwrap();
+        assert!(load_playlist_file(&path, &options).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+// 标签最多读取这么多字节，避免带大封面的文件读取过多
+const MAX_TAG_SIZE: u64 = 16 * 1024 * 1024;
+// Ogg 文件只在开头这么多字节中查找注释头
+const OGG_SCAN_SIZE: u64 = 64 * 1024;
+
+/// 前置放大允许的范围（dB）
+pub const MIN_PREAMP: f32 = -15.0;
+pub const MAX_PREAMP: f32 = 15.0;
+
+/// ReplayGain 音量归一化模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RgMode {
+    #[default]
+    Off,
+    Track, // 每首歌单独归一化
+    Album, // 同一专辑保持相对音量，没有专辑增益时使用音轨增益
+}
+
+impl RgMode {
+    /// 解析模式名 off、track、album（不区分大小写）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "off" => Some(RgMode::Off),
+            "track" => Some(RgMode::Track),
+            "album" => Some(RgMode::Album),
+            _ => None,
+        }
+    }
+}
+
+/// 读取文件的 ReplayGain 标签 (音轨增益 dB, 专辑增益 dB)。
+/// 支持 MP3 的 ID3v2 TXXX 帧和 FLAC / Ogg 的 Vorbis 注释，没有音轨增益时返回 None
+pub fn read_replaygain(path: &Path) -> Option<(f32, Option<f32>)> {
+    let tags = read_tags(path)?;
+    let find = |key: &str| {
+        tags.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .and_then(|(_, v)| parse_gain(v))
+    };
+    Some((
+        find("REPLAYGAIN_TRACK_GAIN")?,
+        find("REPLAYGAIN_ALBUM_GAIN"),
+    ))
+}
+
+/// 读取文件的全部文本标签 (名称, 值)：ID3v2 文本帧（常用帧换成 Vorbis 注释的名称，
+/// TXXX 使用其描述）或 FLAC / Ogg 的 Vorbis 注释。不是这几种格式时返回 None
+pub fn read_tags(path: &Path) -> Option<Vec<(String, String)>> {
+    let mut file = File::open(path).ok()?;
+    let mut magic = [0u8; 10];
+    file.read_exact(&mut magic).ok()?;
+    let tags = if magic.starts_with(b"ID3") {
+        let size = syncsafe(&magic[6..10]) as u64;
+        let mut body = Vec::new();
+        file.take(size.min(MAX_TAG_SIZE))
+            .read_to_end(&mut body)
+            .ok()?;
+        id3_text_frames(&body, magic[3])
+    } else if magic.starts_with(b"fLaC") {
+        let mut rest = magic[4..].to_vec();
+        file.take(MAX_TAG_SIZE).read_to_end(&mut rest).ok()?;
+        flac_comments(&rest)
+    } else if magic.starts_with(b"OggS") {
+        let mut rest = magic.to_vec();
+        file.take(OGG_SCAN_SIZE).read_to_end(&mut rest).ok()?;
+        ogg_comments(&rest)
+    } else {
+        return None;
+    };
+    Some(tags)
+}
+
+/// 按模式选择要使用的增益（dB），关闭或文件没有标签时为 None
+pub fn gain_db(mode: RgMode, path: &Path) -> Option<f32> {
+    if mode == RgMode::Off {
+        return None;
+    }
+    let (track, album) = read_replaygain(path)?;
+    match mode {
+        RgMode::Album => Some(album.unwrap_or(track)),
+        _ => Some(track),
+    }
+}
+
+/// dB 换算为线性音量倍数
+pub fn gain_factor(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+/// 增益的显示文本，例如 -6.5 dB、+2.0 dB
+pub fn format_gain(db: f32) -> String {
+    format!("{:+.1} dB", db)
+}
+
+// 标签值形如 "-6.54 dB"
+fn parse_gain(value: &str) -> Option<f32> {
+    let number = value
+        .trim()
+        .trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    number.trim().parse().ok().filter(|db: &f32| db.is_finite())
+}
+
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0, |acc, &b| (acc << 7) | (b & 0x7f) as u32)
+}
+
+// 常用的 ID3 文本帧对应的 Vorbis 注释名称
+const ID3_NAMES: [(&[u8], &str); 10] = [
+    (b"TIT2", "TITLE"),
+    (b"TPE1", "ARTIST"),
+    (b"TPE2", "ALBUMARTIST"),
+    (b"TALB", "ALBUM"),
+    (b"TRCK", "TRACKNUMBER"),
+    (b"TPOS", "DISCNUMBER"),
+    (b"TYER", "DATE"),
+    (b"TDRC", "DATE"),
+    (b"TCON", "GENRE"),
+    (b"TCOM", "COMPOSER"),
+];
+
+// ID3v2.3 / v2.4 的文本帧（T 开头）：编码 + 文本；TXXX 为编码 + 描述 + 值。
+// v2.4 的帧长度为 syncsafe 整数
+fn id3_text_frames(body: &[u8], version: u8) -> Vec<(String, String)> {
+    let mut tags = Vec::new();
+    if version < 3 {
+        return tags;
+    }
+    let mut pos = 0;
+    while pos + 10 <= body.len() {
+        let id = &body[pos..pos + 4];
+        if id[0] == 0 {
+            break; // 填充区
+        }
+        let size_bytes = &body[pos + 4..pos + 8];
+        let size = if version >= 4 {
+            syncsafe(size_bytes) as usize
+        } else {
+            u32::from_be_bytes(size_bytes.try_into().unwrap()) as usize
+        };
+        let start = pos + 10;
+        let Some(frame) = body.get(start..start + size) else {
+            break;
+        };
+        if id[0] == b'T'
+            && let Some((&encoding, text)) = frame.split_first()
+        {
+            let fields = decode_id3_text(encoding, text);
+            let mut fields = fields.split('\0');
+            if id == b"TXXX" {
+                if let (Some(desc), Some(value)) = (fields.next(), fields.next()) {
+                    tags.push((desc.to_string(), value.to_string()));
+                }
+            } else if let Some(value) = fields.next() {
+                let name = ID3_NAMES
+                    .iter()
+                    .find(|(frame_id, _)| *frame_id == id)
+                    .map(|(_, name)| name.to_string())
+                    .unwrap_or_else(|| String::from_utf8_lossy(id).into_owned());
+                tags.push((name, value.to_string()));
+            }
+        }
+        pos = start + size;
+    }
+    tags
+}
+
+// 编码：0 为 Latin-1，1 为带 BOM 的 UTF-16，2 为 UTF-16BE，3 为 UTF-8
+fn decode_id3_text(encoding: u8, bytes: &[u8]) -> String {
+    match encoding {
+        0 => bytes.iter().map(|&b| b as char).collect(),
+        1 | 2 => {
+            let mut big_endian = encoding == 2;
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .filter_map(|pair| match pair {
+                    [0xfe, 0xff] => {
+                        big_endian = true;
+                        None
+                    }
+                    [0xff, 0xfe] => {
+                        big_endian = false;
+                        None
+                    }
+                    [a, b] if big_endian => Some(u16::from_be_bytes([*a, *b])),
+                    [a, b] => Some(u16::from_le_bytes([*a, *b])),
+                    _ => None,
+                })
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+// FLAC 元数据块：1 字节（最高位为最后一块标记，低 7 位为类型）+ 3 字节长度，类型 4 为 Vorbis 注释
+fn flac_comments(data: &[u8]) -> Vec<(String, String)> {
+    let mut pos = 0;
+    while pos + 4 <= data.len() {
+        let header = data[pos];
+        let size = u32::from_be_bytes([0, data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let start = pos + 4;
+        let Some(block) = data.get(start..start + size) else {
+            break;
+        };
+        if header & 0x7f == 4 {
+            return vorbis_comments(block);
+        }
+        if header & 0x80 != 0 {
+            break;
+        }
+        pos = start + size;
+    }
+    Vec::new()
+}
+
+// Ogg Vorbis 的注释头以 \x03vorbis 开头，Opus 以 OpusTags 开头。
+// 不拆分 Ogg 页，注释头通常完整地位于文件开头的一页中
+fn ogg_comments(data: &[u8]) -> Vec<(String, String)> {
+    for magic in [&b"\x03vorbis"[..], &b"OpusTags"[..]] {
+        if let Some(pos) = data.windows(magic.len()).position(|w| w == magic) {
+            return vorbis_comments(&data[pos + magic.len()..]);
+        }
+    }
+    Vec::new()
+}
+
+// Vorbis 注释：厂商字符串，然后是若干 KEY=value，长度均为 32 位小端整数
+fn vorbis_comments(data: &[u8]) -> Vec<(String, String)> {
+    let mut tags = Vec::new();
+    let mut pos = 0;
+    if read_field(data, &mut pos).is_none() {
+        return tags;
+    }
+    let Some(count) = read_u32(data, &mut pos) else {
+        return tags;
+    };
+    for _ in 0..count {
+        let Some(field) = read_field(data, &mut pos) else {
+            break;
+        };
+        if let Some((key, value)) = String::from_utf8_lossy(field).split_once('=') {
+            tags.push((key.to_string(), value.to_string()));
+        }
+    }
+    tags
+}
+
+fn read_u32(data: &[u8], pos: &mut usize) -> Option<u32> {
+    let bytes = data.get(*pos..*pos + 4)?;
+    *pos += 4;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_field<'a>(data: &'a [u8], pos: &mut usize) -> Option<&'a [u8]> {
+    let len = read_u32(data, pos)? as usize;
+    let field = data.get(*pos..*pos + len)?;
+    *pos += len;
+    Some(field)
+}
@@ -0,0 +1,85 @@
+use crate::i18n::{Msg, tr};
+use crate::playlist::canonical_key;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+const SNAPSHOT_DIR: &str = ".beatcli";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+
+/// 播放列表快照：规范路径 -> 修改时间
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub files: HashMap<PathBuf, SystemTime>,
+}
+
+/// 当前文件与快照的差异
+#[derive(Debug, Default)]
+pub struct SnapshotDiff {
+    pub added: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+}
+
+impl SnapshotDiff {
+    pub fn total(&self) -> usize {
+        self.added.len() + self.removed.len() + self.modified.len()
+    }
+}
+
+impl Snapshot {
+    /// 快照文件路径：~/.beatcli/snapshot.json
+    pub fn path() -> Option<PathBuf> {
+        dirs::home_dir().map(|dir| dir.join(SNAPSHOT_DIR).join(SNAPSHOT_FILE))
+    }
+
+    pub fn from_files(paths: &[PathBuf]) -> Self {
+        let files = paths
+            .iter()
+            .filter_map(|p| {
+                let mtime = fs::metadata(p).and_then(|m| m.modified()).ok()?;
+                Some((canonical_key(p), mtime))
+            })
+            .collect();
+        Self { files }
+    }
+
+    pub fn load() -> anyhow::Result<Self> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!(tr!(Msg::NoHomeDir)))?;
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self) -> anyhow::Result<PathBuf> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!(tr!(Msg::NoHomeDir)))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// 与另一份快照比较：新增、删除、修改时间变化的文件
+    pub fn diff(&self, current: &Snapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+        for (path, mtime) in &current.files {
+            match self.files.get(path) {
+                None => diff.added.push(path.clone()),
+                Some(old) if old != mtime => diff.modified.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = self
+            .files
+            .keys()
+            .filter(|p| !current.files.contains_key(*p))
+            .cloned()
+            .collect();
+        diff.added.sort();
+        diff.removed.sort();
+        diff.modified.sort();
+        diff
+    }
+}
@@ -0,0 +1,396 @@
+use crate::i18n::{Msg, tr};
+use crate::player::PlayState;
+use crate::playlist::PlaylistView;
+use crate::ui::{
+    self, BorderStyle, FlashLevel, Renderer, UiState, debug_lines, format_time, lyric_rows,
+    mode_label, theme, volume_bar, vu_meter_lines,
+};
+use crossterm::style::Color;
+use ratatui::Frame;
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::symbols::border;
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table, TableState, Wrap};
+use std::io::{Stdout, stdout};
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
+// 输入框的高度（含边框）
+const INPUT_HEIGHT: u16 = 3;
+// 消息框最少占用的高度（含边框），多行消息最多占屏幕高度的 1/MESSAGE_MAX_RATIO
+const MESSAGE_MIN_HEIGHT: u16 = 3;
+const MESSAGE_MAX_RATIO: u16 = 3;
+// 播放列表占中间区域宽度的百分比
+const PLAYLIST_PERCENT: u16 = 40;
+
+/// 基于 ratatui 的全屏界面（--tui 或配置 ui.tui = true）：状态、进度、播放列表和歌词分区显示，
+/// 底部是提示消息和命令输入框。每次绘制都按 UiState 重新生成整个画面，由 ratatui 只输出变化的部分
+#[derive(Default)]
+pub struct Tui {
+    terminal: Option<Terminal<CrosstermBackend<Stdout>>>, // 第一次绘制时创建并进入备用屏幕
+    playlist: PlaylistView,                               // 上次绘制时的播放列表，只更新进度时沿用
+    message: Option<(String, FlashLevel)>,                // 最近一条提示消息，显示到下一条消息为止
+    redraws: usize,
+}
+
+impl Tui {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 重新生成整个画面
+    fn render(&mut self, ui: &UiState) -> std::io::Result<()> {
+        if self.terminal.is_none() {
+            ui::activate_alt_screen()?;
+            let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+            terminal.clear()?;
+            self.terminal = Some(terminal);
+        }
+        let (pl, message) = (&self.playlist, &self.message);
+        if let Some(terminal) = &mut self.terminal {
+            terminal.draw(|frame| draw_frame(frame, ui, pl, message.as_ref()))?;
+        }
+        self.redraws += 1;
+        Ok(())
+    }
+}
+
+impl Renderer for Tui {
+    fn draw(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        if let Some(flash) = ui.next_flash() {
+            self.message = Some(flash);
+        }
+        // 播放线程据此决定是否发送进度、电平等更新
+        ui.playing_ui_active = ui.now_index.is_some();
+        self.playlist = pl.clone();
+        self.render(ui)
+    }
+
+    fn refresh(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        Renderer::draw(self, ui, pl)
+    }
+
+    fn update_progress(&mut self, ui: &UiState) -> std::io::Result<()> {
+        self.render(ui)
+    }
+
+    fn update_vu_meter(&mut self, ui: &UiState) -> std::io::Result<()> {
+        self.render(ui)
+    }
+
+    fn update_visualizer(&mut self, ui: &UiState) -> std::io::Result<()> {
+        self.render(ui)
+    }
+
+    // ratatui 每次绘制前自动按终端大小调整布局
+    fn resized(&self) -> bool {
+        false
+    }
+
+    fn show_start_screen(&mut self, _text: &str) -> std::io::Result<()> {
+        self.message = None;
+        if let Some(terminal) = &mut self.terminal {
+            terminal.clear()?;
+        }
+        Ok(())
+    }
+
+    fn show_scan_progress(
+        &mut self,
+        ui: &UiState,
+        seen: usize,
+        found: usize,
+    ) -> std::io::Result<()> {
+        self.message = Some((tr!(Msg::ScanProgress, seen, found), FlashLevel::Info));
+        self.render(ui)
+    }
+
+    fn clear_scan_progress(&mut self) -> std::io::Result<()> {
+        self.message = None;
+        Ok(())
+    }
+
+    fn show_goodbye(&mut self) {
+        // 先离开备用屏幕，告别信息留在原来的终端内容之后
+        if self.terminal.take().is_some() {
+            let _ = crossterm::terminal::disable_raw_mode();
+            let _ = ui::leave_alt_screen();
+        }
+        ui::show_goodbye_message();
+    }
+
+    fn redraw_stats(&self) -> (usize, usize) {
+        (self.redraws, 0)
+    }
+
+    // 全屏界面的帮助显示在消息区域，下次绘制时出现
+    fn show_overlay(&mut self, text: &str) -> std::io::Result<()> {
+        self.message = Some((text.to_string(), FlashLevel::Info));
+        Ok(())
+    }
+
+    fn close_overlay(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        Renderer::draw(self, ui, pl)
+    }
+}
+
+// 关闭颜色时使用终端的默认颜色
+fn color(c: Color) -> ratatui::style::Color {
+    if ui::style().color {
+        c.into()
+    } else {
+        ratatui::style::Color::Reset
+    }
+}
+
+// 带标题的方框，边框样式跟随配色方案
+fn block(title: &str) -> Block<'_> {
+    let set = match theme().border_style {
+        BorderStyle::Block => border::THICK,
+        BorderStyle::DoubleBox => border::DOUBLE,
+        BorderStyle::SingleBox => border::PLAIN,
+        BorderStyle::Rounded => border::ROUNDED,
+        BorderStyle::Ascii => border::Set {
+            top_left: "+",
+            top_right: "+",
+            bottom_left: "+",
+            bottom_right: "+",
+            vertical_left: "|",
+            vertical_right: "|",
+            horizontal_top: "-",
+            horizontal_bottom: "-",
+        },
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_set(set)
+        .border_style(Style::default().fg(color(theme().title)))
+        .title(title)
+}
+
+// 消息框的高度（含边框），随消息行数变化
+fn message_height(area: Rect, message: Option<&(String, FlashLevel)>) -> u16 {
+    let lines = message.map_or(1, |(text, _)| text.lines().count().max(1)) as u16;
+    (lines + 2)
+        .min(area.height / MESSAGE_MAX_RATIO)
+        .max(MESSAGE_MIN_HEIGHT)
+}
+
+fn draw_frame(
+    frame: &mut Frame,
+    ui: &UiState,
+    pl: &PlaylistView,
+    message: Option<&(String, FlashLevel)>,
+) {
+    let area = frame.size();
+    let status_height = 6 + if ui.show_vu { 2 } else { 0 };
+    let vis_height = ui
+        .visualizer
+        .as_ref()
+        .map_or(0, |vis| vis.lines().lines().count() as u16);
+    let debug = if ui.debug_overlay {
+        debug_lines(ui)
+    } else {
+        Vec::new()
+    };
+    let debug_height = if debug.is_empty() {
+        0
+    } else {
+        debug.len() as u16 + 2
+    };
+    let [status, progress, vis, debug_rect, body, message_rect, input] = Layout::vertical([
+        Constraint::Length(status_height),
+        Constraint::Length(1),
+        Constraint::Length(vis_height),
+        Constraint::Length(debug_height),
+        Constraint::Min(3),
+        Constraint::Length(message_height(area, message)),
+        Constraint::Length(INPUT_HEIGHT),
+    ])
+    .areas(area);
+
+    draw_status(frame, status, ui, pl);
+    draw_progress(frame, progress, ui);
+    if let Some(visualizer) = &ui.visualizer {
+        let text = visualizer.lines();
+        let vis_text = Paragraph::new(text.lines().map(Line::from).collect::<Vec<_>>())
+            .style(Style::default().fg(color(theme().progress)));
+        frame.render_widget(vis_text, vis);
+    }
+    if !debug.is_empty() {
+        let lines = debug.iter().map(|l| Line::from(l.trim_start().to_string()));
+        let debug_text = Paragraph::new(lines.collect::<Vec<_>>())
+            .style(Style::default().fg(color(theme().accent)))
+            .block(block(Msg::DebugHeader.text()));
+        frame.render_widget(debug_text, debug_rect);
+    }
+    if ui.show_pane && !pl.window.is_empty() {
+        let [list, lyrics] = Layout::horizontal([
+            Constraint::Percentage(PLAYLIST_PERCENT),
+            Constraint::Percentage(100 - PLAYLIST_PERCENT),
+        ])
+        .areas(body);
+        draw_playlist(frame, list, pl);
+        draw_lyrics(frame, lyrics, ui);
+    } else {
+        draw_lyrics(frame, body, ui);
+    }
+    draw_message(frame, message_rect, message);
+    draw_input(frame, input, ui);
+}
+
+// 状态区域：当前和下一首、播放状态、音量，开启时显示 VU 表
+fn draw_status(frame: &mut Frame, area: Rect, ui: &UiState, pl: &PlaylistView) {
+    let now = if ui.now_name.is_empty() {
+        tr!(Msg::NotPlaying)
+    } else {
+        ui.now_name.clone()
+    };
+    let next = if ui.next_name.is_empty() {
+        tr!(Msg::NoneYet)
+    } else {
+        ui.next_name.clone()
+    };
+    let (now_label, next_label) = (Msg::StatusNow.text(), Msg::StatusNext.text());
+    let label_width = now_label.width().max(next_label.width());
+    let pad = |label: &str| format!("{}{}", label, " ".repeat(label_width - label.width()));
+    let inner_width = area.width.saturating_sub(2) as usize;
+    let mut lines = vec![
+        Line::from(format!(" {} {}", pad(now_label), now)),
+        Line::from(format!(" {} {}", pad(next_label), next)),
+        Line::from(tr!(
+            Msg::StatusLine,
+            ui.play_state.label(),
+            mode_label(ui),
+            pl.len
+        )),
+        Line::styled(
+            tr!(
+                Msg::StatusVolume,
+                volume_bar(ui.volume.unwrap_or(50), inner_width),
+                ui.volume_text()
+            ),
+            Style::default().fg(color(theme().accent)),
+        ),
+    ];
+    if ui.show_vu {
+        lines.extend(
+            vu_meter_lines(ui.levels)
+                .lines()
+                .map(|l| Line::from(l.to_string())),
+        );
+    }
+    let status = Paragraph::new(lines)
+        .style(Style::default().fg(color(theme().title)))
+        .block(block(Msg::StatusHeader.text()));
+    frame.render_widget(status, area);
+}
+
+// 进度条，标签为 已播放/总时长，暂停时前面显示 ⏸
+fn draw_progress(frame: &mut Frame, area: Rect, ui: &UiState) {
+    let total = ui.duration.filter(|d| !d.is_zero());
+    let elapsed = Duration::from_millis(ui.current_ms as u64);
+    let ratio = total.map_or(0.0, |t| {
+        (ui.current_ms as f64 / t.as_millis() as f64).clamp(0.0, 1.0)
+    });
+    let marker = if ui.play_state == PlayState::Paused {
+        "⏸ "
+    } else {
+        ""
+    };
+    let label = format!(
+        "{}{} / {}",
+        marker,
+        format_time(elapsed),
+        total.map_or_else(|| "--:--".to_string(), format_time)
+    );
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(color(theme().progress)))
+        .ratio(ratio)
+        .label(label);
+    frame.render_widget(gauge, area);
+}
+
+// 播放列表：当前歌曲所在的一页，选中当前歌曲
+fn draw_playlist(frame: &mut Frame, area: Rect, pl: &PlaylistView) {
+    let digits = pl.len.to_string().len() as u16;
+    let rows = pl.window.iter().map(|(i, name)| {
+        Row::new(vec![
+            Cell::from(format!("{:>width$}", i + 1, width = digits as usize)),
+            Cell::from(name.clone()),
+        ])
+    });
+    let title = format!("{} ({}/{})", Msg::PaneHeader.text(), pl.page, pl.pages);
+    let table = Table::new(rows, [Constraint::Length(digits), Constraint::Min(1)])
+        .block(block(&title))
+        .style(Style::default().fg(color(theme().title)))
+        .highlight_style(
+            Style::default()
+                .fg(color(theme().accent))
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    let mut state = TableState::default().with_selected(
+        pl.current
+            .and_then(|c| pl.window.iter().position(|(i, _)| *i == c)),
+    );
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+// 歌词区域：与按行界面相同的歌词窗口；没有播放时显示使用提示
+fn draw_lyrics(frame: &mut Frame, area: Rect, ui: &UiState) {
+    let lines: Vec<Line> = if ui.now_index.is_none() {
+        vec![Line::styled(
+            format!("  {}", Msg::WelcomeHint.text()),
+            Style::default().fg(color(theme().info)),
+        )]
+    } else {
+        lyric_rows(ui)
+            .into_iter()
+            .map(|row| {
+                let mut style = Style::default().fg(color(row.color));
+                if row.bold {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+                Line::styled(row.text, style)
+            })
+            .collect()
+    };
+    let lyrics = Paragraph::new(lines).block(block(Msg::LyricsHeader.text()));
+    frame.render_widget(lyrics, area);
+}
+
+// 最近一条提示消息，颜色按级别区分
+fn draw_message(frame: &mut Frame, area: Rect, message: Option<&(String, FlashLevel)>) {
+    let (text, fg) = match message {
+        Some((msg, level)) => {
+            let (prefix, c) = match level {
+                FlashLevel::Info => ("ℹ ", theme().info),
+                FlashLevel::Ok => ("✓ ", theme().ok),
+                FlashLevel::Error => ("✗ ", theme().error),
+            };
+            (format!("{}{}", prefix, msg), c)
+        }
+        None => (String::new(), theme().info),
+    };
+    let widget = Paragraph::new(text)
+        .style(Style::default().fg(color(fg)))
+        .wrap(Wrap { trim: false })
+        .block(block(Msg::TuiMessageTitle.text()));
+    frame.render_widget(widget, area);
+}
+
+// 命令输入框，光标停在输入内容的末尾
+fn draw_input(frame: &mut Frame, area: Rect, ui: &UiState) {
+    let input = ui.input.as_deref().unwrap_or("");
+    let prompt = ">> ";
+    let widget = Paragraph::new(format!("{}{}", prompt, input))
+        .style(Style::default().fg(color(theme().title)))
+        .block(block(Msg::TuiInputTitle.text()));
+    frame.render_widget(widget, area);
+    let cursor = area.x + 1 + (prompt.width() + input.width()) as u16;
+    frame.set_cursor(cursor.min(area.right().saturating_sub(2)), area.y + 1);
+}
@@ -1,168 +1,1454 @@
+use crate::audiobook::{Chapter, format_speed};
+use crate::config::{ColorsConfig, UiConfig, parse_color};
+use crate::i18n::{Msg, tr};
+use crate::player::PlayState;
 use crate::playlist::{PlaybackMode, PlaylistView};
-use crossterm::cursor::MoveTo;
-use crossterm::cursor::{RestorePosition, SavePosition};
-use crossterm::execute;
-use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
-use crossterm::terminal::{Clear, ClearType};
-use std::io::{Write, stdout};
-use unicode_width::UnicodeWidthStr;
-
-// 统一UI样式常量
-const UI_WIDTH: usize = 60;
-const UI_BORDER_CHAR: &str = "═";
-const UI_CORNER_CHAR: &str = "█";
-const UI_TITLE_COLOR: Color = Color::Cyan;
-const UI_ACCENT_COLOR: Color = Color::Yellow;
-const UI_SUCCESS_COLOR: Color = Color::Green;
-const UI_ERROR_COLOR: Color = Color::Red;
-const UI_INFO_COLOR: Color = Color::Blue;
+use crate::replaygain::{format_gain, gain_factor};
+use crossterm::cursor::{MoveTo, RestorePosition, SavePosition};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Write, stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// 终端比这更窄时不画方框，标题只显示文字
+const MIN_BOXED_WIDTH: usize = 40;
+// 自动宽度且无法获取终端大小（如输出被重定向）时使用的宽度
+const FALLBACK_WIDTH: usize = 60;
+
+/// 界面样式（宽度、歌词窗口）和终端能力，启动时根据配置和终端设置一次
+#[derive(Debug, Clone)]
+pub struct UiStyle {
+    pub width: usize,        // 配置的界面宽度，0 为跟随终端宽度
+    pub lyric_window: usize, // 当前歌词行上下各显示的行数
+    pub interlude_ms: u128,  // 歌词间隔超过这个时长时显示间奏标记，0 为不显示
+    pub status_bar: bool,    // 是否在终端最后一行显示状态栏
+    pub alt_screen: bool,    // 播放界面是否使用备用屏幕
+    pub ansi: bool,          // 终端是否支持 ANSI 转义序列，不支持时歌词只能使用清屏模式
+    pub plain: bool,         // 只能逐行输出纯文本，不显示播放界面
+    pub tui: bool,           // 使用全屏界面，输入和输出都必须是终端
+    pub color: bool,         // 是否输出颜色：配置关闭或输出不是终端时为 false
+}
+
+impl UiStyle {
+    /// 按配置和检测到的终端能力设置样式：逐行输出时不使用备用屏幕，输入不是终端时不使用全屏界面
+    pub fn from_config(config: &UiConfig) -> Self {
+        let plain = plain_output();
+        let ansi = !plain && ansi_supported();
+        let tui = config.tui && ansi && std::io::stdin().is_terminal();
+        Self {
+            width: config.width,
+            lyric_window: config.lyric_window,
+            interlude_ms: config.interlude_ms as u128,
+            status_bar: config.status_bar,
+            alt_screen: config.alt_screen && ansi,
+            ansi,
+            plain,
+            tui,
+            color: config.color && !plain,
+        }
+    }
+}
+
+/// 内置的配色方案
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThemePreset {
+    #[default]
+    Default,
+    Mono, // 不使用颜色
+    Solarized,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 3] = [
+        ThemePreset::Default,
+        ThemePreset::Mono,
+        ThemePreset::Solarized,
+    ];
+
+    /// 解析方案名 default、mono、solarized（不区分大小写）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "default" => Some(ThemePreset::Default),
+            "mono" => Some(ThemePreset::Mono),
+            "solarized" => Some(ThemePreset::Solarized),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePreset::Default => "default",
+            ThemePreset::Mono => "mono",
+            ThemePreset::Solarized => "solarized",
+        }
+    }
+}
+
+/// 标题方框和分隔线使用的字符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BorderStyle {
+    #[default]
+    #[serde(rename = "block")]
+    Block, // █ 方框，═ 分隔线
+    #[serde(rename = "double")]
+    DoubleBox, // ╔═╗║╚╝
+    #[serde(rename = "single")]
+    SingleBox, // ┌─┐│└┘
+    #[serde(rename = "rounded")]
+    Rounded, // ╭─╮│╰╯
+    #[serde(rename = "ascii")]
+    Ascii, // +-+|
+}
+
+impl BorderStyle {
+    pub const ALL: [BorderStyle; 5] = [
+        BorderStyle::Block,
+        BorderStyle::DoubleBox,
+        BorderStyle::SingleBox,
+        BorderStyle::Rounded,
+        BorderStyle::Ascii,
+    ];
+
+    /// 解析样式名 block、double、single、rounded、ascii（不区分大小写）
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().to_lowercase();
+        Self::ALL.into_iter().find(|style| style.name() == s)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BorderStyle::Block => "block",
+            BorderStyle::DoubleBox => "double",
+            BorderStyle::SingleBox => "single",
+            BorderStyle::Rounded => "rounded",
+            BorderStyle::Ascii => "ascii",
+        }
+    }
+
+    // 方框的字符：左上、右上、左下、右下、横线、竖线
+    fn box_chars(self) -> [char; 6] {
+        match self {
+            BorderStyle::Block => ['█'; 6],
+            BorderStyle::DoubleBox => ['╔', '╗', '╚', '╝', '═', '║'],
+            BorderStyle::SingleBox => ['┌', '┐', '└', '┘', '─', '│'],
+            BorderStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BorderStyle::Ascii => ['+', '+', '+', '+', '-', '|'],
+        }
+    }
+
+    // 小节标题和界面底部的分隔线字符
+    fn line_char(self) -> char {
+        match self {
+            BorderStyle::Block | BorderStyle::DoubleBox => '═',
+            BorderStyle::SingleBox | BorderStyle::Rounded => '─',
+            BorderStyle::Ascii => '=',
+        }
+    }
+}
+
+/// 界面配色和边框样式，所有输出颜色都从这里取；mono 方案全部为 Color::Reset，即不输出颜色
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub title: Color,         // 标题和状态区域
+    pub accent: Color,        // 歌词区域的标题和边框
+    pub ok: Color,            // 成功提示
+    pub error: Color,         // 错误提示
+    pub info: Color,          // 一般提示
+    pub lyric_current: Color, // 当前歌词行
+    pub lyric_dim: Color,     // 其他歌词行
+    pub interlude: Color,     // 歌词间隔中的间奏标记
+    pub progress: Color,      // 进度条和频谱面板
+    pub border_style: BorderStyle,
+}
+
+impl Theme {
+    const DEFAULT: Theme = Theme {
+        title: Color::Cyan,
+        accent: Color::Blue,
+        ok: Color::Green,
+        error: Color::Red,
+        info: Color::Blue,
+        lyric_current: Color::DarkGreen,
+        lyric_dim: Color::DarkGrey,
+        interlude: Color::DarkCyan,
+        progress: Color::Cyan,
+        border_style: BorderStyle::Block,
+    };
+
+    pub fn preset(preset: ThemePreset) -> Self {
+        let rgb = |r, g, b| Color::Rgb { r, g, b };
+        match preset {
+            ThemePreset::Default => Theme::DEFAULT,
+            ThemePreset::Mono => Theme {
+                title: Color::Reset,
+                accent: Color::Reset,
+                ok: Color::Reset,
+                error: Color::Reset,
+                info: Color::Reset,
+                lyric_current: Color::Reset,
+                lyric_dim: Color::Reset,
+                interlude: Color::Reset,
+                progress: Color::Reset,
+                border_style: BorderStyle::Block,
+            },
+            // Solarized 的强调色，暗色歌词为 base01
+            ThemePreset::Solarized => Theme {
+                title: rgb(38, 139, 210),
+                accent: rgb(108, 113, 196),
+                ok: rgb(133, 153, 0),
+                error: rgb(220, 50, 47),
+                info: rgb(42, 161, 152),
+                lyric_current: rgb(181, 137, 0),
+                lyric_dim: rgb(88, 110, 117),
+                interlude: rgb(211, 54, 130),
+                progress: rgb(42, 161, 152),
+                border_style: BorderStyle::Block,
+            },
+        }
+    }
+
+    /// 按名称查找方案：内置方案或 [ui.themes] 中定义的方案，找不到时返回 None
+    pub fn named(name: &str, config: &UiConfig) -> Option<Self> {
+        match ThemePreset::parse(name) {
+            Some(preset) => Some(Theme::preset(preset)),
+            None => config
+                .themes
+                .get(&name.trim().to_lowercase())
+                .map(|colors| Theme::DEFAULT.with_colors(colors)),
+        }
+    }
+
+    /// 配置中的方案和边框样式，再用 [ui.colors] 中设置的颜色覆盖对应的项
+    pub fn from_config(config: &UiConfig) -> Self {
+        let theme = Theme::named(&config.theme, config)
+            .unwrap_or(Theme::DEFAULT)
+            .with_colors(&config.colors);
+        Theme {
+            border_style: config.border_style,
+            ..theme
+        }
+    }
+
+    // 用设置了的颜色覆盖对应的项
+    fn with_colors(mut self, colors: &ColorsConfig) -> Self {
+        for (slot, name) in [
+            (&mut self.title, &colors.title),
+            (&mut self.accent, &colors.accent),
+            (&mut self.ok, &colors.success),
+            (&mut self.error, &colors.error),
+            (&mut self.info, &colors.info),
+            (&mut self.lyric_current, &colors.lyric_current),
+            (&mut self.lyric_dim, &colors.lyric_dim),
+            (&mut self.interlude, &colors.interlude),
+            (&mut self.progress, &colors.progress),
+        ] {
+            if let Some(color) = name.as_deref().and_then(parse_color) {
+                *slot = color;
+            }
+        }
+        self
+    }
+}
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::DEFAULT);
+
+/// 当前的配色
+pub fn theme() -> Theme {
+    *THEME.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 切换配色，之后的绘制使用新的颜色
+pub fn set_theme(theme: Theme) {
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = theme;
+}
+
+impl Theme {
+    /// 设置前景色，所有带颜色的输出都经过这里；关闭颜色时不输出任何内容，▶ 等标记照常显示
+    pub fn paint(color: Color) -> Paint {
+        Paint {
+            color: Some(color),
+            enabled: style().color,
+        }
+    }
+
+    /// 恢复默认颜色，与 paint 配对使用
+    pub fn unpaint() -> Paint {
+        Paint {
+            color: None,
+            enabled: style().color,
+        }
+    }
+}
+
+/// Theme::paint 返回的终端命令，color 为 None 时恢复默认颜色，enabled 为 false 时什么也不输出
+pub struct Paint {
+    color: Option<Color>,
+    enabled: bool,
+}
+
+impl crossterm::Command for Paint {
+    fn write_ansi(&self, f: &mut impl std::fmt::Write) -> std::fmt::Result {
+        if !self.enabled {
+            return Ok(());
+        }
+        match self.color {
+            Some(color) => SetForegroundColor(color).write_ansi(f),
+            None => ResetColor.write_ansi(f),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        match self.color {
+            Some(color) => SetForegroundColor(color).execute_winapi(),
+            None => ResetColor.execute_winapi(),
+        }
+    }
+}
+
+static UI_STYLE: OnceLock<UiStyle> = OnceLock::new();
+
+/// 设置界面样式，只在启动时生效一次
+pub fn init_style(style: UiStyle) {
+    let _ = UI_STYLE.set(style);
+}
+
+pub fn style() -> &'static UiStyle {
+    UI_STYLE.get_or_init(|| UiStyle::from_config(&UiConfig::default()))
+}
+
+/// 界面宽度（字符数）：配置的宽度，为 0 时跟随终端；每次绘制时重新获取，不超过终端当前的宽度
+pub fn ui_width() -> usize {
+    let columns = terminal::size().ok().map(|(cols, _)| cols as usize);
+    match (style().width, columns) {
+        (0, Some(cols)) => cols,
+        (0, None) => FALLBACK_WIDTH,
+        (width, Some(cols)) => width.min(cols),
+        (width, None) => width,
+    }
+}
+
+/// 把一行文字调整为正好占 width 列：按显示宽度截断或用空格补齐（中文等宽字符占两列），
+/// 原地覆盖较长的旧内容时不会留下残余字符。制表符等控制字符换成空格，避免光标跳动
+pub fn fit_width(text: &str, width: usize) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    let mut line = truncate_width(&text, width);
+    let padding = width.saturating_sub(line.width());
+    line.extend(std::iter::repeat_n(' ', padding));
+    line
+}
+
+/// 按显示宽度截断文本，超出 max 时以 … 结尾
+pub fn truncate_width(text: &str, max: usize) -> String {
+    if text.width() <= max {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if width + w + 1 > max {
+            break;
+        }
+        result.push(c);
+        width += w;
+    }
+    if max > 0 {
+        result.push('…');
+    }
+    result
+}
+
+/// 格式化为 分:秒，例如 3:07
+pub fn format_time(d: Duration) -> String {
+    let secs = d.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+/// /debug 显示的内部状态，开启时由播放线程每次更新进度时写入
+#[derive(Clone, Debug, Default)]
+pub struct DebugInfo {
+    pub decoded: Option<Duration>, // 按已解码样本计算的播放位置，没有歌曲时为 None
+    pub clock: Duration,           // 按经过时间估算的播放位置
+    pub paused: Duration,          // 当前歌曲累计的暂停时长
+    pub queue: Vec<String>,        // 播放队列中的歌曲（文件名）
+    pub tick_rate: f32,            // 最近一秒内播放线程更新进度的次数
+}
 
 #[derive(Clone, Default)]
 pub struct UiState {
     pub show_welcome: bool,
-    pub flash: Option<(String, FlashLevel)>,
+    pub flash: FlashQueue,
     pub now_index: Option<usize>,
     pub now_name: String,
     pub next_name: String,
     pub volume: Option<u8>,
+    pub replay_gain: Option<f32>, // 当前歌曲应用的 ReplayGain 增益（dB，含前置放大）
     pub mode: PlaybackMode,
+    pub mode_settings: Option<PlaybackMode>, // 已应用了哪个模式在 [modes.*] 中的设置
 
     // 歌词相关
     pub lyrics: Option<crate::lyrics::Lyrics>,
     pub current_ms: u128,                  // 当前播放时间（毫秒）
-    pub show_lyrics: bool,                 // 是否显示歌词
-    pub current_lyric_line: Option<usize>, // 当前歌词行索引，用于检测歌词变化
+    pub duration: Option<Duration>,        // 当前歌曲的总时长，未知时为 None
+    pub show_lyrics: bool,                 // 是否显示歌词，/lyrics 切换后换歌时保持
+    pub current_lyric_line: Option<usize>, // 当前歌词行索引（前奏中为 None），用于检测歌词变化
+    pub interlude_frame: Option<usize>, // 间奏标记当前的帧（不在歌词间隔中时为 None），用于检测变化
+
+    // 有声书模式
+    pub speed: f32,                     // 播放速度
+    pub chapters: Vec<Chapter>,         // 当前文件的 cue 章节
+    pub current_chapter: Option<usize>, // 当前章节下标
+
+    // VU 表
+    pub show_vu: bool,
+    pub levels: (f32, f32), // 左右声道电平（RMS，0.0-1.0）
+
+    // 调试信息：/debug 开启后在播放界面中显示计时和内部状态
+    pub debug_overlay: bool,
+    pub debug_info: DebugInfo,
+
+    pub visualizer: Option<Visualizer>, // 频谱面板，--vis 或 /viz 开启后显示
+    pub show_pane: bool,                // 是否在状态区域下方显示播放列表面板
+    pub list_page_size: usize,          // /list 每页显示的歌曲数
+
+    // 紧凑显示：终端窄于 MIN_BOXED_WIDTH 列时只显示一行播放状态，/compact 可强制开关
+    pub compact_mode: bool,
+    pub compact_override: Option<bool>,
+
+    // 安静模式：播放界面只剩一行原地更新的状态，不显示歌词，--quiet 或 /quiet 开启
+    pub quiet_mode: bool,
 
     // 简化的UI状态管理
+    pub input: Option<String>, // 全屏界面底部输入框中的内容，行输入时为 None
     pub playing_ui_active: bool, // 是否处于播放界面模式
+    pub hotkey_mode: bool,     // 是否处于单键热键模式（终端为 raw 模式）
+    pub play_state: PlayState, // 播放中、已暂停或已停止
+    pub ui_suspended: bool, // 播放中显示帮助页时暂停播放界面的刷新，按任意键或执行下一条命令后恢复
+    pub spinner_frame: usize, // 播放中指示符的当前帧
 
     // 流式歌词输出状态
-    pub lyrics_stream_mode: bool,     // 是否启用流式歌词输出
-    pub lyrics_base_row: Option<u16>, // 歌词区域起始行位置
-    pub status_base_row: Option<u16>, // 播放状态区域起始行位置
-    pub last_lyrics_range: Option<(usize, usize)>, // 上次显示的歌词范围，用于减少不必要的更新
+    pub lyrics_stream_mode: bool, // 流式模式：重绘时只重写变化的行；否则每次清屏重绘
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub enum FlashLevel {
+    #[default]
     Info,
     Ok,
     Error,
 }
 
-impl Default for FlashLevel {
-    fn default() -> Self {
-        FlashLevel::Info
+// 播放界面中每条提示消息至少显示的时间，之后才显示队列中的下一条
+const FLASH_MIN_DURATION: Duration = Duration::from_millis(1500);
+// 等待显示的消息数上限，超出时先丢弃最早的普通提示
+const FLASH_QUEUE_LIMIT: usize = 8;
+
+// 等待显示的提示消息
+#[derive(Clone, Debug)]
+struct Flash {
+    text: String,
+    level: FlashLevel,
+    immediate: bool, // 多行的输出（/list、/now 等）不排队，立即显示
+}
+
+/// 提示消息队列：同时产生的多条消息按到达顺序逐条显示，避免后一条冲掉还没看清的前一条。
+/// 错误插到普通消息之前，但不会冲掉正在显示的错误
+#[derive(Clone, Debug, Default)]
+pub struct FlashQueue {
+    pending: VecDeque<Flash>,
+    showing: Option<(bool, Instant)>, // 正在显示的消息是否为错误, 开始显示的时间
+}
+
+impl FlashQueue {
+    fn push(&mut self, text: String, level: FlashLevel) {
+        let immediate = text.trim_end().contains('\n');
+        let flash = Flash {
+            text,
+            level,
+            immediate,
+        };
+        if flash.level == FlashLevel::Error {
+            let at = self
+                .pending
+                .iter()
+                .position(|f| !f.immediate && f.level != FlashLevel::Error)
+                .unwrap_or(self.pending.len());
+            self.pending.insert(at, flash);
+        } else {
+            self.pending.push_back(flash);
+        }
+        if self.pending.len() > FLASH_QUEUE_LIMIT {
+            let drop_at = [FlashLevel::Info, FlashLevel::Ok]
+                .into_iter()
+                .find_map(|level| {
+                    self.pending
+                        .iter()
+                        .position(|f| !f.immediate && f.level == level)
+                })
+                .unwrap_or(0);
+            self.pending.remove(drop_at);
+        }
+    }
+
+    /// 是否还有等待显示的消息
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    // 队首的消息还要等多久才能显示；hold 为 false 时不需要等待
+    fn wait(&self, hold: bool) -> Option<Duration> {
+        let next = self.pending.front()?;
+        let Some((showing_error, since)) = self.showing else {
+            return Some(Duration::ZERO);
+        };
+        let interrupts = next.immediate || (next.level == FlashLevel::Error && !showing_error);
+        if !hold || interrupts {
+            return Some(Duration::ZERO);
+        }
+        Some(FLASH_MIN_DURATION.saturating_sub(since.elapsed()))
+    }
+
+    // 取出可以显示的下一条消息，正在显示的消息还没到最短显示时间时返回 None
+    fn next(&mut self, hold: bool) -> Option<(String, FlashLevel)> {
+        if !self.wait(hold)?.is_zero() {
+            return None;
+        }
+        let flash = self.pending.pop_front()?;
+        self.showing = Some((flash.level == FlashLevel::Error, Instant::now()));
+        Some((flash.text, flash.level))
     }
 }
 
 impl UiState {
+    /// 实际输出的音量倍数：音量乘以 ReplayGain 增益
+    pub fn output_volume(&self) -> f32 {
+        let base = (self.volume.unwrap_or(50) as f32 / 100.0).min(1.0);
+        base * self.replay_gain.map_or(1.0, gain_factor)
+    }
+
+    /// 音量的显示文本，应用了 ReplayGain 时附带增益，例如 50% (RG -6.5 dB)
+    pub fn volume_text(&self) -> String {
+        let volume = self.volume.unwrap_or(50);
+        match self.replay_gain {
+            Some(db) => tr!(Msg::VolumeWithGain, volume, format_gain(db)),
+            None => format!("{}%", volume),
+        }
+    }
+
+    /// 当前章节序号和章节总数（从 1 开始），没有章节时为 None
+    pub fn chapter_progress(&self) -> Option<(usize, usize)> {
+        let idx = self.current_chapter?;
+        (!self.chapters.is_empty()).then(|| (idx + 1, self.chapters.len()))
+    }
+
     pub fn set_now_playing(&mut self, idx: usize, name: String, next: String) {
         self.now_index = Some(idx);
         self.now_name = name;
         self.next_name = next;
         self.show_welcome = false;
-        self.show_lyrics = true; // 默认显示歌词
         self.current_lyric_line = None; // 重置歌词行索引
+        self.interlude_frame = None;
         self.playing_ui_active = true; // 激活播放界面模式
+        self.play_state = PlayState::Playing;
+    }
 
-        // 初始化流式输出状态
-        self.lyrics_stream_mode = true; // 默认启用流式歌词
-        self.lyrics_base_row = None;
-        self.status_base_row = None;
-        self.last_lyrics_range = None;
+    /// 回到未播放的初始状态：退出播放界面，清除当前歌曲、歌词和章节
+    pub fn reset_playing(&mut self) {
+        self.now_index = None;
+        self.now_name.clear();
+        self.next_name.clear();
+        self.replay_gain = None;
+        self.lyrics = None;
+        self.current_ms = 0;
+        self.duration = None;
+        self.current_lyric_line = None;
+        self.interlude_frame = None;
+        self.chapters.clear();
+        self.current_chapter = None;
+        self.levels = (0.0, 0.0);
+        if let Some(vis) = &mut self.visualizer {
+            vis.clear();
+        }
+        self.playing_ui_active = false;
+        self.play_state = PlayState::Stopped;
     }
 
-    pub fn flash_message(&mut self, msg: Option<String>, level: FlashLevel) {
-        self.flash = msg.map(|s| (s, level));
+    /// 按 /compact 的设置或终端宽度更新 compact_mode，返回是否发生变化
+    pub fn update_compact(&mut self) -> bool {
+        let compact = self
+            .compact_override
+            .unwrap_or_else(|| ui_width() < MIN_BOXED_WIDTH);
+        let changed = compact != self.compact_mode;
+        self.compact_mode = compact;
+        changed
     }
 
-    pub fn toggle_lyrics(&mut self) {
-        self.show_lyrics = !self.show_lyrics;
+    /// 把提示消息加入队列，由 UI 线程按顺序显示
+    pub fn flash_message(&mut self, msg: String, level: FlashLevel) {
+        self.flash.push(msg, level);
+    }
+
+    // 播放界面重绘时会冲掉之前输出的消息，此时每条消息至少显示一段时间；
+    // 其他情况下消息逐行输出，不需要等待
+    fn flash_hold(&self) -> bool {
+        self.playing_ui_active && !self.quiet_mode && !style().plain
     }
 
-    pub fn clear_flash(&mut self) {
-        self.flash = None;
+    /// 取出可以显示的下一条提示消息
+    pub fn next_flash(&mut self) -> Option<(String, FlashLevel)> {
+        let hold = self.flash_hold();
+        self.flash.next(hold)
+    }
+
+    /// 距离下一条提示消息可以显示还有多久，没有等待的消息时为 None
+    pub fn flash_wait(&self) -> Option<Duration> {
+        self.flash.wait(self.flash_hold())
+    }
+
+    pub fn toggle_lyrics(&mut self) {
+        self.show_lyrics = !self.show_lyrics;
     }
 
     // 切换歌词显示模式（流式 vs 清屏）
     pub fn toggle_lyrics_mode(&mut self) {
         self.lyrics_stream_mode = !self.lyrics_stream_mode;
-        // 切换模式时重置位置信息
-        self.lyrics_base_row = None;
-        self.status_base_row = None;
-        self.last_lyrics_range = None;
     }
 }
 
-// 统一UI样式函数
-fn create_title_bar(title: &str) -> String {
-    let title_width = title.width(); // 使用 unicode-width 计算实际显示宽度
-    let total_padding = UI_WIDTH - title_width - 2; // 减去两边的边框字符
-    let left_padding = total_padding / 2;
-    let right_padding = total_padding - left_padding; // 确保总长度正确
+// raw 模式下换行不会自动回到行首，统一输出 \r\n（普通模式下同样无害）
+fn crlf(text: &str) -> String {
+    if style().plain {
+        return text.to_string();
+    }
+    text.replace('\n', "\r\n")
+}
 
-    let mut result = String::new();
-    result.push_str(&UI_CORNER_CHAR.repeat(UI_WIDTH));
-    result.push('\n');
-    result.push_str(&format!(
-        "{}{}{}{}",
-        UI_CORNER_CHAR,
+// 输出一段带颜色的文字，逐行输出纯文本时不带颜色
+fn print_colored(color: Color, text: &str) -> std::io::Result<()> {
+    if style().plain {
+        print!("{}", text);
+        return stdout().flush();
+    }
+    execute!(
+        stdout(),
+        Theme::paint(color),
+        Print(crlf(text)),
+        Theme::unpaint()
+    )
+}
+
+// 播放界面底部的输入提示：热键模式显示按键说明，否则显示命令提示符
+fn input_hint(ui: &UiState) -> &'static str {
+    if ui.hotkey_mode {
+        Msg::HotkeyHint.text()
+    } else {
+        ">>： "
+    }
+}
+
+// 正在播放一行在播放界面中的行（从 0 开始，标题和空行之后），行首显示播放中指示符
+const NOW_ROW: u16 = 2;
+// 进度条在播放界面中的行（从 0 开始，状态区域的音量行之后）
+const PROGRESS_ROW: u16 = 7;
+// 播放中指示符的各帧，暂停或停止时显示空白
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// 按播放位置计算指示符的帧，每 200ms 换一帧
+pub fn spinner_frame(current_ms: u128) -> usize {
+    (current_ms / 200 % SPINNER_FRAMES.len() as u128) as usize
+}
+
+fn spinner_char(ui: &UiState) -> char {
+    if ui.play_state == PlayState::Playing {
+        SPINNER_FRAMES[ui.spinner_frame % SPINNER_FRAMES.len()]
+    } else {
+        ' '
+    }
+}
+// VU 表在播放界面中的起始行（进度条之后）
+const VU_FIRST_ROW: u16 = 8;
+// 音量条的格数为界面宽度的 1/VOLUME_BAR_RATIO，限制在最少和最多格数之间
+const VOLUME_BAR_RATIO: usize = 6;
+const VOLUME_BAR_MIN: usize = 10;
+const VOLUME_BAR_MAX: usize = 30;
+
+/// 音量条，例如 ▮▮▮▮▮▯▯▯▯▯，格数随界面宽度变化
+pub fn volume_bar(volume: u8, width: usize) -> String {
+    let cells = (width / VOLUME_BAR_RATIO).clamp(VOLUME_BAR_MIN, VOLUME_BAR_MAX);
+    let filled = (volume.min(100) as usize * cells + 50) / 100;
+    format!("{}{}", "▮".repeat(filled), "▯".repeat(cells - filled))
+}
+// 电平表的显示范围（dB）
+const VU_FLOOR_DB: f32 = -48.0;
+/// 频谱面板的频段数
+pub const VIS_BARS: usize = 24;
+// 频谱面板的行数，每行按八分之一格细分
+const VIS_HEIGHT: usize = 4;
+// 柱子下降时每次保留的比例，使画面不会剧烈跳动
+const VIS_DECAY: f32 = 0.7;
+const VIS_BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 频谱面板：保存各频段柱子的高度（0.0-1.0），上升立即显示，下降逐渐回落
+#[derive(Clone, Debug, Default)]
+pub struct Visualizer {
+    bars: Vec<f32>,
+}
+
+impl Visualizer {
+    pub fn update(&mut self, bars: Vec<f32>) {
+        if self.bars.len() != bars.len() {
+            self.bars = bars;
+            return;
+        }
+        for (old, new) in self.bars.iter_mut().zip(bars) {
+            *old = new.max(*old * VIS_DECAY);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.bars.clear();
+    }
+
+    /// 面板的各行（从上到下），柱子宽度按界面宽度平分
+    pub fn lines(&self) -> String {
+        let bars = if self.bars.is_empty() {
+            &[0.0; VIS_BARS][..]
+        } else {
+            &self.bars[..]
+        };
+        let bar_width = (ui_width().saturating_sub(4) / bars.len()).max(1);
+        let mut out = String::new();
+        for row in (0..VIS_HEIGHT).rev() {
+            out.push_str("  ");
+            for &bar in bars {
+                let eighths = (bar * (VIS_HEIGHT * 8) as f32).round() as usize;
+                let cell = eighths.saturating_sub(row * 8).min(8);
+                out.extend(std::iter::repeat_n(VIS_BLOCKS[cell], bar_width));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// 精确到毫秒的时间，例如 2:13.480
+fn precise_time(d: Duration) -> String {
+    format!("{}.{:03}", format_time(d), d.subsec_millis())
+}
+
+/// /debug 显示的各行：两种计时及偏差、当前歌词行及其时间范围、模式和更新频率、播放队列
+pub fn debug_lines(ui: &UiState) -> Vec<String> {
+    let info = &ui.debug_info;
+    let none = || "-".to_string();
+    let drift = info.decoded.map(|d| {
+        format!(
+            "{:+}ms",
+            d.as_millis() as i64 - info.clock.as_millis() as i64
+        )
+    });
+    let (line, range) = match (&ui.lyrics, ui.current_lyric_line) {
+        (Some(lyrics), Some(i)) => {
+            let start = Duration::from_millis(lyrics.lines[i].0 as u64);
+            let end = lyrics
+                .lines
+                .get(i + 1)
+                .map_or_else(none, |l| precise_time(Duration::from_millis(l.0 as u64)));
+            (
+                format!("{}/{}", i + 1, lyrics.lines.len()),
+                format!("{} - {}", precise_time(start), end),
+            )
+        }
+        _ => (none(), none()),
+    };
+    let queue = if info.queue.is_empty() {
+        none()
+    } else {
+        format!("{} ({})", info.queue.len(), info.queue.join(", "))
+    };
+    vec![
+        tr!(
+            Msg::DebugClock,
+            info.decoded.map_or_else(none, precise_time),
+            precise_time(info.clock),
+            drift.unwrap_or_else(none),
+            precise_time(info.paused)
+        ),
+        tr!(Msg::DebugLyrics, line, range),
+        tr!(
+            Msg::DebugState,
+            mode_label(ui),
+            format!("{:.1}", info.tick_rate)
+        ),
+        tr!(Msg::DebugQueue, queue),
+    ]
+}
+
+// 进度条，例如 "  02:13 ━━━━━━●───────── 04:35"；暂停时行首显示 ⏸，总时长未知时只显示已播放时间
+fn progress_line(ui: &UiState) -> String {
+    let clock = |ms: u128| format!("{:02}:{:02}", ms / 60_000, (ms % 60_000) / 1000);
+    let marker = if ui.play_state == PlayState::Paused {
+        "⏸ "
+    } else {
+        "  "
+    };
+    let elapsed = clock(ui.current_ms);
+    let Some(total) = ui.duration.filter(|d| !d.is_zero()) else {
+        return format!("{}{}", marker, elapsed);
+    };
+    let total_text = clock(total.as_millis());
+    let bar_width = ui_width()
+        .saturating_sub(marker.width() + elapsed.width() + total_text.width() + 3)
+        .max(2);
+    let ratio = (ui.current_ms as f64 / total.as_millis() as f64).clamp(0.0, 1.0);
+    let filled = ((ratio * (bar_width - 1) as f64).round() as usize).min(bar_width - 1);
+    format!(
+        "{}{} {}●{} {}",
+        marker,
+        elapsed,
+        "━".repeat(filled),
+        "─".repeat(bar_width - 1 - filled),
+        total_text
+    )
+}
+
+// 播放状态的标记：播放中 ▶、暂停 ⏸、停止 ■
+fn state_marker(ui: &UiState) -> char {
+    match ui.play_state {
+        PlayState::Playing => '▶',
+        PlayState::Paused => '⏸',
+        PlayState::Stopped => '■',
+    }
+}
+
+// 紧凑显示的状态行，例如 "▶ 歌手 - 歌名 [02:13]"，歌名太长时截断
+fn compact_line(ui: &UiState, width: usize) -> String {
+    let marker = state_marker(ui);
+    let name = if ui.now_name.is_empty() {
+        tr!(Msg::NotPlaying)
+    } else {
+        ui.now_name.clone()
+    };
+    let ms = ui.current_ms;
+    let clock = format!("[{:02}:{:02}]", ms / 60_000, (ms % 60_000) / 1000);
+    let name_width = width.saturating_sub(marker.width().unwrap_or(1) + clock.width() + 2);
+    format!("{} {} {}", marker, truncate_width(&name, name_width), clock)
+}
+
+// 安静模式的状态行，例如 "▶ 03/57 夜曲.flac 02:13/04:35 vol 50%"，歌名太长时截断。
+// 比终端窄一列，避免写到最后一列时自动换行
+fn quiet_line(ui: &UiState, total: usize, width: usize) -> String {
+    let clock = |ms: u128| format!("{:02}:{:02}", ms / 60_000, (ms % 60_000) / 1000);
+    let digits = total.to_string().len().max(2);
+    let index = ui
+        .now_index
+        .map_or_else(|| "-".repeat(digits), |i| format!("{:0digits$}", i + 1));
+    let time = match ui.duration.filter(|d| !d.is_zero()) {
+        Some(d) => format!("{}/{}", clock(ui.current_ms), clock(d.as_millis())),
+        None => clock(ui.current_ms),
+    };
+    let head = format!("{} {}/{:0digits$} ", state_marker(ui), index, total);
+    let tail = format!(" {} vol {}", time, ui.volume_text());
+    let width = width.saturating_sub(1);
+    let name = truncate_width(
+        &ui.now_name,
+        width.saturating_sub(head.width() + tail.width()),
+    );
+    truncate_width(&format!("{}{}{}", head, name, tail), width)
+}
+
+// 播放界面中是否正在输入命令：交互输入时只有按 : 后才会离开热键模式
+fn prompt_open(ui: &UiState) -> bool {
+    ui.playing_ui_active && !ui.hotkey_mode && std::io::stdin().is_terminal()
+}
+
+// 底部状态栏，例如 "[▶ 当前播放: 歌名 | 音量: 80% | 模式: 随机播放 | 2:34/5:01]"。
+// 太窄时先截断歌名，歌名放不下时省略音量和模式，保留播放时间
+fn status_bar_line(ui: &UiState, width: usize) -> String {
+    let mode = match ui.mode {
+        PlaybackMode::Sequential => Msg::ModeNameSequential.text(),
+        PlaybackMode::RepeatOne => Msg::ModeNameRepeatOne.text(),
+        PlaybackMode::Shuffle => Msg::ModeNameShuffle.text(),
+        PlaybackMode::Audiobook => Msg::ModeNameAudiobook.text(),
+    };
+    let total = ui
+        .duration
+        .filter(|d| !d.is_zero())
+        .map_or_else(|| "--:--".to_string(), format_time);
+    let time = format!(
+        "{}/{}",
+        format_time(Duration::from_millis(ui.current_ms as u64)),
+        total
+    );
+    let prefix = format!("[{} {} ", state_marker(ui), Msg::StatusNow.text());
+    let full = format!(
+        " | {} | {} | {}]",
+        tr!(Msg::BarVolume, ui.volume_text()),
+        tr!(Msg::BarMode, mode),
+        time
+    );
+    let short = format!(" | {}]", time);
+    let title_width = |tail: &str| width.saturating_sub(prefix.width() + tail.width());
+    let tail = if title_width(&full) >= 8 { full } else { short };
+    let title = truncate_width(&ui.now_name, title_width(&tail));
+    truncate_width(&format!("{}{}{}", prefix, title, tail), width)
+}
+
+// 播放列表面板：当前歌曲所在的一页，当前歌曲前显示 ▶，标题中显示页码
+fn pane_lines(pl: &PlaylistView, width: usize) -> String {
+    if pl.window.is_empty() {
+        return String::new();
+    }
+    let digits = pl.len.to_string().len();
+    let title = format!("{} ({}/{})", Msg::PaneHeader.text(), pl.page, pl.pages);
+    let mut out = create_section_header(&title, width, theme().border_style);
+    out.push('\n');
+    for (i, name) in &pl.window {
+        let marker = if pl.current == Some(*i) { '▶' } else { ' ' };
+        let line = format!("{} {:>digits$}. {}", marker, i + 1, name);
+        out.push_str(&truncate_width(&line, width));
+        out.push('\n');
+    }
+    out.push_str(&create_footer());
+    out
+}
+
+/// 左右声道的电平条，按分贝刻度显示
+pub fn vu_meter_lines(levels: (f32, f32)) -> String {
+    let bar_width = ui_width().saturating_sub(6);
+    let bar = |level: f32| {
+        let db = 20.0 * level.max(1e-6).log10();
+        let ratio = ((db - VU_FLOOR_DB) / -VU_FLOOR_DB).clamp(0.0, 1.0);
+        let filled = (ratio * bar_width as f32).round() as usize;
+        format!("{}{}", "█".repeat(filled), "░".repeat(bar_width - filled))
+    };
+    format!("  L {}\n  R {}\n", bar(levels.0), bar(levels.1))
+}
+
+// 方框的第一行：左上角、横线、右上角
+fn create_box_top(width: usize, style: BorderStyle) -> String {
+    let [top_left, top_right, _, _, line, _] = style.box_chars();
+    format!(
+        "{}{}{}",
+        top_left,
+        line.to_string().repeat(width.saturating_sub(2)),
+        top_right
+    )
+}
+
+// 方框中间的一行，标题居中，两边为竖线
+fn create_box_mid(title: &str, width: usize, style: BorderStyle) -> String {
+    let [_, _, _, _, _, side] = style.box_chars();
+    let title = truncate_width(title, width.saturating_sub(2));
+    let total_padding = width.saturating_sub(title.width() + 2); // 减去两边的边框字符
+    let left_padding = total_padding / 2;
+    format!(
+        "{}{}{}{}{}",
+        side,
         " ".repeat(left_padding),
         title,
-        " ".repeat(right_padding)
-    ));
-    result.push_str(UI_CORNER_CHAR);
-    result.push('\n');
-    result.push_str(&UI_CORNER_CHAR.repeat(UI_WIDTH));
-    result.push('\n');
-    result
+        " ".repeat(total_padding - left_padding),
+        side
+    )
+}
+
+// 方框的最后一行：左下角、横线、右下角
+fn create_box_bottom(width: usize, style: BorderStyle) -> String {
+    let [_, _, bottom_left, bottom_right, line, _] = style.box_chars();
+    format!(
+        "{}{}{}",
+        bottom_left,
+        line.to_string().repeat(width.saturating_sub(2)),
+        bottom_right
+    )
 }
 
-fn create_section_header(title: &str) -> String {
+// 统一UI样式函数，终端太窄时退化为不带方框的标题
+fn create_title_bar(title: &str, width: usize, style: BorderStyle) -> String {
+    if width < MIN_BOXED_WIDTH {
+        return format!("{}\n", truncate_width(title, width.saturating_sub(2)));
+    }
+    format!(
+        "{}\n{}\n{}\n",
+        create_box_top(width, style),
+        create_box_mid(title, width, style),
+        create_box_bottom(width, style)
+    )
+}
+
+fn create_section_header(title: &str, width: usize, style: BorderStyle) -> String {
+    let title = truncate_width(title, width.saturating_sub(2));
+    if width < MIN_BOXED_WIDTH {
+        return format!("{}\n", title);
+    }
     let title_width = title.width(); // 使用 unicode-width 计算实际显示宽度
-    let total_border_len = UI_WIDTH - title_width - 2; // 减去两边的空格
+    let total_border_len = width - title_width - 2; // 减去两边的空格
     let left_border_len = total_border_len / 2;
     let right_border_len = total_border_len - left_border_len; // 确保总长度正确
+    let line = style.line_char().to_string();
 
     format!(
         "{} {} {}\n",
-        UI_BORDER_CHAR.repeat(left_border_len),
+        line.repeat(left_border_len),
         title,
-        UI_BORDER_CHAR.repeat(right_border_len)
+        line.repeat(right_border_len)
     )
 }
 
+/// 界面宽度的分隔线，使用当前边框样式的横线字符
+pub fn border_line() -> String {
+    theme()
+        .border_style
+        .line_char()
+        .to_string()
+        .repeat(ui_width())
+}
+
+/// 在界面宽度内居中的一行文字（按显示宽度计算，中英文都能对齐）
+pub fn center_line(text: &str) -> String {
+    let width = ui_width();
+    let text = truncate_width(text, width);
+    let padding = width.saturating_sub(text.width()) / 2;
+    format!("{}{}", " ".repeat(padding), text)
+}
+
+/// 帮助和 /now 中的小节分隔线，如 ──── 基本信息 ────
+pub fn divider(title: &str) -> String {
+    let total = ui_width().saturating_sub(title.width() + 2);
+    let left = total / 2;
+    format!(
+        "{} {} {}",
+        "─".repeat(left),
+        title,
+        "─".repeat(total - left)
+    )
+}
+
+// 把标签补齐到相同的显示宽度，使后面的内容对齐
+fn pad_labels<const N: usize>(labels: [&str; N]) -> [String; N] {
+    let width = labels.iter().map(|l| l.width()).max().unwrap_or(0);
+    labels.map(|l| format!("{}{}", l, " ".repeat(width - l.width())))
+}
+
 fn create_footer() -> String {
-    UI_BORDER_CHAR.repeat(UI_WIDTH) + "\n"
+    border_line() + "\n"
 }
 
 fn create_goodbye_message() -> String {
     let mut msg = String::new();
-    msg.push_str(&create_title_bar("🎵 感谢使用 BeatCLI"));
-    msg.push_str("\n                    再见，下次再见！\n");
-    msg.push_str("              希望音乐带给您美好的时光 🎶\n\n");
+    msg.push_str(&create_title_bar(
+        Msg::GoodbyeTitle.text(),
+        ui_width(),
+        theme().border_style,
+    ));
+    msg.push('\n');
+    msg.push_str(&center_line(Msg::GoodbyeLine1.text()));
+    msg.push('\n');
+    msg.push_str(&center_line(Msg::GoodbyeLine2.text()));
+    msg.push_str("\n\n");
     msg.push_str(&create_footer());
     msg
 }
 
-// 公开的goodbye消息函数
+// 公开的goodbye消息函数，先离开备用屏幕，使消息显示在原来的屏幕上
 pub fn show_goodbye_message() {
-    let mut stdout = stdout();
-    execute!(
-        stdout,
-        SetForegroundColor(UI_TITLE_COLOR),
-        Print(create_goodbye_message()),
-        ResetColor
-    )
-    .ok();
+    let _ = leave_alt_screen();
+    let _ = print_colored(theme().title, &create_goodbye_message());
 }
 
-pub struct Screen;
+// 是否只能逐行输出纯文本：输出被重定向（如 beatcli | tee log）或终端不支持光标定位（TERM=dumb）
+fn plain_output() -> bool {
+    !stdout().is_terminal() || std::env::var("TERM").is_ok_and(|t| t == "dumb")
+}
+
+// 终端是否支持 ANSI 转义序列。Windows 上会尝试开启虚拟终端处理，旧版控制台开启失败时返回 false，
+// 此时光标定位由 crossterm 通过 WinAPI 完成，不使用流式歌词
+fn ansi_supported() -> bool {
+    #[cfg(windows)]
+    {
+        crossterm::ansi_support::supports_ansi()
+    }
+    #[cfg(not(windows))]
+    {
+        true
+    }
+}
+
+// 当前是否在备用屏幕上，panic 时据此决定是否需要离开
+static ALT_SCREEN_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// 进入备用屏幕（配置 alt_screen = true 时），返回是否刚刚进入
+fn enter_alt_screen() -> std::io::Result<bool> {
+    if !style().alt_screen {
+        return Ok(false);
+    }
+    activate_alt_screen()
+}
+
+/// 进入备用屏幕，返回是否刚刚进入；已经在备用屏幕上时什么也不做
+pub fn activate_alt_screen() -> std::io::Result<bool> {
+    if ALT_SCREEN_ACTIVE.swap(true, Ordering::SeqCst) {
+        return Ok(false);
+    }
+    execute!(stdout(), EnterAlternateScreen)?;
+    Ok(true)
+}
+
+/// 离开备用屏幕，恢复进入前的终端内容；不在备用屏幕上时什么也不做
+pub fn leave_alt_screen() -> std::io::Result<()> {
+    if ALT_SCREEN_ACTIVE.swap(false, Ordering::SeqCst) {
+        execute!(stdout(), LeaveAlternateScreen)?;
+    }
+    Ok(())
+}
+
+/// 恢复终端：关闭 raw 模式并离开备用屏幕。程序退出和 panic 时调用
+pub fn restore_terminal() {
+    let _ = terminal::disable_raw_mode();
+    let _ = leave_alt_screen();
+}
+
+/// 离开作用域时恢复终端，出错提前返回时也不会停留在备用屏幕或 raw 模式
+pub struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// 播放界面中的一行：文字和颜色，当前歌词行加粗
+#[derive(Clone, PartialEq)]
+pub struct Row {
+    pub text: String,
+    pub color: Color,
+    pub bold: bool,
+}
+
+// 把多行文字按行加入 rows，使用同一颜色
+fn push_rows(rows: &mut Vec<Row>, text: &str, color: Color) {
+    rows.extend(text.lines().map(|line| Row {
+        text: line.to_string(),
+        color,
+        bold: false,
+    }));
+}
+
+// 在光标处输出一行，补齐到界面宽度以覆盖原来的内容
+// 使用 crossterm 的命令而不是手写转义序列，不支持 ANSI 的旧版 Windows 控制台也能正常显示
+fn queue_row(out: &mut impl Write, row: &Row, width: usize) -> std::io::Result<()> {
+    queue!(out, Theme::paint(row.color))?;
+    if row.bold {
+        queue!(out, SetAttribute(Attribute::Bold))?;
+    }
+    queue!(out, Print(fit_width(&row.text, width)))?;
+    if row.bold {
+        queue!(out, SetAttribute(Attribute::Reset))?;
+    }
+    queue!(out, Theme::unpaint())
+}
+
+// 间奏标记每一帧的时长和帧数，依次显示 🎵 …、🎵 ……、🎵 ………
+const INTERLUDE_FRAME_MS: u128 = 500;
+const INTERLUDE_FRAMES: usize = 3;
+
+/// 处在两行歌词之间的长间隔中时返回间奏标记当前的帧，否则返回 None
+pub fn interlude_frame(lyrics: &crate::lyrics::Lyrics, ms: u128) -> Option<usize> {
+    let threshold = style().interlude_ms;
+    (threshold > 0 && lyrics.is_in_gap(ms, threshold))
+        .then_some((ms / INTERLUDE_FRAME_MS) as usize % INTERLUDE_FRAMES)
+}
+
+/// 歌词窗口中的各行，当前行前显示 ▶；不显示歌词或没有歌词时为空。
+/// 第一行开始之前显示前奏提示和接下来的几行，都不高亮；
+/// 歌词间隔较长时上一行不再高亮，在它后面显示闪动的间奏标记
+pub fn lyric_rows(ui: &UiState) -> Vec<Row> {
+    let Some(lyrics) = ui
+        .lyrics
+        .as_ref()
+        .filter(|l| ui.show_lyrics && !l.lines.is_empty())
+    else {
+        return Vec::new();
+    };
+    let window = style().lyric_window;
+    let mut active = lyrics.active_line(ui.current_ms);
+    let interlude = interlude_frame(lyrics, ui.current_ms);
+    let mut rows = Vec::new();
+    let (start, end) = match active {
+        Some(idx) => (
+            idx.saturating_sub(window),
+            (idx + window + 1).min(lyrics.lines.len()),
+        ),
+        None => {
+            rows.push(Row {
+                text: format!("    {}", Msg::LyricsIntro.text()),
+                color: theme().lyric_dim,
+                bold: false,
+            });
+            (0, window.min(lyrics.lines.len()))
+        }
+    };
+    // 间奏标记占用窗口最后一行的位置，使歌词区域的行数不变
+    let (end, marker_after) = match (active, interlude) {
+        (Some(idx), Some(_)) => {
+            active = None;
+            (end.min(idx + window).max(idx + 1), Some(idx))
+        }
+        _ => (end, None),
+    };
+    rows.extend((start..end).map(|i| {
+        let current = active == Some(i);
+        let (marker, color) = if current {
+            ("▶ ", theme().lyric_current)
+        } else {
+            ("  ", theme().lyric_dim)
+        };
+        Row {
+            text: format!("  {}{}", marker, lyrics.lines[i].1),
+            color,
+            bold: current,
+        }
+    }));
+    if let (Some(idx), Some(frame)) = (marker_after, interlude) {
+        rows.insert(
+            idx - start + 1,
+            Row {
+                text: format!("    🎵 {}", "…".repeat(frame + 1)),
+                color: theme().interlude,
+                bold: false,
+            },
+        );
+    }
+    rows
+}
+
+/// 状态区域显示的播放模式，有声书模式下显示章节进度或播放速度
+pub fn mode_label(ui: &UiState) -> String {
+    match ui.mode {
+        PlaybackMode::Sequential => tr!(Msg::ModeNameSequential),
+        PlaybackMode::RepeatOne => tr!(Msg::ModeNameRepeatOne),
+        PlaybackMode::Shuffle => tr!(Msg::ModeNameShuffle),
+        PlaybackMode::Audiobook => match ui.chapter_progress() {
+            Some((n, total)) => tr!(Msg::ChapterProgress, n, total),
+            None => tr!(Msg::AudiobookStatus, format_speed(ui.speed)),
+        },
+    }
+}
+
+// 播放界面的全部行：状态区域（含进度条、VU 表、频谱面板、列表面板），然后是歌词区域
+fn interface_rows(ui: &UiState, pl: &PlaylistView, width: usize) -> Vec<Row> {
+    let now = if ui.now_name.is_empty() {
+        tr!(Msg::NotPlaying)
+    } else {
+        ui.now_name.clone()
+    };
+    let next = if ui.next_name.is_empty() {
+        tr!(Msg::NoneYet)
+    } else {
+        ui.next_name.clone()
+    };
+
+    // 播放状态区域
+    let [now_label, next_label] = pad_labels([Msg::StatusNow.text(), Msg::StatusNext.text()]);
+    // 歌名太长时截断，避免在窄终端中折行
+    let name_width = width.saturating_sub(now_label.width() + 3);
+    let (now, next) = (
+        truncate_width(&now, name_width),
+        truncate_width(&next, name_width),
+    );
+    let head = format!(
+        "{}\n{} {} {}\n  {} {}\n\n{}\n",
+        create_section_header(Msg::StatusHeader.text(), width, theme().border_style),
+        spinner_char(ui),
+        now_label,
+        now,
+        next_label,
+        next,
+        truncate_width(
+            &tr!(
+                Msg::StatusLine,
+                ui.play_state.label(),
+                mode_label(ui),
+                pl.len
+            ),
+            width
+        ),
+    );
+    let volume = tr!(
+        Msg::StatusVolume,
+        volume_bar(ui.volume.unwrap_or(50), width),
+        ui.volume_text()
+    );
+
+    let mut rows = Vec::new();
+    push_rows(&mut rows, &head, theme().title);
+    push_rows(&mut rows, &truncate_width(&volume, width), theme().accent);
+    push_rows(&mut rows, &progress_line(ui), theme().progress);
+    if ui.show_vu {
+        push_rows(&mut rows, &vu_meter_lines(ui.levels), theme().title);
+    }
+    if let Some(vis) = &ui.visualizer {
+        push_rows(&mut rows, &vis.lines(), theme().progress);
+    }
+    push_rows(&mut rows, &create_footer(), theme().title);
+    if ui.show_pane {
+        push_rows(&mut rows, &pane_lines(pl, width), theme().title);
+    }
+
+    // 调试区域
+    if ui.debug_overlay {
+        push_rows(
+            &mut rows,
+            &create_section_header(Msg::DebugHeader.text(), width, theme().border_style),
+            theme().accent,
+        );
+        for line in debug_lines(ui) {
+            push_rows(&mut rows, &truncate_width(&line, width), theme().accent);
+        }
+        push_rows(&mut rows, &create_footer(), theme().accent);
+    }
+
+    // 歌词区域
+    let lyrics = lyric_rows(ui);
+    if !lyrics.is_empty() {
+        push_rows(
+            &mut rows,
+            &create_section_header(Msg::LyricsHeader.text(), width, theme().border_style),
+            theme().accent,
+        );
+        rows.extend(lyrics);
+        push_rows(&mut rows, &create_footer(), theme().accent);
+    }
+    rows
+}
+
+// 帮助页的各行：放不下时按终端宽度分成多栏，仍然放不下时截断并以 … 结尾。
+// 栏宽不计占满整行的分隔线，分栏后分隔线按栏宽截断，居中的标题改为左对齐
+fn overlay_lines(text: &str, width: usize, height: usize) -> Vec<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let column_width = lines
+        .iter()
+        .map(|l| l.trim().width())
+        .filter(|&w| w < width)
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let columns = if lines.len() > height {
+        (width / column_width.max(1)).max(1)
+    } else {
+        1
+    };
+    let rows = lines.len().div_ceil(columns);
+    let mut out: Vec<String> = (0..rows)
+        .map(|r| {
+            let cells: Vec<&str> = (0..columns)
+                .filter_map(|c| lines.get(c * rows + r))
+                .map(|l| if columns > 1 { l.trim() } else { l })
+                .collect();
+            let mut line = String::new();
+            for (i, cell) in cells.iter().enumerate() {
+                if i + 1 < cells.len() {
+                    line.push_str(&fit_width(cell, column_width));
+                } else {
+                    line.push_str(cell);
+                }
+            }
+            truncate_width(&line, width)
+        })
+        .collect();
+    if out.len() > height {
+        out.truncate(height.saturating_sub(1));
+        out.push("…".to_string());
+    }
+    out
+}
+
+// 紧凑显示：第一行为播放状态，开启歌词时在其下方逐行显示，不画边框
+fn compact_rows(ui: &UiState, width: usize) -> Vec<Row> {
+    let mut rows = Vec::new();
+    push_rows(&mut rows, &compact_line(ui, width), theme().title);
+    rows.extend(lyric_rows(ui));
+    rows
+}
+
+/// 终端界面绘制器，由 UI 线程创建一次并复用
+#[derive(Default)]
+pub struct Screen {
+    last_rows: Option<Vec<Row>>, // 上次绘制的播放界面各行，之后只重写内容变化的行
+    last_layout: Option<Layout>, // 上次绘制时的布局，变化后清屏重绘
+    last_spinner: Option<char>,  // 上次绘制的播放中指示符
+    width: usize,                // 上次绘制时的界面宽度，终端大小变化后需要完整重绘
+    scan_frame: Option<usize>,   // 正在显示扫描进度时，进度行指示符的当前帧
+    last_bar: Option<String>,    // 上次绘制的底部状态栏，未变化时不重绘
+    last_progress: Option<ProgressMark>, // 上次原地重写时间时的状态
+    last_plain: Option<(Option<usize>, Option<usize>)>, // 逐行输出时上次输出的 (歌曲序号, 歌词行)
+    last_quiet: Option<String>,  // 安静模式下上次输出的状态行
+    quiet_total: usize,          // 安静模式状态行中显示的歌曲总数
+    full_redraws: usize,         // 完整重绘次数
+    partial_redraws: usize,      // 只重写变化行的次数
+}
+
+// 决定界面布局的数据，任何一项变化时都要清屏重绘
+#[derive(PartialEq)]
+struct Layout {
+    width: usize,
+    theme: Theme,
+    compact: bool,
+}
+
+// 原地重写播放时间时记下的状态，用于限制重写频率
+struct ProgressMark {
+    state: PlayState,
+    duration: Option<Duration>,
+    second: u128,
+    at: Instant,
+}
+
+// 播放时间原地重写的最短间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
 
 impl Screen {
-    pub fn new() -> std::io::Result<Self> {
-        Ok(Self)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 离开备用屏幕并清屏后显示启动时的初始界面，下次进入播放界面时完整重绘
+    pub fn show_start_screen(&mut self, text: &str) -> std::io::Result<()> {
+        self.last_rows = None;
+        self.last_layout = None;
+        leave_alt_screen()?;
+        if style().plain {
+            println!("{}", text);
+            return Ok(());
+        }
+        execute!(
+            stdout(),
+            Clear(ClearType::All),
+            MoveTo(0, 0),
+            Print(crlf(text))
+        )
+    }
+
+    /// 终端宽度是否与上次绘制播放界面时不同，由 UI 线程定期检查
+    pub fn resized(&self) -> bool {
+        self.width != ui_width()
+    }
+
+    /// 播放界面的重绘次数 (完整重绘, 只重写变化的行)
+    pub fn redraw_stats(&self) -> (usize, usize) {
+        (self.full_redraws, self.partial_redraws)
     }
 
     pub fn draw(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
@@ -170,13 +1456,18 @@ impl Screen {
 
         // 欢迎页显示（正常输出）
         if ui.show_welcome {
-            let welcome_content = create_title_bar("🎵 BeatCLI — Console Music Player");
+            let welcome_content = create_title_bar(
+                "🎵 BeatCLI — Console Music Player",
+                ui_width(),
+                theme().border_style,
+            );
+            print_colored(theme().title, &welcome_content)?;
             execute!(
                 stdout,
-                SetForegroundColor(UI_TITLE_COLOR),
-                Print(welcome_content),
-                ResetColor,
-                Print("\n      输入 /help 查看命令，/folder <path> 选择音乐目录\n\n>>： ")
+                Print(format!(
+                    "\n{}\n\n>>： ",
+                    center_line(Msg::WelcomeHint.text())
+                ))
             )?;
             std::io::stdout().flush()?;
             return Ok(());
@@ -184,8 +1475,9 @@ impl Screen {
 
         // 进入播放模式时清屏并显示播放界面
         if ui.now_index.is_some() && !ui.playing_ui_active {
-            execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
             ui.playing_ui_active = true;
+            self.last_rows = None;
+            self.last_layout = None;
 
             // 显示播放界面，不显示输入提示符
             self.show_playing_interface(ui, pl)?;
@@ -193,279 +1485,647 @@ impl Screen {
             return Ok(());
         }
 
-        // 在播放模式下，检查歌词是否变化
-        if ui.playing_ui_active && ui.show_lyrics {
-            // 检查歌词是否变化
-            if let Some(lyrics) = &ui.lyrics {
-                if !lyrics.lines.is_empty() {
-                    let current_idx = lyrics.current_line_index(ui.current_ms);
-                    let old_idx = ui.current_lyric_line.unwrap_or(usize::MAX);
-
-                    if current_idx != old_idx {
-                        ui.current_lyric_line = Some(current_idx);
-
-                        // 根据模式选择不同的刷新方式
-                        if ui.lyrics_stream_mode {
-                            // 流式输出模式：只更新歌词区域
-                            self.stream_update_lyrics(ui, current_idx)?;
-                        } else {
-                            // 清屏模式：重新显示整个界面
-                            execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
-                            self.show_playing_interface(ui, pl)?;
-                        }
-
-                        std::io::stdout().flush()?;
-                        return Ok(());
-                    }
-                }
+        // 在播放模式下，歌词行变化时重绘（流式模式下只重写变化的几行）
+        if ui.playing_ui_active
+            && ui.show_lyrics
+            && !ui.quiet_mode
+            && let Some(lyrics) = &ui.lyrics
+            && !lyrics.lines.is_empty()
+        {
+            let active = lyrics.active_line(ui.current_ms);
+            let interlude = interlude_frame(lyrics, ui.current_ms);
+            if ui.current_lyric_line != active || ui.interlude_frame != interlude {
+                ui.current_lyric_line = active;
+                ui.interlude_frame = interlude;
+                self.show_playing_interface(ui, pl)?;
+                std::io::stdout().flush()?;
+                return Ok(());
             }
         }
 
         // 显示Flash消息（正常输出）
-        if let Some((msg, level)) = &ui.flash {
+        while let Some((msg, level)) = ui.next_flash() {
             let (prefix, color) = match level {
-                FlashLevel::Info => ("ℹ ", UI_INFO_COLOR),
-                FlashLevel::Ok => ("✓ ", UI_SUCCESS_COLOR),
-                FlashLevel::Error => ("✗ ", UI_ERROR_COLOR),
+                FlashLevel::Info => ("ℹ ", theme().info),
+                FlashLevel::Ok => ("✓ ", theme().ok),
+                FlashLevel::Error => ("✗ ", theme().error),
             };
 
-            execute!(
-                stdout,
-                SetForegroundColor(color),
-                Print(prefix),
-                ResetColor,
-                Print(msg),
-                Print("\n")
-            )?;
+            // 安静模式下消息输出在状态行的位置，之后在下一行重新输出状态行
+            let quiet = ui.playing_ui_active && ui.quiet_mode && !style().plain;
+            if quiet && !prompt_open(ui) {
+                execute!(stdout, Print('\r'), Clear(ClearType::CurrentLine))?;
+            }
+            print_colored(color, prefix)?;
+            execute!(stdout, Print(crlf(&msg)), Print(crlf("\n")))?;
 
             // 在播放模式下显示输入提示符
-            if ui.playing_ui_active {
-                print!(">>： ");
+            if quiet {
+                self.print_quiet_status(ui)?;
+            } else if ui.playing_ui_active && !style().plain {
+                print!("{}", input_hint(ui));
             }
 
-            ui.flash = None;
+            // 消息输出后屏幕可能滚动，各行的位置不再可靠，下次从第一行起覆盖整个界面
+            self.last_rows = None;
         }
 
         std::io::stdout().flush()
     }
 
-    // 显示完整的播放界面
-    fn show_playing_interface(&self, ui: &UiState, pl: &PlaylistView) -> std::io::Result<()> {
-        let mut stdout = stdout();
-
-        let now = if ui.now_name.is_empty() {
-            "(未播放)".to_string()
+    // 显示播放界面，返回是否完整重绘。流式模式下布局和行数不变时只重写内容变化的行；
+    // 否则从第一行起覆盖整个界面，布局变化或清屏模式时先清屏。完整重绘后光标位于界面下方
+    fn show_playing_interface(
+        &mut self,
+        ui: &mut UiState,
+        pl: &PlaylistView,
+    ) -> std::io::Result<bool> {
+        if style().plain {
+            self.print_plain_status(ui)?;
+            return Ok(false);
+        }
+        if ui.quiet_mode {
+            leave_alt_screen()?;
+            self.last_rows = None;
+            self.last_layout = None;
+            self.quiet_total = pl.len;
+            self.print_quiet_status(ui)?;
+            return Ok(false);
+        }
+        // 刚进入备用屏幕时屏幕是空的，需要完整重绘
+        if enter_alt_screen()? {
+            self.last_rows = None;
+            self.last_layout = None;
+        }
+        let width = ui_width();
+        self.width = width;
+        ui.update_compact();
+        let rows = if ui.compact_mode {
+            compact_rows(ui, width)
         } else {
-            ui.now_name.clone()
+            interface_rows(ui, pl, width)
         };
-        let next = if ui.next_name.is_empty() {
-            "(无)".to_string()
-        } else {
-            ui.next_name.clone()
+        let layout = Layout {
+            width,
+            theme: theme(),
+            compact: ui.compact_mode,
         };
-
-        // 播放状态区域
-        let status_content = format!(
-            "{}\n  当前播放: {}\n  下一首:   {}\n\n  播放模式: {}    音量: {}%    播放列表: {} 首\n{}",
-            create_section_header("🎵 播放状态"),
-            now,
-            next,
-            match ui.mode {
-                PlaybackMode::Sequential => "顺序播放",
-                PlaybackMode::RepeatOne => "单曲循环",
-                PlaybackMode::Shuffle => "随机播放",
-            },
-            ui.volume.unwrap_or(50),
-            pl.len,
-            create_footer()
-        );
-
-        execute!(
-            stdout,
-            SetForegroundColor(UI_TITLE_COLOR),
-            Print(status_content),
-            ResetColor
-        )?;
-
-        // 歌词区域
-        if ui.show_lyrics {
-            if let Some(lyrics) = &ui.lyrics {
-                if !lyrics.lines.is_empty() {
-                    let current_ms = ui.current_ms;
-                    let current_idx = lyrics.current_line_index(current_ms);
-                    let start = current_idx.saturating_sub(3);
-                    let end = (current_idx + 4).min(lyrics.lines.len());
-
-                    let mut lyrics_content = String::new();
-                    lyrics_content.push_str(&create_section_header("🎶 歌词"));
-
-                    for i in start..end {
-                        let (_, ref text) = lyrics.lines[i];
-                        if i == current_idx {
-                            lyrics_content.push_str(&format!("  \x1b[32m▶ {}\x1b[0m\n", text)); // 绿色高亮
-                        } else {
-                            lyrics_content.push_str(&format!("    {}\n", text));
-                        }
+        let same_layout = self.last_layout.as_ref() == Some(&layout);
+
+        let mut out = stdout().lock();
+        let full = match &self.last_rows {
+            Some(old) if same_layout && ui.lyrics_stream_mode && old.len() == rows.len() => {
+                queue!(out, SavePosition)?;
+                for (i, (row, old)) in rows.iter().zip(old).enumerate() {
+                    if row != old {
+                        queue!(out, MoveTo(0, i as u16))?;
+                        queue_row(&mut out, row, width)?;
                     }
-
-                    lyrics_content.push_str(&create_footer());
-
-                    execute!(
-                        stdout,
-                        SetForegroundColor(UI_INFO_COLOR),
-                        Print(lyrics_content),
-                        ResetColor
-                    )?;
                 }
+                queue!(out, RestorePosition)?;
+                self.partial_redraws += 1;
+                false
             }
+            _ => {
+                if !same_layout || !ui.lyrics_stream_mode {
+                    queue!(out, Clear(ClearType::All))?;
+                }
+                queue!(out, MoveTo(0, 0))?;
+                for row in &rows {
+                    queue_row(&mut out, row, width)?;
+                    queue!(out, Print("\r\n"))?;
+                }
+                queue!(out, Clear(ClearType::FromCursorDown))?;
+                self.last_bar = None;
+                self.full_redraws += 1;
+                true
+            }
+        };
+        out.flush()?;
+        drop(out);
+
+        self.last_spinner = Some(spinner_char(ui));
+        self.last_rows = Some(rows);
+        self.last_layout = Some(layout);
+        self.draw_status_bar(ui)?;
+        Ok(full)
+    }
+
+    // 逐行输出时代替播放界面：换歌时输出歌名，歌词行变化时输出新的一行
+    fn print_plain_status(&mut self, ui: &UiState) -> std::io::Result<()> {
+        let lyric = ui
+            .lyrics
+            .as_ref()
+            .filter(|_| ui.show_lyrics && !ui.quiet_mode)
+            .and_then(|l| l.active_line(ui.current_ms).map(|i| (i, &l.lines[i].1)));
+        let state = (ui.now_index, lyric.map(|(i, _)| i));
+        let last = self.last_plain.replace(state);
+        let mut out = stdout().lock();
+        if last.map(|(song, _)| song) != Some(ui.now_index) && ui.now_index.is_some() {
+            writeln!(out, "▶ {} {}", Msg::StatusNow.text(), ui.now_name)?;
+        }
+        if last != Some(state)
+            && let Some((_, text)) = lyric
+        {
+            writeln!(out, "  ♪ {}", text)?;
         }
+        out.flush()
+    }
 
-        Ok(())
+    // 安静模式下在光标所在行原地重写状态行，不换行。
+    // 状态行就在光标所在行，按 : 输入命令时不输出，以免覆盖正在输入的内容
+    fn print_quiet_status(&mut self, ui: &UiState) -> std::io::Result<()> {
+        if prompt_open(ui) {
+            return Ok(());
+        }
+        let line = quiet_line(ui, self.quiet_total, ui_width());
+        let mut out = stdout().lock();
+        queue!(
+            out,
+            Print('\r'),
+            Clear(ClearType::CurrentLine),
+            Theme::paint(theme().title),
+            Print(&line),
+            Theme::unpaint()
+        )?;
+        self.last_quiet = Some(line);
+        out.flush()
     }
 
-    // 流式更新歌词（高度优化，避免闪屏）
-    fn stream_update_lyrics(
-        &mut self,
-        ui: &mut UiState,
-        current_idx: usize,
-    ) -> std::io::Result<()> {
-        if let Some(lyrics) = &ui.lyrics {
-            if lyrics.lines.is_empty() {
+    /// 原地刷新进度条所在的行（紧凑显示时为第一行的状态行，安静模式下为唯一的状态行），
+    /// 不影响歌词区域；内容未变化时不输出
+    pub fn update_progress(&mut self, ui: &UiState) -> std::io::Result<()> {
+        if style().plain {
+            return Ok(());
+        }
+        if ui.quiet_mode {
+            if self.last_quiet.as_deref() == Some(&quiet_line(ui, self.quiet_total, ui_width())) {
                 return Ok(());
             }
+            return self.print_quiet_status(ui);
+        }
+        let (row, line, color) = if ui.compact_mode {
+            (0, compact_line(ui, self.width), theme().title)
+        } else {
+            (PROGRESS_ROW, progress_line(ui), theme().progress)
+        };
+        let cached = self
+            .last_rows
+            .as_mut()
+            .and_then(|rows| rows.get_mut(row as usize));
+        if cached.as_ref().is_some_and(|r| r.text == line) {
+            return Ok(());
+        }
+        let mut out = stdout().lock();
+        queue!(
+            out,
+            SavePosition,
+            MoveTo(0, row),
+            Clear(ClearType::CurrentLine),
+            Theme::paint(color),
+            Print(&line),
+            Theme::unpaint(),
+            RestorePosition
+        )?;
+        if let Some(cached) = cached {
+            cached.text = line;
+        }
+        out.flush()
+    }
 
-            let start = current_idx.saturating_sub(3);
-            let end = (current_idx + 4).min(lyrics.lines.len());
+    /// 在光标所在行（消息区域）显示扫描进度，每次调用覆盖上一次的内容并换一帧指示符
+    pub fn show_scan_progress(&mut self, seen: usize, found: usize) -> std::io::Result<()> {
+        if style().plain {
+            return Ok(());
+        }
+        let frame = self.scan_frame.map_or(0, |f| f + 1);
+        self.scan_frame = Some(frame);
+        let line = format!(
+            "{} {}",
+            SPINNER_FRAMES[frame % SPINNER_FRAMES.len()],
+            tr!(Msg::ScanProgress, seen, found)
+        );
+        let mut out = stdout().lock();
+        queue!(
+            out,
+            Print('\r'),
+            Clear(ClearType::CurrentLine),
+            Theme::paint(theme().info),
+            Print(truncate_width(&line, ui_width())),
+            Theme::unpaint()
+        )?;
+        out.flush()
+    }
 
-            // 如果范围没有变化且只是当前行的高亮变化，使用更精细的更新
-            if let Some((last_start, last_end)) = ui.last_lyrics_range {
-                if start == last_start && end == last_end {
-                    return self.update_lyrics_highlight_only(ui, current_idx, start, end);
-                }
-            }
+    /// 扫描结束后清除进度行，之后的消息从行首输出
+    pub fn clear_scan_progress(&mut self) -> std::io::Result<()> {
+        if self.scan_frame.take().is_none() {
+            return Ok(());
+        }
+        execute!(stdout(), Print('\r'), Clear(ClearType::CurrentLine))
+    }
 
-            // 初始化位置
-            if ui.lyrics_base_row.is_none() {
-                ui.lyrics_base_row = Some(10);
-            }
+    /// 在终端最后一行绘制状态栏（配置 status_bar = true 时），内容未变化时不输出
+    pub fn draw_status_bar(&mut self, ui: &UiState) -> std::io::Result<()> {
+        if !style().status_bar || style().plain || ui.quiet_mode {
+            return Ok(());
+        }
+        let Ok((_, rows)) = terminal::size() else {
+            return Ok(());
+        };
+        let line = status_bar_line(ui, ui_width());
+        if self.last_bar.as_ref() == Some(&line) {
+            return Ok(());
+        }
+        let mut out = stdout().lock();
+        queue!(
+            out,
+            SavePosition,
+            MoveTo(0, rows.saturating_sub(1)),
+            Clear(ClearType::CurrentLine),
+            Theme::paint(theme().title),
+            Print(&line),
+            Theme::unpaint(),
+            RestorePosition
+        )?;
+        self.last_bar = Some(line);
+        out.flush()
+    }
 
-            let base_row = ui.lyrics_base_row.unwrap();
+    // 进度行（或紧凑、安静模式的状态行）和状态栏中的时间每秒最多重写一次；
+    // 暂停、继续、换歌和向后跳转时立即重写，暂停期间时间不变所以不再重写
+    fn progress_due(&mut self, ui: &UiState) -> bool {
+        let second = ui.current_ms / 1000;
+        let due = self.last_progress.as_ref().is_none_or(|mark| {
+            mark.state != ui.play_state
+                || mark.duration != ui.duration
+                || second < mark.second
+                || (second != mark.second && mark.at.elapsed() >= PROGRESS_INTERVAL)
+        });
+        if due {
+            self.last_progress = Some(ProgressMark {
+                state: ui.play_state,
+                duration: ui.duration,
+                second,
+                at: Instant::now(),
+            });
+        }
+        due
+    }
 
-            // 保存光标位置
-            print!("\x1b7"); // 保存光标位置
+    /// 原地刷新正在播放一行行首的指示符，只重绘这一个字符
+    pub fn update_spinner(&mut self, ui: &UiState) -> std::io::Result<()> {
+        let spinner = spinner_char(ui);
+        if ui.compact_mode || self.last_rows.is_none() || self.last_spinner == Some(spinner) {
+            return Ok(());
+        }
+        let mut out = stdout().lock();
+        queue!(
+            out,
+            SavePosition,
+            MoveTo(0, NOW_ROW),
+            Theme::paint(theme().title),
+            Print(spinner),
+            Theme::unpaint(),
+            RestorePosition
+        )?;
+        self.last_spinner = Some(spinner);
+        out.flush()
+    }
 
-            // 一次性构建所有更新内容，减少IO操作
-            let mut buffer = String::with_capacity(1024);
+    // 原地刷新 VU 表的两行，不影响界面其余部分
+    pub fn update_vu_meter(&self, ui: &UiState) -> std::io::Result<()> {
+        if ui.compact_mode || ui.quiet_mode || style().plain {
+            return Ok(());
+        }
+        let mut out = stdout().lock();
+        queue!(out, SavePosition)?;
+        for (offset, line) in vu_meter_lines(ui.levels).lines().enumerate() {
+            queue!(
+                out,
+                MoveTo(0, VU_FIRST_ROW + offset as u16),
+                Clear(ClearType::CurrentLine),
+                Print(line)
+            )?;
+        }
+        queue!(out, RestorePosition)?;
+        out.flush()
+    }
 
-            // 更新歌词区域
-            for (line_offset, i) in (start..end).enumerate() {
-                let row = base_row + line_offset as u16 + 1;
-                let (_, ref text) = lyrics.lines[i];
+    /// 原地刷新频谱面板（位于进度条和 VU 表之后），不影响界面其余部分
+    pub fn update_visualizer(&self, ui: &UiState) -> std::io::Result<()> {
+        let Some(vis) = ui
+            .visualizer
+            .as_ref()
+            .filter(|_| !ui.compact_mode && !ui.quiet_mode && !style().plain)
+        else {
+            return Ok(());
+        };
+        let first_row = VU_FIRST_ROW + if ui.show_vu { 2 } else { 0 };
+        let mut out = stdout().lock();
+        queue!(out, SavePosition, Theme::paint(theme().progress))?;
+        for (offset, line) in vis.lines().lines().enumerate() {
+            queue!(
+                out,
+                MoveTo(0, first_row + offset as u16),
+                Clear(ClearType::CurrentLine),
+                Print(line)
+            )?;
+        }
+        queue!(out, Theme::unpaint(), RestorePosition)?;
+        out.flush()
+    }
 
-                // 使用ANSI转义序列移动光标到指定位置
-                buffer.push_str(&format!("\x1b[{};1H", row));
+    /// 在播放界面的位置显示帮助页，最后一行提示按任意键返回；关闭前由 UI 线程暂停播放界面的刷新
+    pub fn show_overlay(&mut self, text: &str) -> std::io::Result<()> {
+        let (width, height) = (ui_width(), terminal::size().map_or(24, |(_, h)| h as usize));
+        let mut out = stdout().lock();
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        for line in overlay_lines(text, width, height.saturating_sub(1)) {
+            queue!(out, Print(line), Print("\r\n"))?;
+        }
+        queue!(
+            out,
+            MoveTo(0, height.saturating_sub(1) as u16),
+            Theme::paint(theme().info),
+            Print(truncate_width(Msg::OverlayHint.text(), width)),
+            Theme::unpaint()
+        )?;
+        out.flush()
+    }
 
-                if i == current_idx {
-                    // 当前高亮行：绿色 + 箭头
-                    buffer.push_str(&format!(
-                        "\x1b[32m\x1b[1m  ▶ {:<width$}\x1b[0m",
-                        text,
-                        width = UI_WIDTH.saturating_sub(4)
-                    ));
-                } else {
-                    // 普通行：灰色
-                    buffer.push_str(&format!(
-                        "\x1b[90m    {:<width$}\x1b[0m",
-                        text,
-                        width = UI_WIDTH.saturating_sub(4)
-                    ));
-                }
+    /// 关闭帮助页：先按原样重绘进入帮助页前的播放界面，再按当前状态更新变化的行
+    pub fn close_overlay(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        if let Some(rows) = &self.last_rows {
+            let mut out = stdout().lock();
+            queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+            for row in rows {
+                queue_row(&mut out, row, self.width)?;
+                queue!(out, Print("\r\n"))?;
             }
+            queue!(out, Print(input_hint(ui)))?;
+            out.flush()?;
+        }
+        self.last_bar = None;
+        self.force_refresh_playing_interface(ui, pl)
+    }
 
-            // 清理下方可能的剩余行
-            for line_offset in (end - start)..7 {
-                let row = base_row + line_offset as u16 + 1;
-                buffer.push_str(&format!("\x1b[{};1H{:<width$}", row, "", width = UI_WIDTH));
-            }
+    pub fn force_refresh_playing_interface(
+        &mut self,
+        ui: &mut UiState,
+        pl: &PlaylistView,
+    ) -> std::io::Result<()> {
+        // 只重写了变化的行时光标仍在原来的输入行，不再输出提示符
+        if self.show_playing_interface(ui, pl)? {
+            print!("{}", input_hint(ui));
+        }
+        std::io::stdout().flush()?;
 
-            // 一次性输出所有内容，然后恢复光标
-            print!("{}", buffer);
-            print!("\x1b8"); // 恢复光标位置
+        Ok(())
+    }
+}
 
-            // 更新记录的范围
-            ui.last_lyrics_range = Some((start, end));
+/// 界面绘制器，UI 线程按收到的事件调用。默认使用按行绘制的 Screen，--tui 时使用全屏的 Tui
+pub trait Renderer {
+    /// 按当前状态绘制：欢迎页、进入播放界面、歌词行变化和提示消息
+    fn draw(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()>;
+    /// 重绘整个播放界面
+    fn refresh(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()>;
+    /// 播放进度变化：更新进度条、播放中指示符和状态栏
+    fn update_progress(&mut self, ui: &UiState) -> std::io::Result<()>;
+    fn update_vu_meter(&mut self, ui: &UiState) -> std::io::Result<()>;
+    fn update_visualizer(&mut self, ui: &UiState) -> std::io::Result<()>;
+    /// 终端大小是否变化，变化后由 UI 线程调用 refresh
+    fn resized(&self) -> bool;
+    fn show_start_screen(&mut self, text: &str) -> std::io::Result<()>;
+    fn show_scan_progress(
+        &mut self,
+        ui: &UiState,
+        seen: usize,
+        found: usize,
+    ) -> std::io::Result<()>;
+    fn clear_scan_progress(&mut self) -> std::io::Result<()>;
+    /// 退出前显示告别信息
+    fn show_goodbye(&mut self);
+    /// 播放界面的重绘次数 (完整重绘, 只重写变化的行)
+    fn redraw_stats(&self) -> (usize, usize);
+    /// 在播放界面上显示帮助页，关闭前 UI 线程不再刷新播放界面
+    fn show_overlay(&mut self, text: &str) -> std::io::Result<()>;
+    /// 关闭帮助页，恢复显示前的播放界面
+    fn close_overlay(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()>;
+}
+
+impl Renderer for Screen {
+    fn draw(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        Screen::draw(self, ui, pl)
+    }
 
-            // 刷新输出
-            std::io::Write::flush(&mut std::io::stdout())?;
+    fn refresh(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        self.force_refresh_playing_interface(ui, pl)
+    }
+
+    fn update_progress(&mut self, ui: &UiState) -> std::io::Result<()> {
+        if self.progress_due(ui) {
+            Screen::update_progress(self, ui)?;
+            self.draw_status_bar(ui)?;
         }
+        self.update_spinner(ui)
+    }
 
-        Ok(())
+    fn update_vu_meter(&mut self, ui: &UiState) -> std::io::Result<()> {
+        Screen::update_vu_meter(self, ui)
     }
 
-    // 只更新高亮状态，不移动文本
-    fn update_lyrics_highlight_only(
-        &self,
-        ui: &mut UiState,
-        current_idx: usize,
-        start: usize,
-        end: usize,
+    fn update_visualizer(&mut self, ui: &UiState) -> std::io::Result<()> {
+        Screen::update_visualizer(self, ui)
+    }
+
+    fn resized(&self) -> bool {
+        Screen::resized(self)
+    }
+
+    fn show_start_screen(&mut self, text: &str) -> std::io::Result<()> {
+        Screen::show_start_screen(self, text)
+    }
+
+    fn show_scan_progress(
+        &mut self,
+        _ui: &UiState,
+        seen: usize,
+        found: usize,
     ) -> std::io::Result<()> {
-        if let Some(lyrics) = &ui.lyrics {
-            let base_row = ui.lyrics_base_row.unwrap();
+        Screen::show_scan_progress(self, seen, found)
+    }
 
-            print!("\x1b7"); // 保存光标位置
+    fn clear_scan_progress(&mut self) -> std::io::Result<()> {
+        Screen::clear_scan_progress(self)
+    }
 
-            let mut buffer = String::with_capacity(512);
+    fn show_goodbye(&mut self) {
+        show_goodbye_message();
+    }
 
-            // 只更新颜色，不移动文本
-            for (line_offset, i) in (start..end).enumerate() {
-                let row = base_row + line_offset as u16 + 1;
-                let (_, ref text) = lyrics.lines[i];
+    fn redraw_stats(&self) -> (usize, usize) {
+        Screen::redraw_stats(self)
+    }
 
-                buffer.push_str(&format!("\x1b[{};1H", row));
+    fn show_overlay(&mut self, text: &str) -> std::io::Result<()> {
+        Screen::show_overlay(self, text)
+    }
 
-                if i == current_idx {
-                    // 当前高亮行
-                    buffer.push_str(&format!(
-                        "\x1b[32m\x1b[1m  ▶ {:<width$}\x1b[0m",
-                        text,
-                        width = UI_WIDTH.saturating_sub(4)
-                    ));
-                } else {
-                    // 普通行
-                    buffer.push_str(&format!(
-                        "\x1b[90m    {:<width$}\x1b[0m",
-                        text,
-                        width = UI_WIDTH.saturating_sub(4)
-                    ));
-                }
-            }
+    fn close_overlay(&mut self, ui: &mut UiState, pl: &PlaylistView) -> std::io::Result<()> {
+        Screen::close_overlay(self, ui, pl)
+    }
+}
 
-            print!("{}", buffer);
-            print!("\x1b8"); // 恢复光标位置
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_time_is_rewritten_at_most_once_per_second() {
+        let mut screen = Screen::new();
+        let mut ui = UiState {
+            play_state: PlayState::Playing,
+            duration: Some(Duration::from_secs(180)),
+            current_ms: 5_000,
+            ..UiState::default()
+        };
+        assert!(screen.progress_due(&ui));
+        // 同一秒内和不到一秒后的下一秒都不重写
+        ui.current_ms = 5_600;
+        assert!(!screen.progress_due(&ui));
+        ui.current_ms = 6_100;
+        assert!(!screen.progress_due(&ui));
+        screen.last_progress.as_mut().unwrap().at -= PROGRESS_INTERVAL;
+        assert!(screen.progress_due(&ui));
+
+        // 暂停时立即显示暂停标记，之后时间不变不再重写
+        ui.play_state = PlayState::Paused;
+        assert!(screen.progress_due(&ui));
+        assert!(!screen.progress_due(&ui));
+        ui.play_state = PlayState::Playing;
+        assert!(screen.progress_due(&ui));
+
+        // 换歌后从 0 开始
+        ui.current_ms = 0;
+        ui.duration = Some(Duration::from_secs(200));
+        assert!(screen.progress_due(&ui));
+    }
 
-            std::io::Write::flush(&mut std::io::stdout())?;
+    #[test]
+    fn fit_width_pads_and_truncates_by_display_width() {
+        let cases = [
+            "abc",
+            "晴天 Jay",
+            "e\u{301}te\u{301}",
+            "🎵 间奏 music",
+            "tab\there",
+            "一二三四五六七八九十 long line",
+        ];
+        for width in 0..16 {
+            for text in cases {
+                assert_eq!(fit_width(text, width).width(), width, "{text:?} {width}");
+            }
         }
+        assert_eq!(fit_width("中文字", 4), "中… ");
+        assert_eq!(fit_width("e\u{301}", 3), "e\u{301}  ");
+        assert_eq!(fit_width("a\tb", 3), "a b");
+    }
 
-        Ok(())
+    #[test]
+    fn lyric_rows_fill_the_window_width() {
+        let lyrics = crate::lyrics::Lyrics::parse(
+            "[00:01.00]晴天 Sunny day\n[00:02.00]🎵 cafe\u{301} 咖啡\n[00:03.00]abc",
+        );
+        let ui = UiState {
+            lyrics: Some(lyrics),
+            show_lyrics: true,
+            current_ms: 2_000,
+            ..UiState::default()
+        };
+        let rows = lyric_rows(&ui);
+        assert_eq!(rows.iter().filter(|r| r.bold).count(), 1);
+        for width in [8, 13, 20, 40] {
+            for row in &rows {
+                let mut out = Vec::new();
+                queue_row(&mut out, row, width).unwrap();
+                let printed = String::from_utf8(out).unwrap();
+                let line = fit_width(&row.text, width);
+                assert_eq!(line.width(), width);
+                assert!(printed.contains(&line));
+            }
+        }
     }
-    pub fn force_refresh_playing_interface(
-        &self,
-        ui: &mut UiState,
-        pl: &PlaylistView,
-    ) -> std::io::Result<()> {
-        use crossterm::cursor::MoveTo;
-        use crossterm::execute;
-        use crossterm::terminal::{Clear, ClearType};
 
-        let mut stdout = stdout();
+    #[test]
+    fn lyrics_preferences_survive_a_track_change() {
+        let mut ui = UiState::default();
+        let (show, stream) = (ui.show_lyrics, ui.lyrics_stream_mode);
+        ui.toggle_lyrics();
+        ui.toggle_lyrics_mode();
+        ui.set_now_playing(1, "b.mp3".into(), "c.mp3".into());
+        assert_eq!(ui.show_lyrics, !show);
+        assert_eq!(ui.lyrics_stream_mode, !stream);
+        ui.reset_playing();
+        ui.set_now_playing(2, "c.mp3".into(), String::new());
+        assert_eq!(ui.show_lyrics, !show);
+        assert_eq!(ui.lyrics_stream_mode, !stream);
+    }
 
-        // 强制清屏并重新显示播放界面
-        execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
-        self.show_playing_interface(ui, pl)?;
-        print!(">>： ");
-        std::io::stdout().flush()?;
+    #[test]
+    fn paint_writes_nothing_when_color_is_disabled() {
+        use crossterm::Command;
+
+        let t = Theme::DEFAULT;
+        let colors = [
+            t.title,
+            t.accent,
+            t.ok,
+            t.error,
+            t.info,
+            t.lyric_current,
+            t.lyric_dim,
+        ];
+        for color in colors.into_iter().map(Some).chain([None]) {
+            let mut plain = String::new();
+            Paint {
+                color,
+                enabled: false,
+            }
+            .write_ansi(&mut plain)
+            .unwrap();
+            assert_eq!(plain, "");
+            let mut colored = String::new();
+            Paint {
+                color,
+                enabled: true,
+            }
+            .write_ansi(&mut colored)
+            .unwrap();
+            assert!(colored.starts_with("\x1b["));
+        }
+    }
 
-        Ok(())
+    #[test]
+    fn lyric_rows_carry_no_escape_sequences() {
+        let lyrics =
+            crate::lyrics::Lyrics::parse("[00:01.00]first\n[00:09.00]second\n[00:10.00]third");
+        let mut ui = UiState {
+            lyrics: Some(lyrics),
+            show_lyrics: true,
+            ..UiState::default()
+        };
+        // 前奏、高亮行和间奏标记
+        for ms in [0, 1_000, 5_000, 9_500] {
+            ui.current_ms = ms;
+            let rows = lyric_rows(&ui);
+            assert!(!rows.is_empty());
+            for row in rows {
+                assert!(!row.text.contains('\x1b'), "{:?}", row.text);
+            }
+        }
+        ui.current_ms = 1_000;
+        assert!(lyric_rows(&ui).iter().any(|r| r.text.contains("▶ first")));
     }
 }
@@ -0,0 +1,92 @@
+use crate::cue;
+use crate::i18n::{Msg, tr};
+use crate::playlist::{Playlist, SortKey};
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+
+// 最多保留的可撤销操作数，超出时丢弃最早的
+const MAX_UNDO: usize = 10;
+
+/// 改变播放列表、可以用 /undo 撤销的操作，保存操作前的列表
+pub enum UndoAction {
+    Shuffle {
+        original: Vec<PathBuf>,
+    },
+    Sort {
+        original: Vec<PathBuf>,
+        key: SortKey,
+    },
+    Dedup {
+        original: Vec<PathBuf>,
+        removed: usize,
+    },
+    Remove {
+        original: Vec<PathBuf>,
+        removed: usize,
+    },
+    Clear {
+        original: Vec<PathBuf>,
+    },
+}
+
+impl UndoAction {
+    /// 撤销时显示的操作说明
+    pub fn description(&self) -> String {
+        match self {
+            UndoAction::Shuffle { .. } => tr!(Msg::UndoShuffle),
+            UndoAction::Sort { key, .. } => {
+                let key_name = match key {
+                    SortKey::Name => Msg::SortName.text(),
+                    SortKey::TrackNumberInferred => Msg::SortTrack.text(),
+                };
+                tr!(Msg::UndoSort, key_name)
+            }
+            UndoAction::Dedup { removed, .. } => tr!(Msg::UndoDedup, removed),
+            UndoAction::Remove { removed, .. } => tr!(Msg::UndoRemove, removed),
+            UndoAction::Clear { original } => tr!(Msg::UndoClear, original.len()),
+        }
+    }
+
+    /// 把播放列表恢复为操作前的顺序。
+    /// 之后删除的文件不再恢复，之后添加的歌曲保留在列表末尾
+    pub fn restore(self, pl: &mut Playlist) {
+        let (UndoAction::Shuffle { original }
+        | UndoAction::Sort { original, .. }
+        | UndoAction::Dedup { original, .. }
+        | UndoAction::Remove { original, .. }
+        | UndoAction::Clear { original }) = self;
+        let known: HashSet<&PathBuf> = original.iter().collect();
+        let current: HashSet<&PathBuf> = pl.items.iter().collect();
+        let mut items: Vec<PathBuf> = original
+            .iter()
+            .filter(|p| current.contains(p) || cue::audio_file(p).exists())
+            .cloned()
+            .collect();
+        items.extend(pl.items.iter().filter(|p| !known.contains(p)).cloned());
+        pl.restore_items(items);
+    }
+}
+
+/// 最近的可撤销操作，最新的在末尾
+#[derive(Default)]
+pub struct UndoStack {
+    actions: VecDeque<UndoAction>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, action: UndoAction) {
+        if self.actions.len() >= MAX_UNDO {
+            self.actions.pop_front();
+        }
+        self.actions.push_back(action);
+    }
+
+    pub fn pop(&mut self) -> Option<UndoAction> {
+        self.actions.pop_back()
+    }
+
+    /// 播放列表被整体替换（如扫描新文件夹）后，之前的操作不再能撤销
+    pub fn clear(&mut self) {
+        self.actions.clear();
+    }
+}
@@ -0,0 +1,94 @@
+use crossbeam_channel::{Receiver, Sender, select, unbounded};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+// 收到事件后等待这么久没有新事件再通知，合并批量复制等产生的连续事件
+const DEBOUNCE: Duration = Duration::from_millis(500);
+// 事件一直不断时最多等待这么久也要通知一次
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// 监视音乐文件夹中文件的增加、删除和重命名。
+/// 析构时停止监视并等待后台线程退出
+pub struct FolderWatcher {
+    watcher: Option<RecommendedWatcher>,
+    stop_tx: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl FolderWatcher {
+    /// 开始递归监视文件夹，变化的路径经过去抖合并后传给 on_change
+    pub fn start<F>(folder: &Path, on_change: F) -> notify::Result<Self>
+    where
+        F: Fn(Vec<PathBuf>) + Send + 'static,
+    {
+        let (event_tx, event_rx) = unbounded();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if let Ok(event) = res {
+                let _ = event_tx.send(event);
+            }
+        })?;
+        watcher.watch(folder, RecursiveMode::Recursive)?;
+
+        let (stop_tx, stop_rx) = unbounded();
+        let handle = thread::spawn(move || debounce(event_rx, stop_rx, on_change));
+        Ok(Self {
+            watcher: Some(watcher),
+            stop_tx: Some(stop_tx),
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        self.watcher = None;
+        // 关闭停止通道，后台线程随之退出
+        self.stop_tx = None;
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn debounce<F: Fn(Vec<PathBuf>)>(event_rx: Receiver<Event>, stop_rx: Receiver<()>, on_change: F) {
+    loop {
+        let mut paths = BTreeSet::new();
+        select! {
+            recv(event_rx) -> event => match event {
+                Ok(event) => collect_paths(&mut paths, event),
+                Err(_) => return,
+            },
+            recv(stop_rx) -> _ => return,
+        }
+
+        let first = Instant::now();
+        while first.elapsed() < MAX_DELAY {
+            select! {
+                recv(event_rx) -> event => match event {
+                    Ok(event) => collect_paths(&mut paths, event),
+                    Err(_) => return,
+                },
+                recv(stop_rx) -> _ => return,
+                default(DEBOUNCE) => break,
+            }
+        }
+
+        if !paths.is_empty() {
+            on_change(paths.into_iter().collect());
+        }
+    }
+}
+
+// 只关心会改变播放列表的事件：创建、删除和重命名（移入、移出）
+fn collect_paths(paths: &mut BTreeSet<PathBuf>, event: Event) {
+    if matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+    ) {
+        paths.extend(event.paths);
+    }
+}
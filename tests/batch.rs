@@ -0,0 +1,59 @@
+// 非终端输入的批处理模式：执行完管道中的命令后自动退出
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(20);
+
+// 用 ALSA 的 null 设备代替声卡，没有声卡的环境中也能运行。
+// 只有读取 $HOME/.asoundrc 的 ALSA 环境才有效，默认不运行：cargo test -- --ignored
+#[cfg(target_os = "linux")]
+#[test]
+#[ignore = "needs ALSA honouring $HOME/.asoundrc"]
+fn piped_commands_run_and_exit_at_eof() {
+    // 使用空的主目录和配置目录，不读取本机的配置和检查点
+    let home = std::env::temp_dir().join(format!("beatcli-batch-{}", std::process::id()));
+    std::fs::create_dir_all(&home).unwrap();
+    std::fs::write(home.join(".asoundrc"), "pcm.!default { type null }\n").unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_BeatCLI"))
+        .env("HOME", &home)
+        .env("XDG_CONFIG_HOME", &home)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("start BeatCLI");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"/volume 30; /mode\n/sleep 100ms\n/config\n")
+        .unwrap();
+    // 在另一个线程中读取输出，避免管道写满后程序阻塞
+    let mut out = child.stdout.take().unwrap();
+    let reader = std::thread::spawn(move || {
+        let mut text = String::new();
+        let _ = out.read_to_string(&mut text);
+        text
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if started.elapsed() > TIMEOUT {
+            let _ = child.kill();
+            panic!("BeatCLI did not exit after the end of its input");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    let stdout = reader.join().unwrap();
+    let _ = std::fs::remove_dir_all(&home);
+
+    assert!(status.success(), "exit status {:?}\n{}", status, stdout);
+    // /config 在 /sleep 之后执行，说明管道中的命令都执行完后才退出
+    assert!(stdout.contains("[playback]"), "{}", stdout);
+}